@@ -0,0 +1,93 @@
+use axum::{extract::State, response::Json, http::StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use beacon_storage::Database;
+use crate::server::AppState;
+
+#[derive(Deserialize)]
+pub struct CompactRequest {
+    /// Column family to compact; compacts all of them if omitted
+    pub cf: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckpointRequest {
+    pub path: String,
+}
+
+fn admin_error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message.into() })))
+}
+
+/// `GET /api/v1/admin/db` - current database size and the active
+/// `DatabaseConfig`, so operators can decide when to compact or checkpoint.
+pub async fn get_db_info(
+    State(state): State<AppState>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let size_info = state
+        .storage
+        .get_size_info()
+        .map_err(|e| admin_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({
+        "size": {
+            "total_size": size_info.total_size,
+            "cf_sizes": size_info.cf_sizes,
+        },
+        "read_cache": state.storage.cache_stats(),
+        "read_cache_entries_by_cf": state.storage.cache_entries_by_cf(),
+        "config": state.storage.config(),
+    })))
+}
+
+/// `POST /api/v1/admin/db/compact` - trigger a manual compaction of one
+/// column family, or of all of them if `cf` is omitted.
+pub async fn compact_db(
+    State(state): State<AppState>,
+    Json(payload): Json<CompactRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    match &payload.cf {
+        Some(cf) => state
+            .storage
+            .compact_cf(cf)
+            .map_err(|e| admin_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        None => state
+            .storage
+            .compact_all()
+            .map_err(|e| admin_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+    }
+
+    Ok(Json(json!({ "status": "compacted", "cf": payload.cf })))
+}
+
+/// `POST /api/v1/admin/db/checkpoint` - create an online RocksDB checkpoint
+/// (a hardlinked backup) at `path`, for online backups without restarting
+/// the node.
+pub async fn checkpoint_db(
+    State(state): State<AppState>,
+    Json(payload): Json<CheckpointRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    state
+        .storage
+        .create_checkpoint(&payload.path)
+        .map_err(|e| admin_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({ "status": "checkpoint_created", "path": payload.path })))
+}
+
+/// `POST /api/v1/admin/db/checkpoint/verify` - recompute a checkpoint's file
+/// hashes against its `manifest.json` and report any mismatches, so
+/// operators can trust a cold backup before restoring from it.
+pub async fn verify_checkpoint(
+    State(_state): State<AppState>,
+    Json(payload): Json<CheckpointRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mismatches = Database::verify_checkpoint(&payload.path)
+        .map_err(|e| admin_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(json!({
+        "path": payload.path,
+        "valid": mismatches.is_empty(),
+        "mismatches": mismatches,
+    })))
+}
@@ -1,12 +1,19 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Extension, State},
+    http::{header, HeaderMap, StatusCode},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
 use chrono::{Utc, Duration};
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::{rand_core::OsRng, SaltString};
+use beacon_core::{BeaconError, BeaconResult};
+use beacon_storage::{Database, Keys, StorageBackend, BatchOp, CF_METADATA};
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::middleware::auth::AuthenticatedUser;
 use crate::server::AppState;
 
 #[derive(Deserialize)]
@@ -29,6 +36,7 @@ pub struct Claims {
     pub sub: String, // Subject (user identifier)
     pub exp: usize,  // Expiration time
     pub iat: usize,  // Issued at
+    pub jti: String, // Unique token id, so it can be individually revoked
     pub role: String,
     pub permissions: Vec<String>,
     pub node_id: Option<String>,
@@ -42,198 +50,305 @@ pub struct UserInfo {
     pub last_login: String,
 }
 
-// JWT secret - in production this should come from environment or secure storage
-const JWT_SECRET: &[u8] = b"beacon_blockchain_jwt_secret_change_in_production";
+/// A user account, persisted with an argon2id password hash — the
+/// cleartext password is never stored or compared directly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UserRecord {
+    username: String,
+    password_hash: String,
+    role: String,
+    permissions: Vec<String>,
+}
+
+/// User accounts, stored under `CF_METADATA` keyed by `user:{username}`
+pub struct CredentialStore {
+    db: Arc<Database>,
+}
+
+impl CredentialStore {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Seed the well-known accounts on first run, if they don't already exist.
+    /// Later deployments should provision real accounts via `create_user`.
+    pub fn seed_default_users(&self) -> BeaconResult<()> {
+        let defaults: [(&str, &str, &str, &[&str]); 4] = [
+            ("admin", "admin123", "admin", &["read:blockchain", "write:transactions", "admin:node", "invoke:chaincode", "read:state", "write:state"]),
+            ("operator", "operator123", "operator", &["read:blockchain", "write:transactions", "invoke:chaincode", "read:state"]),
+            ("viewer", "viewer123", "viewer", &["read:blockchain", "read:state"]),
+            ("gateway", "gateway123", "gateway", &["read:blockchain", "write:transactions", "invoke:chaincode", "read:state", "gateway:heartbeat"]),
+        ];
+
+        for (username, password, role, permissions) in defaults {
+            if self.get_user(username)?.is_some() {
+                continue;
+            }
+            let permissions = permissions.iter().map(|p| p.to_string()).collect();
+            self.create_user(username, password, role, permissions)?;
+        }
+
+        Ok(())
+    }
+
+    /// Create or overwrite a user account with an argon2id-hashed password
+    pub fn create_user(&self, username: &str, password: &str, role: &str, permissions: Vec<String>) -> BeaconResult<()> {
+        let record = UserRecord {
+            username: username.to_string(),
+            password_hash: hash_password(password)?,
+            role: role.to_string(),
+            permissions,
+        };
+
+        let key = Keys::metadata(&format!("user:{}", username));
+        let data = serde_json::to_vec(&record)?;
+        self.db.put_cf(CF_METADATA, &key, &data)
+    }
+
+    fn get_user(&self, username: &str) -> BeaconResult<Option<UserRecord>> {
+        let key = Keys::metadata(&format!("user:{}", username));
+        match self.db.get_cf(CF_METADATA, &key)? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Verify a username/password pair against the stored argon2id hash
+    pub fn verify_credentials(&self, username: &str, password: &str) -> BeaconResult<Option<(String, Vec<String>)>> {
+        let Some(user) = self.get_user(username)? else {
+            return Ok(None);
+        };
+
+        let parsed_hash = PasswordHash::new(&user.password_hash)
+            .map_err(|e| BeaconError::crypto(format!("invalid stored password hash: {}", e)))?;
+
+        if Argon2::default().verify_password(password.as_bytes(), &parsed_hash).is_ok() {
+            Ok(Some((user.role, user.permissions)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn hash_password(password: &str) -> BeaconResult<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| BeaconError::crypto(format!("failed to hash password: {}", e)))
+}
+
+/// Blacklist of revoked token `jti`s, stored under `CF_METADATA` keyed by
+/// `revoked:{jti}` with the token's own expiration as the value, so expired
+/// entries can be pruned without tracking them separately
+pub struct TokenRevocationStore {
+    db: Arc<Database>,
+}
+
+impl TokenRevocationStore {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Blacklist `jti` until its token would have expired naturally
+    pub async fn revoke(&self, jti: &str, exp: usize) -> BeaconResult<()> {
+        let key = Keys::metadata(&format!("revoked:{}", jti));
+        StorageBackend::put(self.db.as_ref(), CF_METADATA, &key, &exp.to_le_bytes()).await
+    }
+
+    pub async fn is_revoked(&self, jti: &str) -> BeaconResult<bool> {
+        let key = Keys::metadata(&format!("revoked:{}", jti));
+        Ok(StorageBackend::get(self.db.as_ref(), CF_METADATA, &key).await?.is_some())
+    }
+
+    /// Drop blacklist entries whose token has already expired naturally —
+    /// called from the node's periodic maintenance task
+    pub async fn prune_expired(&self) -> BeaconResult<u64> {
+        let now = Utc::now().timestamp() as usize;
+        let prefix = Keys::metadata("revoked:");
+        let entries = StorageBackend::scan_prefix(self.db.as_ref(), CF_METADATA, &prefix).await?;
+
+        let mut expired = Vec::new();
+        for (key, value) in entries {
+            let exp_bytes: [u8; 8] = value
+                .as_slice()
+                .try_into()
+                .map_err(|_| BeaconError::storage("invalid revocation entry"))?;
+            if (usize::from_le_bytes(exp_bytes)) < now {
+                expired.push(key);
+            }
+        }
+
+        let pruned = expired.len() as u64;
+        if pruned > 0 {
+            let ops = expired.into_iter().map(|key| BatchOp::delete(CF_METADATA, key)).collect();
+            StorageBackend::batch(self.db.as_ref(), ops).await?;
+        }
+
+        Ok(pruned)
+    }
+}
 
 /// User login endpoint
 pub async fn login(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(login_request): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, StatusCode> {
-    // Validate credentials (simplified - in production would validate against secure user store)
-    let (role, permissions) = match validate_credentials(&login_request.username, &login_request.password) {
-        Some((role, perms)) => (role, perms),
-        None => return Err(StatusCode::UNAUTHORIZED),
-    };
-    
-    // Create JWT claims
-    let now = Utc::now();
-    let expires_at = now + Duration::hours(24); // Token expires in 24 hours
-    
-    let claims = Claims {
-        sub: login_request.username.clone(),
-        exp: expires_at.timestamp() as usize,
-        iat: now.timestamp() as usize,
-        role: role.clone(),
-        permissions: permissions.clone(),
-        node_id: login_request.node_id.clone(),
-    };
-    
-    // Generate JWT token
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(JWT_SECRET),
-    ).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+    let (role, permissions) = state
+        .credential_store
+        .verify_credentials(&login_request.username, &login_request.password)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let (token, expires_at) = issue_token(&state, login_request.username.clone(), role.clone(), permissions.clone(), login_request.node_id.clone())?;
+
     let user_info = UserInfo {
         username: login_request.username,
         role,
         node_id: login_request.node_id,
-        last_login: now.to_rfc3339(),
+        last_login: Utc::now().to_rfc3339(),
     };
-    
-    let response = LoginResponse {
+
+    Ok(Json(LoginResponse {
         token,
         expires_at: expires_at.to_rfc3339(),
         user: user_info,
         permissions,
+    }))
+}
+
+/// Issue a signed JWT for `sub`, returning the token and its expiration
+fn issue_token(
+    state: &AppState,
+    sub: String,
+    role: String,
+    permissions: Vec<String>,
+    node_id: Option<String>,
+) -> Result<(String, chrono::DateTime<Utc>), StatusCode> {
+    let now = Utc::now();
+    let expires_at = now + Duration::hours(24);
+
+    let claims = Claims {
+        sub,
+        exp: expires_at.timestamp() as usize,
+        iat: now.timestamp() as usize,
+        jti: Uuid::new_v4().to_string(),
+        role,
+        permissions,
+        node_id,
     };
-    
-    Ok(Json(response))
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(&state.jwt_secret),
+    ).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((token, expires_at))
 }
 
-/// Verify JWT token
-pub async fn verify_token(token: &str) -> Result<Claims, StatusCode> {
-    let validation = Validation::default();
-    
-    match decode::<Claims>(
+/// Verify a JWT token: signature, expiration, and that its `jti` hasn't
+/// been revoked
+pub async fn verify_token(state: &AppState, token: &str) -> Result<Claims, StatusCode> {
+    let token_data = decode::<Claims>(
         token,
-        &DecodingKey::from_secret(JWT_SECRET),
-        &validation,
-    ) {
-        Ok(token_data) => {
-            // Check if token is expired
-            let now = Utc::now().timestamp() as usize;
-            if token_data.claims.exp < now {
-                return Err(StatusCode::UNAUTHORIZED);
-            }
-            
-            Ok(token_data.claims)
-        }
-        Err(_) => Err(StatusCode::UNAUTHORIZED),
+        &DecodingKey::from_secret(&state.jwt_secret),
+        &Validation::default(),
+    ).map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let claims = token_data.claims;
+
+    let now = Utc::now().timestamp() as usize;
+    if claims.exp < now {
+        return Err(StatusCode::UNAUTHORIZED);
     }
+
+    if state.revocation_store.is_revoked(&claims.jti).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)? {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(claims)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, StatusCode> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)
 }
 
 /// Get current user info from token
 pub async fn get_user_info(
-    State(_state): State<AppState>,
+    Extension(auth_user): Extension<AuthenticatedUser>,
 ) -> Result<Json<UserInfo>, StatusCode> {
-    // In a real implementation, extract user from authenticated request
     let user_info = UserInfo {
-        username: "authenticated_user".to_string(),
-        role: "admin".to_string(),
-        node_id: None,
-        last_login: chrono::Utc::now().to_rfc3339(),
+        username: auth_user.claims.sub,
+        role: auth_user.claims.role,
+        node_id: auth_user.claims.node_id,
+        last_login: Utc::now().to_rfc3339(),
     };
-    
+
     Ok(Json(user_info))
 }
 
-/// Logout endpoint (token blacklisting would be implemented here)
-pub async fn logout() -> Result<Json<Value>, StatusCode> {
-    // In a production system, you would add the token to a blacklist
+/// Logout endpoint: revokes the presented token's `jti` so it can no longer
+/// be used, even though it hasn't expired yet
+pub async fn logout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, StatusCode> {
+    let token = bearer_token(&headers)?;
+    let claims = verify_token(&state, token).await?;
+
+    state
+        .revocation_store
+        .revoke(&claims.jti, claims.exp)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(Json(serde_json::json!({
         "message": "Logged out successfully",
         "timestamp": Utc::now().to_rfc3339()
     })))
 }
 
-/// Refresh token endpoint
+/// Refresh token endpoint: validates the presented token, issues a new one,
+/// and revokes the old `jti` so a stolen token can't be replayed after rotation
 pub async fn refresh_token(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Result<Json<LoginResponse>, StatusCode> {
-    // In a real implementation, extract current claims from authenticated request
-    let username = "authenticated_user".to_string();
-    let role = "admin".to_string();
-    let permissions = vec![
-        "read:blockchain".to_string(),
-        "write:transactions".to_string(),
-        "admin:node".to_string(),
-        "invoke:chaincode".to_string(),
-        "read:state".to_string(),
-        "write:state".to_string(),
-    ];
-    
-    // Generate new token with extended expiration
-    let now = Utc::now();
-    let expires_at = now + Duration::hours(24);
-    
-    let new_claims = Claims {
-        sub: username.clone(),
-        exp: expires_at.timestamp() as usize,
-        iat: now.timestamp() as usize,
-        role: role.clone(),
-        permissions: permissions.clone(),
-        node_id: None,
-    };
-    
-    let token = encode(
-        &Header::default(),
-        &new_claims,
-        &EncodingKey::from_secret(JWT_SECRET),
-    ).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+    let token = bearer_token(&headers)?;
+    let claims = verify_token(&state, token).await?;
+
+    let (new_token, expires_at) = issue_token(
+        &state,
+        claims.sub.clone(),
+        claims.role.clone(),
+        claims.permissions.clone(),
+        claims.node_id.clone(),
+    )?;
+
+    state
+        .revocation_store
+        .revoke(&claims.jti, claims.exp)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     let user_info = UserInfo {
-        username,
-        role,
-        node_id: None,
-        last_login: now.to_rfc3339(),
+        username: claims.sub,
+        role: claims.role,
+        node_id: claims.node_id,
+        last_login: Utc::now().to_rfc3339(),
     };
-    
-    let response = LoginResponse {
-        token,
+
+    Ok(Json(LoginResponse {
+        token: new_token,
         expires_at: expires_at.to_rfc3339(),
+        permissions: claims.permissions,
         user: user_info,
-        permissions,
-    };
-    
-    Ok(Json(response))
-}
-
-/// Validate user credentials (simplified implementation)
-fn validate_credentials(username: &str, password: &str) -> Option<(String, Vec<String>)> {
-    // In production, this would validate against a secure user database
-    match (username, password) {
-        ("admin", "admin123") => Some((
-            "admin".to_string(),
-            vec![
-                "read:blockchain".to_string(),
-                "write:transactions".to_string(),
-                "admin:node".to_string(),
-                "invoke:chaincode".to_string(),
-                "read:state".to_string(),
-                "write:state".to_string(),
-            ]
-        )),
-        ("operator", "operator123") => Some((
-            "operator".to_string(),
-            vec![
-                "read:blockchain".to_string(),
-                "write:transactions".to_string(),
-                "invoke:chaincode".to_string(),
-                "read:state".to_string(),
-            ]
-        )),
-        ("viewer", "viewer123") => Some((
-            "viewer".to_string(),
-            vec![
-                "read:blockchain".to_string(),
-                "read:state".to_string(),
-            ]
-        )),
-        ("gateway", "gateway123") => Some((
-            "gateway".to_string(),
-            vec![
-                "read:blockchain".to_string(),
-                "write:transactions".to_string(),
-                "invoke:chaincode".to_string(),
-                "read:state".to_string(),
-                "gateway:heartbeat".to_string(),
-            ]
-        )),
-        _ => None,
-    }
+    }))
 }
 
 /// Check if user has specific permission
@@ -242,7 +357,7 @@ pub fn has_permission(claims: &Claims, required_permission: &str) -> bool {
     if claims.role == "admin" {
         return true;
     }
-    
+
     // Check specific permission
     claims.permissions.contains(&required_permission.to_string())
 }
@@ -257,6 +372,7 @@ pub async fn get_api_key_info(
             sub: "api_admin".to_string(),
             exp: (Utc::now() + Duration::days(365)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            jti: Uuid::new_v4().to_string(),
             role: "admin".to_string(),
             permissions: vec![
                 "read:blockchain".to_string(),
@@ -272,6 +388,7 @@ pub async fn get_api_key_info(
             sub: "api_gateway".to_string(),
             exp: (Utc::now() + Duration::days(30)).timestamp() as usize,
             iat: Utc::now().timestamp() as usize,
+            jti: Uuid::new_v4().to_string(),
             role: "gateway".to_string(),
             permissions: vec![
                 "read:blockchain".to_string(),
@@ -285,3 +402,19 @@ pub async fn get_api_key_info(
         _ => None,
     }
 }
+
+/// Load the JWT signing secret from the environment at startup. Falls back
+/// to a freshly generated secret (logged loudly) so a missing env var fails
+/// safe rather than reusing a known default across deployments.
+pub fn load_jwt_secret() -> Vec<u8> {
+    match std::env::var("BEACON_JWT_SECRET") {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => {
+            tracing::warn!("BEACON_JWT_SECRET not set; generating an ephemeral signing secret for this run. Tokens will not survive a restart.");
+            let mut secret = vec![0u8; 32];
+            use rand::RngCore;
+            rand::rngs::OsRng.fill_bytes(&mut secret);
+            secret
+        }
+    }
+}
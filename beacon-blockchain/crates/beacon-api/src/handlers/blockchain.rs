@@ -197,7 +197,7 @@ pub async fn get_blockchain_info(
             }),
             serde_json::json!({
                 "address": "beacon1qzx2vwy3nw8xv2ljr7d8y9k5m8n4r6t7u8v9w0x",
-                "voting_power": "180000", 
+                "voting_power": "180000",
                 "commission": "3%",
                 "status": "active",
                 "uptime": "99.9%"
@@ -205,14 +205,30 @@ pub async fn get_blockchain_info(
             serde_json::json!({
                 "address": "beacon1qa1s2d3f4g5h6j7k8l9z0x1c2v3b4n5m6q7w8e9r",
                 "voting_power": "150000",
-                "commission": "7%", 
+                "commission": "7%",
                 "status": "active",
                 "uptime": "98.5%"
             })
         ];
-        
+
         info["validators"] = serde_json::json!(validators);
     }
-    
+
     Ok(Json(info))
 }
+
+/// Get the genesis block and network identity, used by new nodes to
+/// validate they're bootstrapping into the right network.
+pub async fn get_genesis_block(State(_state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    Ok(Json(serde_json::json!({
+        "network_id": "beacon_devnet",
+        "genesis_hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "block": {
+            "number": 0,
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "parent_hash": Value::Null,
+            "timestamp": "1970-01-01T00:00:00Z",
+            "validator": "genesis"
+        }
+    })))
+}
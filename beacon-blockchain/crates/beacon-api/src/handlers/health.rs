@@ -1,14 +1,106 @@
-use axum::{http::StatusCode, response::Json};
-use serde_json::Value;
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use crate::server::AppState;
 
-/// Simple health check endpoint
-pub async fn health_check() -> Result<Json<Value>, StatusCode> {
-    let response = serde_json::json!({
-        "status": "healthy",
+/// How long a single subsystem probe gets before it's counted as down -
+/// a wedged dependency shouldn't hang the readiness endpoint itself.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize)]
+struct SubsystemStatus {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl SubsystemStatus {
+    fn up() -> Self {
+        Self { status: "up", detail: None }
+    }
+
+    fn down(detail: impl Into<String>) -> Self {
+        Self { status: "down", detail: Some(detail.into()) }
+    }
+
+    fn is_up(&self) -> bool {
+        self.status == "up"
+    }
+}
+
+/// `GET /health/live` - is the process up and serving requests at all. Never
+/// touches a dependency, so it can't itself wedge on one; a load balancer or
+/// orchestrator uses this to decide whether to restart the process.
+pub async fn liveness() -> Json<Value> {
+    Json(json!({
+        "status": "alive",
         "timestamp": chrono::Utc::now().to_rfc3339(),
         "service": "beacon-api",
-        "version": "1.0.0"
+    }))
+}
+
+/// `GET /health/ready` - is the node actually able to serve traffic: probes
+/// `Database`, `StateStorage`, and chaincode executor reachability (all
+/// critical - any one down fails the probe), plus the current peer count
+/// (informational only - a fresh single-node devnet legitimately has no
+/// peers yet, so this never fails the probe by itself). Returns 503 when a
+/// critical dependency is down, so a load balancer or orchestrator pulls
+/// this instance out of rotation instead of routing traffic it can't serve.
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let database = check_database(&state).await;
+    let state_storage = check_state_storage(&state).await;
+    let chaincode = check_chaincode(&state).await;
+    let peer_count = state.peer_count.load(std::sync::atomic::Ordering::Relaxed);
+
+    let critical_up = database.is_up() && state_storage.is_up() && chaincode.is_up();
+    let overall_status = if critical_up { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    let body = json!({
+        "status": if critical_up { "ready" } else { "not_ready" },
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "checks": {
+            "database": database,
+            "state_storage": state_storage,
+            "chaincode": chaincode,
+            "peers": {
+                "status": if peer_count > 0 { "up" } else { "degraded" },
+                "count": peer_count,
+            },
+        },
     });
-    
-    Ok(Json(response))
+
+    (overall_status, Json(body))
+}
+
+/// Probe the database with a trivial read that exercises the RocksDB handle
+/// without mutating anything.
+async fn check_database(state: &AppState) -> SubsystemStatus {
+    let storage = state.storage.clone();
+    let probe = tokio::task::spawn_blocking(move || storage.get_stats());
+    match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(_)) => SubsystemStatus::up(),
+        Ok(Err(e)) => SubsystemStatus::down(format!("probe task panicked: {e}")),
+        Err(_) => SubsystemStatus::down("timed out"),
+    }
+}
+
+/// Probe state storage by reading the current state version, which forces a
+/// real round-trip through the underlying storage backend.
+async fn check_state_storage(state: &AppState) -> SubsystemStatus {
+    match tokio::time::timeout(PROBE_TIMEOUT, state.state_storage.state_version()).await {
+        Ok(Ok(_)) => SubsystemStatus::up(),
+        Ok(Err(e)) => SubsystemStatus::down(e.to_string()),
+        Err(_) => SubsystemStatus::down("timed out"),
+    }
+}
+
+/// Probe the chaincode executor by reading its active-execution count,
+/// which round-trips through the lock `ChaincodeExecutor` uses to track
+/// in-flight invocations.
+async fn check_chaincode(state: &AppState) -> SubsystemStatus {
+    match tokio::time::timeout(PROBE_TIMEOUT, state.chaincode_executor.get_active_count()).await {
+        Ok(_) => SubsystemStatus::up(),
+        Err(_) => SubsystemStatus::down("timed out"),
+    }
 }
@@ -0,0 +1,92 @@
+use axum::{extract::State, response::Json, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::server::AppState;
+
+#[derive(Deserialize)]
+pub struct KvPutOp {
+    pub cf: String,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+pub struct KvDeleteOp {
+    pub cf: String,
+    pub key: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct KvBatchWrite {
+    #[serde(default)]
+    pub puts: Vec<KvPutOp>,
+    #[serde(default)]
+    pub deletes: Vec<KvDeleteOp>,
+}
+
+#[derive(Deserialize)]
+pub struct KvRangeQuery {
+    pub cf: String,
+    pub prefix: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    #[serde(default = "default_kv_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+fn default_kv_limit() -> usize {
+    100
+}
+
+#[derive(Deserialize, Default)]
+pub struct KvBatchRequest {
+    #[serde(default)]
+    pub write: KvBatchWrite,
+    #[serde(default)]
+    pub reads: Vec<KvRangeQuery>,
+}
+
+#[derive(Serialize)]
+pub struct KvRangeResult {
+    pub cf: String,
+    pub items: Vec<(String, String)>,
+    pub next_start: Option<String>,
+}
+
+/// Submit a batch of puts/deletes and a batch of bounded range reads in one
+/// round-trip, K2V-style. Fetches e.g. all `tx_block:{index}:*` entries for
+/// several blocks at once instead of paying a request per range.
+/// This handler does not touch storage: `written` is the number of submitted
+/// puts and deletes, and each range returns up to three placeholder entries.
+pub async fn batch(
+    State(_state): State<AppState>,
+    Json(payload): Json<KvBatchRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    // Mock batch read/write
+    let written = payload.write.puts.len() + payload.write.deletes.len();
+
+    let results: Vec<KvRangeResult> = payload
+        .reads
+        .into_iter()
+        .map(|query| {
+            let limit = query.limit.min(100);
+            let items = (0..limit.min(3))
+                .map(|i| {
+                    (
+                        format!("{}{}", query.prefix, i),
+                        format!("mock_value_for_{}{}", query.prefix, i),
+                    )
+                })
+                .collect();
+
+            KvRangeResult { cf: query.cf, items, next_start: None }
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "written": written,
+        "results": results,
+    })))
+}
@@ -1,6 +1,11 @@
 // API handlers for different blockchain operations
 pub mod health;
 pub mod blockchain;
+pub mod network;
+pub mod subscribe;
 pub mod transactions;
 pub mod state;
+pub mod kv;
+pub mod admin;
 pub mod auth;
+pub mod rpc;
@@ -0,0 +1,35 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde_json::Value;
+use crate::server::AppState;
+
+/// Get the node's currently connected peers, for new nodes bootstrapping
+/// their peer list over HTTP instead of (or in addition to) discovery.
+/// Returns a fixed placeholder list; it is not read from the networking layer.
+pub async fn get_peers(State(_state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    Ok(Json(serde_json::json!({
+        "peers": [
+            "/ip4/10.0.0.2/tcp/30303/p2p/12D3KooWHg7WJvJQ3vQbFJLxXQZQKVJr7c8T2h5cq8Q6bVvQJwYz",
+            "/ip4/10.0.0.3/tcp/30303/p2p/12D3KooWMh9Xw4r6k8r8f2q5n8hQ2vQbFJLxXQZQKVJr7c8T2h5c"
+        ]
+    })))
+}
+
+/// Combined bootstrap bundle for a fresh node joining the network: this
+/// node's known-good peers plus the latest finalized block, Lighthouse
+/// `Bootstrapper`-style so a new node can seed its peer list and sanity-check
+/// chain height without hand-configured multiaddrs. Consumed by
+/// `beacon_networking::discovery::Bootstrapper`. The peers and the finalized
+/// block are fixed placeholder values, not read from the node's live state.
+pub async fn get_bootstrap_info(State(_state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    Ok(Json(serde_json::json!({
+        "network_id": "beacon_devnet",
+        "peers": [
+            "/ip4/10.0.0.2/tcp/30303/p2p/12D3KooWHg7WJvJQ3vQbFJLxXQZQKVJr7c8T2h5cq8Q6bVvQJwYz",
+            "/ip4/10.0.0.3/tcp/30303/p2p/12D3KooWMh9Xw4r6k8r8f2q5n8hQ2vQbFJLxXQZQKVJr7c8T2h5c"
+        ],
+        "finalized_block": {
+            "hash": format!("0x{:064x}", 1000u64 * 1234567890),
+            "height": 1000
+        }
+    })))
+}
@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::server::AppState;
+
+use super::blockchain::{get_block, get_block_by_hash, get_blockchain_info, BlockQuery};
+use super::transactions::get_transaction;
+
+/// A single Ethereum-style JSON-RPC 2.0 call
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcRequest {
+    #[serde(default)]
+    pub jsonrpc: Option<String>,
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A single JSON-RPC 2.0 response, mirroring `JsonRpcRequest`'s `id`
+#[derive(Debug, Serialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonRpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl JsonRpcResponse {
+    fn ok(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    fn method_not_found(id: Value, method: &str) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32601,
+                message: format!("Method not found: {}", method),
+            }),
+        }
+    }
+
+    fn invalid_params(id: Value, detail: &str) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32602,
+                message: format!("Invalid params: {}", detail),
+            }),
+        }
+    }
+}
+
+/// JSON-RPC 2.0 endpoint mirroring the existing REST block/transaction
+/// handlers under Ethereum-compatible method names, so existing Ethereum
+/// tooling and wallets can talk to BEACON without a custom client. Accepts
+/// either a single request object or a batch (array of request objects),
+/// per the JSON-RPC 2.0 spec.
+pub async fn rpc(State(state): State<AppState>, Json(body): Json<Value>) -> Json<Value> {
+    match body {
+        Value::Array(requests) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(dispatch_raw(&state, request).await);
+            }
+            Json(Value::Array(responses))
+        }
+        single => Json(dispatch_raw(&state, single).await),
+    }
+}
+
+async fn dispatch_raw(state: &AppState, raw: Value) -> Value {
+    let id = raw.get("id").cloned().unwrap_or(Value::Null);
+    let request: JsonRpcRequest = match serde_json::from_value(raw) {
+        Ok(request) => request,
+        Err(e) => {
+            return serde_json::to_value(JsonRpcResponse::invalid_params(id, &e.to_string()))
+                .expect("JsonRpcResponse always serializes");
+        }
+    };
+
+    let response = dispatch(state, request).await;
+    serde_json::to_value(response).expect("JsonRpcResponse always serializes")
+}
+
+async fn dispatch(state: &AppState, request: JsonRpcRequest) -> JsonRpcResponse {
+    let id = request.id;
+    let params = request.params.as_array().cloned().unwrap_or_default();
+
+    match request.method.as_str() {
+        "eth_blockNumber" => {
+            let Ok(Json(info)) = get_blockchain_info(State(state.clone()), Query(Default::default())).await else {
+                return JsonRpcResponse::invalid_params(id, "failed to load blockchain info");
+            };
+            let Some(number) = info["latest_block"]["number"].as_u64() else {
+                return JsonRpcResponse::invalid_params(id, "blockchain info missing latest block number");
+            };
+            JsonRpcResponse::ok(id, Value::String(format!("0x{:x}", number)))
+        }
+
+        "eth_getBlockByNumber" => {
+            let Some(number_param) = params.first() else {
+                return JsonRpcResponse::invalid_params(id, "expected [blockNumber, fullTransactions]");
+            };
+            let Some(block_number) = parse_block_number(number_param) else {
+                return JsonRpcResponse::invalid_params(id, "blockNumber must be a hex string, decimal, or \"latest\"");
+            };
+            let include_transactions = params.get(1).and_then(Value::as_bool).unwrap_or(false);
+            let query = BlockQuery { include_transactions: Some(include_transactions), include_validators: None };
+            match get_block(State(state.clone()), Path(block_number), Query(query)).await {
+                Ok(Json(block)) => JsonRpcResponse::ok(id, block),
+                Err(_) => JsonRpcResponse::invalid_params(id, "block not found"),
+            }
+        }
+
+        "eth_getBlockByHash" => {
+            let Some(hash) = params.first().and_then(Value::as_str) else {
+                return JsonRpcResponse::invalid_params(id, "expected [blockHash, fullTransactions]");
+            };
+            let include_transactions = params.get(1).and_then(Value::as_bool).unwrap_or(false);
+            let query = BlockQuery { include_transactions: Some(include_transactions), include_validators: None };
+            match get_block_by_hash(State(state.clone()), Path(hash.to_string()), Query(query)).await {
+                Ok(Json(block)) => JsonRpcResponse::ok(id, block),
+                Err(_) => JsonRpcResponse::invalid_params(id, "block not found"),
+            }
+        }
+
+        "eth_getTransactionByHash" => {
+            let Some(hash) = params.first().and_then(Value::as_str) else {
+                return JsonRpcResponse::invalid_params(id, "expected [transactionHash]");
+            };
+            match get_transaction(State(state.clone()), Path(hash.to_string())).await {
+                Ok(Json(transaction)) => JsonRpcResponse::ok(id, transaction),
+                Err(_) => JsonRpcResponse::invalid_params(id, "transaction not found"),
+            }
+        }
+
+        "beacon_getChainInfo" => {
+            let include_validators = params.first().and_then(Value::as_bool).unwrap_or(false);
+            let mut query = std::collections::HashMap::new();
+            if include_validators {
+                query.insert("include_validators".to_string(), "true".to_string());
+            }
+            match get_blockchain_info(State(state.clone()), Query(query)).await {
+                Ok(Json(info)) => JsonRpcResponse::ok(id, info),
+                Err(_) => JsonRpcResponse::invalid_params(id, "failed to load blockchain info"),
+            }
+        }
+
+        method => JsonRpcResponse::method_not_found(id, method),
+    }
+}
+
+/// Parse an `eth_getBlockByNumber`-style block number: a `0x`-prefixed hex
+/// string, a plain decimal string, or one of the tags `"latest"`/`"pending"`
+/// (both mapped to this mock chain's single head, block 1000) or
+/// `"earliest"` (block 0).
+fn parse_block_number(value: &Value) -> Option<u64> {
+    match value.as_str()? {
+        "latest" | "pending" => Some(1000),
+        "earliest" => Some(0),
+        hex if hex.starts_with("0x") => u64::from_str_radix(&hex[2..], 16).ok(),
+        decimal => decimal.parse().ok(),
+    }
+}
@@ -1,4 +1,4 @@
-use axum::{extract::{Query, State}, response::Json, http::StatusCode};
+use axum::{extract::{Path, Query, State}, response::Json, http::StatusCode};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use chrono::Utc;
@@ -9,6 +9,9 @@ pub struct StateQuery {
     pub key: String,
     pub chaincode_id: Option<String>,
     pub channel_id: Option<String>,
+    /// When true, include a merkle inclusion proof against the current
+    /// `StateStorage::state_root()` alongside the value.
+    pub proof: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -17,6 +20,11 @@ pub struct StateRangeQuery {
     pub end_key: String,
     pub chaincode_id: Option<String>,
     pub limit: Option<u32>,
+    /// Opaque cursor from a previous response's `next_cursor`; resumes
+    /// scanning strictly after that key instead of starting at `start_key`.
+    pub cursor: Option<String>,
+    /// Case-sensitive substring match applied to each entry's value
+    pub value_filter: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -26,84 +34,196 @@ pub struct StateHistoryQuery {
     pub limit: Option<u32>,
     pub from_block: Option<u64>,
     pub to_block: Option<u64>,
+    /// Opaque cursor from a previous response's `next_cursor`
+    pub cursor: Option<String>,
+    /// Case-sensitive substring match applied to each entry's value
+    pub value_filter: Option<String>,
 }
 
 pub async fn get_state(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<StateQuery>,
 ) -> Result<Json<Value>, StatusCode> {
-    // Mock state retrieval
+    let wants_proof = params.proof.unwrap_or(false);
+
+    let body = if wants_proof {
+        let Some((value, proof, root)) = state
+            .state_storage
+            .state_proof(&params.key)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        else {
+            return Err(StatusCode::NOT_FOUND);
+        };
+
+        json!({
+            "key": params.key,
+            "value": String::from_utf8_lossy(&value),
+            "chaincode_id": params.chaincode_id.unwrap_or("default".to_string()),
+            "channel_id": params.channel_id.unwrap_or("default".to_string()),
+            "proof": {
+                "state_root": root,
+                "path": proof,
+            },
+        })
+    } else {
+        let Some(value) = state
+            .state_storage
+            .get_state(&params.key)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        else {
+            return Err(StatusCode::NOT_FOUND);
+        };
+
+        json!({
+            "key": params.key,
+            "value": String::from_utf8_lossy(&value),
+            "chaincode_id": params.chaincode_id.unwrap_or("default".to_string()),
+            "channel_id": params.channel_id.unwrap_or("default".to_string()),
+        })
+    };
+
+    Ok(Json(body))
+}
+
+#[derive(Deserialize)]
+pub struct StateSmtProofQuery {
+    pub chaincode_id: Option<String>,
+    pub channel_id: Option<String>,
+}
+
+/// Trustless state read: a key's current value (or its absence) plus a
+/// sparse Merkle proof against `StateStorage::state_smt_root()`, the same
+/// commitment recorded in `BlockHeader.metadata`'s `"state_smt_root"` entry.
+/// Unlike `get_state`'s `?proof=true`, a `value: null` response here is
+/// itself a proven fact - the proof verifies the key's absence from state.
+pub async fn get_state_smt_proof(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Query(params): Query<StateSmtProofQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let (proof, root) = state
+        .state_storage
+        .state_smt_proof(&key)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(Json(json!({
-        "key": params.key,
-        "value": format!("mock_value_for_{}", params.key),
+        "key": key,
+        "value": proof.value.as_deref().map(String::from_utf8_lossy),
         "chaincode_id": params.chaincode_id.unwrap_or("default".to_string()),
         "channel_id": params.channel_id.unwrap_or("default".to_string()),
-        "block_number": 12345,
-        "transaction_id": "mock_tx_id",
-        "timestamp": Utc::now().to_rfc3339(),
-        "version": {
-            "block_num": 12345,
-            "tx_num": 1
-        }
+        "proof": {
+            "state_smt_root": root,
+            "siblings": proof.siblings,
+        },
     })))
 }
 
 pub async fn query_state(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<StateRangeQuery>,
 ) -> Result<Json<Value>, StatusCode> {
-    let limit = params.limit.unwrap_or(10).min(100);
-    
-    // Mock state range query
-    let mut results = Vec::new();
-    for i in 0..limit {
-        let key = format!("{}_{}", params.start_key, i);
-        results.push(json!({
-            "key": key,
-            "value": format!("mock_value_for_{}", key),
-            "chaincode_id": params.chaincode_id.as_ref().unwrap_or(&"default".to_string()),
-            "block_number": 12340 + i,
-            "transaction_id": format!("mock_tx_{}", i),
-            "timestamp": Utc::now().to_rfc3339()
-        }));
-    }
-    
+    let limit = params.limit.unwrap_or(10).min(100) as usize;
+
+    let entries = state
+        .state_storage
+        .get_state_range(&params.start_key, &params.end_key)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let filtered: Vec<(Vec<u8>, Vec<u8>)> = entries
+        .into_iter()
+        .filter(|(_, value)| matches_value_filter(value, params.value_filter.as_deref()))
+        .collect();
+
+    let (page, has_more) = paginate_by_cursor(filtered, params.cursor.as_deref(), limit);
+    let next_cursor = has_more
+        .then(|| page.last().map(|(key, _)| String::from_utf8_lossy(key).to_string()))
+        .flatten();
+
+    let results: Vec<Value> = page
+        .into_iter()
+        .map(|(key, value)| {
+            json!({
+                "key": String::from_utf8_lossy(&key),
+                "value": String::from_utf8_lossy(&value),
+                "chaincode_id": params.chaincode_id.as_ref().unwrap_or(&"default".to_string()),
+            })
+        })
+        .collect();
+
     Ok(Json(json!({
         "results": results,
         "range": {
             "start_key": params.start_key,
             "end_key": params.end_key,
-            "limit": limit
+            "limit": limit,
+            "value_filter": params.value_filter,
         },
-        "has_more": limit == 100
+        "next_cursor": next_cursor,
+        "has_more": has_more
     })))
 }
 
 pub async fn get_state_history(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<StateHistoryQuery>,
 ) -> Result<Json<Value>, StatusCode> {
     let limit = params.limit.unwrap_or(10).min(100);
-    
-    // Mock state history
-    let mut history = Vec::new();
-    for i in 0..limit {
-        let block_num = 12340 + i as u64;
-        history.push(json!({
-            "key": params.key,
-            "value": format!("historical_value_{}_{}", params.key, i),
-            "chaincode_id": params.chaincode_id.as_ref().unwrap_or(&"default".to_string()),
-            "block_number": block_num,
-            "transaction_id": format!("historical_tx_{}", i),
-            "timestamp": Utc::now().to_rfc3339(),
-            "is_delete": false,
-            "version": {
-                "block_num": block_num,
-                "tx_num": 1
+
+    // StateStorage doesn't keep a per-key version log today - only the
+    // last-write `(block_index, timestamp)` recorded for relative-timelock
+    // checks (`StateStorage::key_provenance`) and the undo journal needed
+    // for a reorg, keyed by block hash rather than by state key. Until a
+    // real version log exists, the only history we can honestly report is
+    // the current value, filtered by `block_range`/`value_filter` and
+    // gated by `cursor` so a client paging forward sees it exactly once.
+    let history = if params.cursor.is_some() {
+        Vec::new()
+    } else {
+        match state
+            .state_storage
+            .get_state(&params.key)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        {
+            Some(value) => {
+                let last_block = state
+                    .state_storage
+                    .key_provenance(&params.key)
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                    .map(|(index, _)| index);
+
+                let in_block_range = match last_block {
+                    Some(block) => {
+                        params.from_block.map(|from| block >= from).unwrap_or(true)
+                            && params.to_block.map(|to| block <= to).unwrap_or(true)
+                    }
+                    // No recorded write for this key (e.g. genesis state) -
+                    // only an unbounded query can honestly include it.
+                    None => params.from_block.is_none() && params.to_block.is_none(),
+                };
+
+                if in_block_range && matches_value_filter(&value, params.value_filter.as_deref()) {
+                    vec![json!({
+                        "key": params.key,
+                        "value": String::from_utf8_lossy(&value),
+                        "chaincode_id": params.chaincode_id.as_ref().unwrap_or(&"default".to_string()),
+                        "is_delete": false,
+                        "block": last_block,
+                        "timestamp": Utc::now().to_rfc3339(),
+                    })]
+                } else {
+                    Vec::new()
+                }
             }
-        }));
-    }
-    
+            None => Vec::new(),
+        }
+    };
+
     Ok(Json(json!({
         "key": params.key,
         "history": history,
@@ -111,7 +231,38 @@ pub async fn get_state_history(
             "limit": limit,
             "from_block": params.from_block,
             "to_block": params.to_block,
+            "value_filter": params.value_filter,
+            "next_cursor": Value::Null,
             "has_more": false
         }
     })))
 }
+
+/// `true` if `needle` is absent, or is a substring of `value` decoded lossily as UTF-8.
+fn matches_value_filter(value: &[u8], needle: Option<&str>) -> bool {
+    needle
+        .map(|needle| String::from_utf8_lossy(value).contains(needle))
+        .unwrap_or(true)
+}
+
+/// Skip past `cursor` (exclusive) in an already key-sorted list, then return
+/// at most `limit` entries plus whether more remain beyond them - the state
+/// API's opaque-cursor counterpart to `StateStorage`'s internal `paginate`.
+fn paginate_by_cursor<V>(
+    entries: Vec<(Vec<u8>, V)>,
+    cursor: Option<&str>,
+    limit: usize,
+) -> (Vec<(Vec<u8>, V)>, bool) {
+    let start_index = match cursor {
+        Some(after) => entries
+            .iter()
+            .position(|(key, _)| key.as_slice() > after.as_bytes())
+            .unwrap_or(entries.len()),
+        None => 0,
+    };
+
+    let mut page: Vec<(Vec<u8>, V)> = entries.into_iter().skip(start_index).collect();
+    let has_more = page.len() > limit;
+    page.truncate(limit);
+    (page, has_more)
+}
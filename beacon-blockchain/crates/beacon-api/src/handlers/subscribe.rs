@@ -0,0 +1,86 @@
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    extract::{Query, State},
+    response::sse::{Event, Sse},
+    response::IntoResponse,
+};
+use futures::stream::Stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+
+use crate::server::AppState;
+use crate::subscriptions::SubscriptionEvent;
+
+/// Query params narrowing the push feed to events the client cares about
+#[derive(Debug, Deserialize, Clone)]
+pub struct SubscriptionFilter {
+    /// Only forward `NewTransaction` events for this chaincode; blocks and reorgs are unaffected
+    pub chaincode_id: Option<String>,
+}
+
+impl SubscriptionFilter {
+    fn matches(&self, event: &SubscriptionEvent) -> bool {
+        match (&self.chaincode_id, event) {
+            (Some(wanted), SubscriptionEvent::NewTransaction(tx)) => &tx.chaincode_id == wanted,
+            _ => true,
+        }
+    }
+}
+
+/// WebSocket endpoint streaming new blocks, transactions, and reorgs
+pub async fn subscribe_ws(
+    State(state): State<AppState>,
+    Query(filter): Query<SubscriptionFilter>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_ws_subscriber(socket, state, filter))
+}
+
+async fn handle_ws_subscriber(mut socket: WebSocket, state: AppState, filter: SubscriptionFilter) {
+    let mut events = state.subscriptions.subscribe();
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Subscriber lagged behind the event feed, skipped {} events", skipped);
+                continue;
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        };
+
+        if !filter.matches(&event) {
+            continue;
+        }
+
+        let Ok(payload) = serde_json::to_string(&event) else {
+            continue;
+        };
+        if socket.send(Message::Text(payload)).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// SSE fallback for clients that can't use WebSockets, streaming the same feed
+pub async fn subscribe_sse(
+    State(state): State<AppState>,
+    Query(filter): Query<SubscriptionFilter>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.subscriptions.subscribe())
+        .filter_map(move |event| {
+            let event = event.ok()?;
+            if !filter.matches(&event) {
+                return None;
+            }
+            let payload = serde_json::to_string(&event).ok()?;
+            Some(Ok(Event::default().data(payload)))
+        });
+
+    Sse::new(stream).keep_alive(
+        axum::response::sse::KeepAlive::new()
+            .interval(Duration::from_secs(15))
+            .text("keep-alive"),
+    )
+}
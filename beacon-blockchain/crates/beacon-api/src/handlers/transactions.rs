@@ -3,7 +3,11 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use uuid::Uuid;
 use chrono::Utc;
+use rocksdb::{IteratorMode, Direction};
+use beacon_core::{Transaction, TransactionInput, TransactionType, TransactionId, Address};
+use beacon_storage::CF_TRANSACTIONS;
 use crate::server::AppState;
+use crate::subscriptions::TransactionNotification;
 
 #[derive(Deserialize)]
 pub struct SubmitTransactionRequest {
@@ -30,12 +34,37 @@ pub struct TransactionQuery {
 }
 
 pub async fn submit_transaction(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(payload): Json<SubmitTransactionRequest>,
 ) -> Result<Json<Value>, StatusCode> {
-    // Mock transaction submission
-    let tx_id = Uuid::new_v4().to_string();
-    
+    let mut metadata = std::collections::HashMap::new();
+    if let Some(policy) = &payload.endorsement_policy {
+        metadata.insert("endorsement_policy".to_string(), policy.clone());
+    }
+
+    let input = TransactionInput {
+        chaincode_id: payload.chaincode_id.clone(),
+        function: payload.function.clone(),
+        args: payload.args.clone(),
+        metadata,
+    };
+
+    let transaction = Transaction::new(TransactionType::Invoke, Address::new("api-client"), None, input, 0);
+    let tx_id = transaction.id.as_str().to_string();
+
+    state
+        .transaction_storage
+        .store_transaction(&transaction)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    state.subscriptions.publish_transaction(TransactionNotification {
+        transaction_id: tx_id.clone(),
+        chaincode_id: payload.chaincode_id.clone(),
+        function: payload.function.clone(),
+        status: "submitted".to_string(),
+    });
+
     Ok(Json(json!({
         "transaction_id": tx_id,
         "status": "submitted",
@@ -43,68 +72,106 @@ pub async fn submit_transaction(
         "function": payload.function,
         "args": payload.args,
         "timestamp": Utc::now().to_rfc3339(),
-        "estimated_confirmation_time": "30s",
-        "gas_estimate": 21000
+        "gas_limit": transaction.gas_limit
     })))
 }
 
 pub async fn get_transaction(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(tx_id): Path<String>,
 ) -> Result<Json<Value>, StatusCode> {
-    // Mock transaction retrieval
+    let transaction = state
+        .transaction_storage
+        .get_transaction(&TransactionId::from_string(tx_id))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
     Ok(Json(json!({
-        "transaction_id": tx_id,
-        "status": "confirmed",
-        "block_number": 12345,
-        "block_hash": format!("0x{:064x}", 12345),
-        "chaincode_id": "asset-transfer",
-        "function": "transfer",
-        "args": ["alice", "bob", "100"],
-        "timestamp": Utc::now().to_rfc3339(),
-        "gas_used": 21000,
-        "events": [
-            {
-                "event_name": "Transfer",
-                "payload": {
-                    "from": "alice",
-                    "to": "bob",
-                    "amount": "100"
-                }
-            }
-        ]
+        "transaction_id": transaction.id.as_str(),
+        "status": "submitted",
+        "hash": transaction.hash,
+        "from": transaction.from.as_str(),
+        "to": transaction.to.as_ref().map(|addr| addr.as_str()),
+        "chaincode_id": transaction.input.chaincode_id,
+        "function": transaction.input.function,
+        "args": transaction.input.args,
+        "nonce": transaction.nonce,
+        "gas_limit": transaction.gas_limit,
+        "gas_price": transaction.gas_price,
+        "timestamp": transaction.timestamp.to_millis()
     })))
 }
 
 pub async fn get_transactions(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Query(params): Query<TransactionQuery>,
 ) -> Result<Json<Value>, StatusCode> {
-    let limit = params.limit.unwrap_or(10).min(100);
-    let offset = params.offset.unwrap_or(0);
-    
-    // Mock transaction list
+    let limit = params.limit.unwrap_or(10).min(100) as usize;
+    let offset = params.offset.unwrap_or(0) as usize;
+
+    let iter = state
+        .storage
+        .iter_cf_mode(CF_TRANSACTIONS, IteratorMode::From(b"tx:", Direction::Forward))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
     let mut transactions = Vec::new();
-    for i in 0..limit {
-        let tx_id = Uuid::new_v4().to_string();
+    let mut skipped = 0usize;
+    let mut next = None;
+
+    for item in iter {
+        let (key, value) = item.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if !key.starts_with(b"tx:") {
+            break;
+        }
+
+        let Ok(transaction) = bincode::deserialize::<Transaction>(&value) else {
+            // "{tx_id}:result" entries land in this CF too but aren't a
+            // `Transaction`; skip anything that doesn't deserialize as one.
+            continue;
+        };
+
+        if let Some(chaincode_id) = &params.chaincode_id {
+            if &transaction.input.chaincode_id != chaincode_id {
+                continue;
+            }
+        }
+        if let Some(status) = &params.status {
+            // Every transaction reachable from this API has only ever been
+            // submitted, never confirmed into a block - there's no other
+            // status to filter on yet.
+            if status != "submitted" {
+                continue;
+            }
+        }
+
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+
+        if transactions.len() == limit {
+            next = Some(String::from_utf8_lossy(&key).to_string());
+            break;
+        }
+
         transactions.push(json!({
-            "transaction_id": tx_id,
-            "status": if i % 3 == 0 { "pending" } else { "confirmed" },
-            "block_number": if i % 3 == 0 { Value::Null } else { json!(12340 + i) },
-            "chaincode_id": format!("chaincode-{}", i % 3 + 1),
-            "function": "transfer",
-            "timestamp": Utc::now().to_rfc3339(),
-            "gas_used": 21000 + i * 100
+            "transaction_id": transaction.id.as_str(),
+            "status": "submitted",
+            "chaincode_id": transaction.input.chaincode_id,
+            "function": transaction.input.function,
+            "timestamp": transaction.timestamp.to_millis(),
+            "gas_limit": transaction.gas_limit
         }));
     }
-    
+
     Ok(Json(json!({
         "transactions": transactions,
         "pagination": {
             "limit": limit,
             "offset": offset,
-            "total": 1000,
-            "has_more": offset + limit < 1000
+            "next": next,
+            "has_more": next.is_some()
         }
     })))
 }
@@ -115,7 +182,7 @@ pub async fn invoke_chaincode(
 ) -> Result<Json<Value>, StatusCode> {
     // Mock chaincode invocation
     let execution_id = Uuid::new_v4().to_string();
-    
+
     Ok(Json(json!({
         "execution_id": execution_id,
         "chaincode_id": payload.chaincode_id,
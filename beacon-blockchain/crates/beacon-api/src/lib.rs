@@ -0,0 +1,7 @@
+pub mod handlers;
+pub mod middleware;
+pub mod metrics;
+pub mod server;
+pub mod subscriptions;
+
+pub use server::ApiServer;
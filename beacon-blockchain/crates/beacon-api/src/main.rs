@@ -21,6 +21,7 @@ async fn main() -> Result<()> {
         write_buffer_size: 64, // 64 MB
         max_open_files: 1000,
         enable_statistics: true,
+        read_cache_entries: 10_000,
     };
 
     tracing::info!("📁 Initializing database at: {}", db_config.path);
@@ -41,7 +42,7 @@ async fn main() -> Result<()> {
     };
 
     // Create chaincode GRPC service and executor
-    let grpc_service = Arc::new(ChaincodeShimService::new(state_storage));
+    let grpc_service = Arc::new(ChaincodeShimService::new(state_storage, false));
     let chaincode_executor = Arc::new(ChaincodeExecutor::new(executor_config, grpc_service));
     tracing::info!("✅ Chaincode executor initialized");
 
@@ -49,7 +50,7 @@ async fn main() -> Result<()> {
     let addr: SocketAddr = "0.0.0.0:3000".parse()?;
     
     // Create and configure the API server
-    let server = ApiServer::new(addr, database, chaincode_executor);
+    let server = ApiServer::new(addr, database, chaincode_executor)?;
     
     // Print startup information
     println!("🌟 ========================================");
@@ -0,0 +1,156 @@
+use axum::{http::StatusCode, response::IntoResponse};
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry, TextEncoder,
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry,
+};
+use beacon_storage::Database;
+
+/// Process-wide Prometheus registry backing the `/metrics` endpoint, modeled
+/// on Garage's `admin/metrics.rs`.
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Total HTTP requests handled, tallied by status class rather than exact
+/// code to keep cardinality bounded.
+pub static HTTP_REQUESTS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec_with_registry!(
+        "beacon_api_http_requests_total",
+        "Total HTTP requests handled, by route, method and status class",
+        &["route", "method", "status"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Request latency in seconds, by route and method.
+pub static HTTP_REQUEST_DURATION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec_with_registry!(
+        "beacon_api_http_request_duration_seconds",
+        "HTTP request latency in seconds, by route and method",
+        &["route", "method"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Total on-disk SST size per column family, from `Database::get_size_info`.
+pub static DB_CF_TOTAL_SST_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec_with_registry!(
+        "beacon_storage_cf_total_sst_bytes",
+        "Total on-disk SST file size per column family",
+        &["cf"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Gauges parsed out of `Database::get_stats()` (RocksDB's `rocksdb.stats`
+/// property dump), keyed by the stat name RocksDB itself uses.
+pub static DB_ROCKSDB_STAT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec_with_registry!(
+        "beacon_storage_rocksdb_stat",
+        "Selected RocksDB internal counters parsed from rocksdb.stats (writes, WAL writes, write stalls)",
+        &["stat"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Read-through cache hit/miss counters, from `Database::cache_stats()`.
+pub static DB_READ_CACHE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec_with_registry!(
+        "beacon_storage_read_cache_total",
+        "Read-through cache hits/misses in front of Database::get_cf",
+        &["result"],
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// Currently connected peer count, from `AppState::peer_count`.
+pub static PEER_COUNT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge_with_registry!(
+        "beacon_api_peer_count",
+        "Currently connected peer count",
+        REGISTRY
+    )
+    .unwrap()
+});
+
+/// RocksDB stat lines we pull out of the free-form `rocksdb.stats` dump and
+/// expose as gauges. `rocksdb.stats` is meant for humans, not machines, so
+/// this is a best-effort scrape of the handful of lines operators actually
+/// alert on - not a full parse of the dump.
+const TRACKED_ROCKSDB_STATS: &[(&str, &str)] = &[
+    ("Cumulative writes:", "cumulative_writes"),
+    ("Cumulative WAL: ", "cumulative_wal_writes"),
+    ("Cumulative stall:", "cumulative_stall_micros"),
+    ("Interval stall:", "interval_stall_micros"),
+];
+
+/// Parse the handful of stat lines in `TRACKED_ROCKSDB_STATS` out of
+/// `Database::get_stats()`'s free-form dump and refresh the gauges.
+fn scrape_rocksdb_stats(stats: &str) {
+    for line in stats.lines() {
+        for (prefix, stat_name) in TRACKED_ROCKSDB_STATS {
+            let Some(rest) = line.trim().strip_prefix(prefix) else {
+                continue;
+            };
+            if let Some(value) = rest.split_whitespace().next().and_then(|s| s.parse::<i64>().ok()) {
+                DB_ROCKSDB_STAT.with_label_values(&[stat_name]).set(value);
+            }
+        }
+    }
+}
+
+/// Refresh the DB-derived gauges from `Database::get_stats()` and
+/// `get_size_info()`. Called on each `/metrics` scrape rather than on a
+/// timer, so the numbers are only ever as stale as the last scrape.
+fn scrape_database(database: &Database) {
+    if let Some(stats) = database.get_stats() {
+        scrape_rocksdb_stats(&stats);
+    }
+
+    if let Ok(size_info) = database.get_size_info() {
+        for (cf, size) in &size_info.cf_sizes {
+            DB_CF_TOTAL_SST_BYTES.with_label_values(&[cf]).set(*size as i64);
+        }
+    }
+
+    let cache_stats = database.cache_stats();
+    DB_READ_CACHE.with_label_values(&["hit"]).set(cache_stats.hits as i64);
+    DB_READ_CACHE.with_label_values(&["miss"]).set(cache_stats.misses as i64);
+}
+
+/// Renders every registry (this crate's own, plus `beacon-networking`'s and
+/// `beacon-consensus`'s) in Prometheus text-exposition format. Shared by the
+/// `AppState`-bound `/metrics` route below and `beacon-node`'s standalone
+/// metrics server (`MonitoringConfig::metrics_addr`), which has no
+/// `AppState` to extract.
+pub fn render() -> Result<Vec<u8>, prometheus::Error> {
+    let encoder = TextEncoder::new();
+    let mut metric_families = REGISTRY.gather();
+    metric_families.extend(beacon_networking::metrics::gather());
+    metric_families.extend(beacon_consensus::metrics::gather());
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// `GET /metrics` - scrapes fresh RocksDB and peer-count gauges, then
+/// renders every registry via `render()`.
+pub async fn metrics_handler(
+    axum::extract::State(state): axum::extract::State<crate::server::AppState>,
+) -> impl IntoResponse {
+    scrape_database(&state.storage);
+    PEER_COUNT.set(state.peer_count.load(std::sync::atomic::Ordering::Relaxed) as i64);
+
+    match render() {
+        Ok(buffer) => (StatusCode::OK, [("Content-Type", TextEncoder::new().format_type())], buffer).into_response(),
+        Err(e) => {
+            tracing::error!("failed to encode metrics: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "failed to encode metrics").into_response()
+        }
+    }
+}
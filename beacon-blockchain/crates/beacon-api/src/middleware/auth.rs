@@ -14,7 +14,7 @@ pub struct AuthenticatedUser {
 
 /// Authentication middleware that validates JWT tokens or API keys
 pub async fn auth_middleware(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
@@ -23,13 +23,13 @@ pub async fn auth_middleware(
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|header| header.to_str().ok());
-    
+
     let claims = match auth_header {
         Some(auth_value) => {
             if auth_value.starts_with("Bearer ") {
                 // JWT token authentication
                 let token = &auth_value[7..]; // Remove "Bearer " prefix
-                verify_token(token).await?
+                verify_token(&state, token).await?
             } else if auth_value.starts_with("ApiKey ") {
                 // API key authentication
                 let api_key = &auth_value[7..]; // Remove "ApiKey " prefix
@@ -82,7 +82,7 @@ pub fn require_permission(required_permission: &'static str) -> impl Fn(Request,
 
 /// Optional authentication middleware (allows unauthenticated requests)
 pub async fn optional_auth_middleware(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
@@ -91,11 +91,11 @@ pub async fn optional_auth_middleware(
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|header| header.to_str().ok());
-    
+
     if let Some(auth_value) = auth_header {
         if let Ok(claims) = if auth_value.starts_with("Bearer ") {
             let token = &auth_value[7..];
-            verify_token(token).await
+            verify_token(&state, token).await
         } else if auth_value.starts_with("ApiKey ") {
             let api_key = &auth_value[7..];
             get_api_key_info(api_key).await.ok_or(StatusCode::UNAUTHORIZED)
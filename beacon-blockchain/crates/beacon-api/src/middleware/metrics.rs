@@ -0,0 +1,39 @@
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use crate::metrics::{HTTP_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS};
+
+/// Records per-route request counts, status-class tallies and request
+/// latency for every request, alongside `logging_middleware`.
+pub async fn metrics_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let start_time = Instant::now();
+    let response = next.run(request).await;
+    let duration = start_time.elapsed();
+
+    let status_class = match response.status().as_u16() {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    };
+
+    HTTP_REQUESTS_TOTAL
+        .with_label_values(&[&route, &method, status_class])
+        .inc();
+    HTTP_REQUEST_DURATION_SECONDS
+        .with_label_values(&[&route, &method])
+        .observe(duration.as_secs_f64());
+
+    response
+}
@@ -1,7 +1,9 @@
 pub mod auth;
 pub mod rate_limit;
 pub mod logging;
+pub mod metrics;
 
 pub use auth::{auth_middleware, optional_auth_middleware, require_permission, AuthenticatedUser};
 pub use rate_limit::{rate_limit_middleware, enhanced_rate_limit_middleware, endpoint_rate_limit};
 pub use logging::{logging_middleware, security_headers_middleware, cors_middleware, request_size_limit_middleware, timeout_middleware};
+pub use metrics::metrics_middleware;
@@ -1,64 +1,217 @@
 use axum::{
     extract::{Request, State},
-    http::StatusCode,
+    http::{HeaderValue, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
     Json,
 };
+use dashmap::DashMap;
 use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use crate::server::AppState;
 use crate::middleware::auth::get_rate_limit_key;
 
+/// Requests per period and burst applied to any key without a per-endpoint
+/// override (see `GcraLimiter::for_endpoint`).
+const DEFAULT_RATE: u32 = 100;
+const DEFAULT_PERIOD: Duration = Duration::from_secs(60);
+const DEFAULT_BURST: u32 = 20;
 
-/// Rate limiting middleware using Governor (simplified version)
+/// Outcome of a GCRA admission check for a single key.
+pub enum RateLimitDecision {
+    Allowed { remaining_burst: u32, reset: Duration },
+    Limited { retry_after: Duration },
+}
+
+/// GCRA (generic cell rate algorithm) token limiter. For a configured rate
+/// `r` per `period`, the steady emission interval is `T = period / r` and the
+/// burst tolerance is `tau = T * (burst - 1)`. Each key's theoretical arrival
+/// time (`TAT`) of its next request is tracked in `tat`; a request is allowed
+/// iff `now >= TAT - tau`, in which case `TAT` advances to `max(TAT, now) + T`.
+pub struct GcraLimiter {
+    rate: u32,
+    period: Duration,
+    burst: u32,
+    tat: DashMap<String, Instant>,
+}
+
+impl GcraLimiter {
+    pub fn new(rate: u32, period: Duration, burst: u32) -> Self {
+        Self {
+            rate: rate.max(1),
+            period,
+            burst: burst.max(1),
+            tat: DashMap::new(),
+        }
+    }
+
+    /// The limiter used when an endpoint has no override in
+    /// `endpoint_rate_limit`.
+    pub fn default_limiter() -> Self {
+        Self::new(DEFAULT_RATE, DEFAULT_PERIOD, DEFAULT_BURST)
+    }
+
+    /// A limiter configured for a specific endpoint, falling back to the
+    /// default rate/period/burst for endpoints with no override.
+    pub fn for_endpoint(endpoint: &str) -> Self {
+        let (rate, period, burst) = match endpoint {
+            "auth/login" => (5, Duration::from_secs(60), 2),
+            "transactions/submit" => (30, Duration::from_secs(60), 10),
+            "chaincode/invoke" => (20, Duration::from_secs(60), 5),
+            _ => (DEFAULT_RATE, DEFAULT_PERIOD, DEFAULT_BURST),
+        };
+        Self::new(rate, period, burst)
+    }
+
+    fn emission_interval(&self) -> Duration {
+        self.period / self.rate
+    }
+
+    fn burst_tolerance(&self) -> Duration {
+        self.emission_interval() * (self.burst - 1)
+    }
+
+    /// Check and, if allowed, admit a request for `key`.
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        let now = Instant::now();
+        let interval = self.emission_interval();
+        let tolerance = self.burst_tolerance();
+
+        let mut tat_entry = self.tat.entry(key.to_string()).or_insert(now);
+        let tat = *tat_entry;
+        let allowed_from = tat.checked_sub(tolerance).unwrap_or(now);
+
+        if now >= allowed_from {
+            let new_tat = tat.max(now) + interval;
+            *tat_entry = new_tat;
+            drop(tat_entry);
+            RateLimitDecision::Allowed {
+                remaining_burst: self.remaining_burst(new_tat, now),
+                reset: new_tat.saturating_duration_since(now),
+            }
+        } else {
+            RateLimitDecision::Limited {
+                retry_after: allowed_from - now,
+            }
+        }
+    }
+
+    /// Read-only view of `key`'s current standing, without admitting a
+    /// request or advancing its `TAT`. Used by `rate_limit_status`.
+    pub fn status(&self, key: &str) -> (u32, Duration) {
+        let now = Instant::now();
+        let tat = self
+            .tat
+            .get(key)
+            .map(|entry| *entry)
+            .unwrap_or(now);
+        (self.remaining_burst(tat, now), tat.saturating_duration_since(now))
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    fn remaining_burst(&self, tat: Instant, now: Instant) -> u32 {
+        let interval = self.emission_interval();
+        let slots_used = (tat.saturating_duration_since(now).as_secs_f64() / interval.as_secs_f64()).ceil() as u32;
+        self.burst.saturating_sub(slots_used)
+    }
+}
+
+/// Build the HTTP 429 response for a rejected request.
+fn too_many_requests(retry_after: Duration) -> Response {
+    let mut response = Json(json!({
+        "error": "rate limit exceeded",
+        "retry_after_secs": retry_after.as_secs_f64(),
+    }))
+    .into_response();
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&retry_after.as_secs().to_string()) {
+        headers.insert("Retry-After", value.clone());
+        headers.insert("X-RateLimit-Reset", value);
+    }
+    response
+}
+
+/// Rate limiting middleware backed by the default GCRA limiter.
 pub async fn rate_limit_middleware(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // For now, just proceed without actual rate limiting
-    // In production, implement proper rate limiting based on user/IP
-    Ok(next.run(request).await)
+    let key = get_rate_limit_key(&request);
+    match state.rate_limiter.check(&key) {
+        RateLimitDecision::Allowed { .. } => Ok(next.run(request).await),
+        RateLimitDecision::Limited { retry_after } => Ok(too_many_requests(retry_after)),
+    }
 }
 
-/// Enhanced rate limiting with burst handling (simplified)
+/// Same GCRA admission check as `rate_limit_middleware`, but also surfaces
+/// the remaining burst and reset time as headers on successful responses.
 pub async fn enhanced_rate_limit_middleware(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // For now, just proceed without actual rate limiting
-    Ok(next.run(request).await)
+    let key = get_rate_limit_key(&request);
+    match state.rate_limiter.check(&key) {
+        RateLimitDecision::Allowed { remaining_burst, reset } => {
+            let mut response = next.run(request).await;
+            let headers = response.headers_mut();
+            if let Ok(value) = HeaderValue::from_str(&remaining_burst.to_string()) {
+                headers.insert("X-RateLimit-Remaining", value);
+            }
+            if let Ok(value) = HeaderValue::from_str(&reset.as_secs().to_string()) {
+                headers.insert("X-RateLimit-Reset", value);
+            }
+            Ok(response)
+        }
+        RateLimitDecision::Limited { retry_after } => Ok(too_many_requests(retry_after)),
+    }
 }
 
-/// Endpoint-specific rate limiting (simplified)
+/// Endpoint-specific rate limiting, with its own `GcraLimiter` configured
+/// via `GcraLimiter::for_endpoint`.
 pub fn endpoint_rate_limit(endpoint: &str) -> impl Fn(Request, Next) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, StatusCode>> + Send>> + Clone {
-    let _endpoint_name = endpoint.to_string();
-    
+    let limiter = Arc::new(GcraLimiter::for_endpoint(endpoint));
+    let endpoint = endpoint.to_string();
+
     move |request: Request, next: Next| {
+        let limiter = limiter.clone();
+        let key = format!("{}:{}", endpoint, get_rate_limit_key(&request));
         Box::pin(async move {
-            // For now, just proceed without actual rate limiting
-            Ok(next.run(request).await)
+            match limiter.check(&key) {
+                RateLimitDecision::Allowed { .. } => Ok(next.run(request).await),
+                RateLimitDecision::Limited { retry_after } => Ok(too_many_requests(retry_after)),
+            }
         })
     }
 }
 
-/// Rate limit status endpoint (for monitoring)
+/// Rate limit status endpoint (for monitoring), reporting the real
+/// remaining burst and reset time computed from the caller's stored `TAT`.
 pub async fn rate_limit_status(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     request: Request,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let key = get_rate_limit_key(&request);
-    
-    // Mock status for now
+    let (remaining, reset) = state.rate_limiter.status(&key);
+
     let status = json!({
         "key": key,
-        "limit_per_minute": 100,
-        "burst_capacity": 20,
-        "remaining": "Available",
-        "reset_time": "N/A",
+        "limit_per_period": state.rate_limiter.rate(),
+        "burst_capacity": state.rate_limiter.burst(),
+        "remaining": remaining,
+        "reset_seconds": reset.as_secs(),
         "current_time": chrono::Utc::now().to_rfc3339()
     });
-    
+
     Ok(Json(status))
 }
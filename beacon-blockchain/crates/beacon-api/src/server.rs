@@ -7,33 +7,55 @@ use axum::{
     response::Json,
 };
 use std::net::SocketAddr;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 use beacon_core::BeaconResult;
-use beacon_storage::Database;
+use beacon_storage::{Database, StateStorage, TransactionStorage};
 use beacon_chaincode::ChaincodeExecutor;
 use serde_json::Value;
 
 use crate::handlers::{
-    health::health_check,
-    blockchain::{get_block, get_block_by_hash, get_latest_blocks, get_blockchain_info},
+    health::{liveness, readiness},
+    blockchain::{get_block, get_block_by_hash, get_latest_blocks, get_blockchain_info, get_genesis_block},
+    network::{get_peers, get_bootstrap_info},
+    subscribe::{subscribe_sse, subscribe_ws},
     transactions::{submit_transaction, get_transaction, get_transactions, invoke_chaincode},
-    state::{get_state, query_state, get_state_history},
-    auth::{login, logout, refresh_token, get_user_info},
+    state::{get_state, query_state, get_state_history, get_state_smt_proof},
+    kv::batch as kv_batch,
+    admin::{get_db_info, compact_db, checkpoint_db, verify_checkpoint},
+    auth::{login, logout, refresh_token, get_user_info, CredentialStore, TokenRevocationStore, load_jwt_secret},
+    rpc::rpc,
 };
+use crate::subscriptions::SubscriptionHub;
 use crate::middleware::{
     auth_middleware,
     optional_auth_middleware,
     rate_limit_middleware,
     logging_middleware,
+    metrics_middleware,
     security_headers_middleware,
     cors_middleware,
 };
+use crate::middleware::auth::require_permission;
+use crate::middleware::rate_limit::GcraLimiter;
+use crate::metrics::metrics_handler;
 
 #[derive(Clone)]
 pub struct AppState {
     pub storage: Arc<Database>,
+    pub state_storage: Arc<StateStorage>,
+    pub transaction_storage: Arc<TransactionStorage>,
     pub chaincode_executor: Arc<ChaincodeExecutor>,
+    pub credential_store: Arc<CredentialStore>,
+    pub revocation_store: Arc<TokenRevocationStore>,
+    pub jwt_secret: Arc<Vec<u8>>,
+    pub rate_limiter: Arc<GcraLimiter>,
+    pub subscriptions: Arc<SubscriptionHub>,
+    /// Current connected-peer count, for `handlers::health::readiness`.
+    /// Zero until the networking layer is wired up to update it; see
+    /// `handlers::network::get_peers` for the same not-yet-wired gap.
+    pub peer_count: Arc<AtomicUsize>,
 }
 
 pub struct ApiServer {
@@ -42,12 +64,26 @@ pub struct ApiServer {
 }
 
 impl ApiServer {
-    pub fn new(addr: SocketAddr, storage: Arc<Database>, chaincode_executor: Arc<ChaincodeExecutor>) -> Self {
+    pub fn new(addr: SocketAddr, storage: Arc<Database>, chaincode_executor: Arc<ChaincodeExecutor>) -> BeaconResult<Self> {
+        let credential_store = Arc::new(CredentialStore::new(storage.clone()));
+        credential_store.seed_default_users()?;
+        let revocation_store = Arc::new(TokenRevocationStore::new(storage.clone()));
+        let transaction_storage = Arc::new(TransactionStorage::new(storage.clone()));
+        let state_storage = Arc::new(StateStorage::new(storage.clone()));
+
         let state = AppState {
             storage,
+            state_storage,
+            transaction_storage,
             chaincode_executor,
+            credential_store,
+            revocation_store,
+            jwt_secret: Arc::new(load_jwt_secret()),
+            rate_limiter: Arc::new(GcraLimiter::default_limiter()),
+            subscriptions: Arc::new(SubscriptionHub::new()),
+            peer_count: Arc::new(AtomicUsize::new(0)),
         };
-        Self { addr, state }
+        Ok(Self { addr, state })
     }
     
     pub async fn run(self) -> BeaconResult<()> {
@@ -63,13 +99,22 @@ impl ApiServer {
     
     fn create_router(self) -> Router {
         Router::new()
-            // Health and info endpoints (no auth required)
-            .route("/health", get(health_check))
+            // Health and info endpoints (no auth required). "/health" keeps
+            // its old meaning (readiness) so existing probes/dashboards
+            // pointed at it don't break.
+            .route("/health", get(readiness))
+            .route("/health/live", get(liveness))
+            .route("/health/ready", get(readiness))
             .route("/info", get(server_info))
+            .route("/metrics", get(metrics_handler))
             
             // Authentication endpoints (no auth required)
             .route("/auth/login", post(login))
             .route("/auth/logout", post(logout))
+
+            // Ethereum-compatible JSON-RPC 2.0 endpoint, mirroring the
+            // REST blockchain/transaction handlers under eth_* methods
+            .route("/rpc", post(rpc))
             
             // Public blockchain query endpoints (optional auth for enhanced features)
             .nest("/api/v1", Router::new()
@@ -77,6 +122,11 @@ impl ApiServer {
                 .route("/blocks/:block_number", get(get_block))
                 .route("/blocks/hash/:block_hash", get(get_block_by_hash))
                 .route("/blockchain/info", get(get_blockchain_info))
+                .route("/blockchain/genesis", get(get_genesis_block))
+                .route("/network/peers", get(get_peers))
+                .route("/bootstrap", get(get_bootstrap_info))
+                .route("/subscribe", get(subscribe_ws))
+                .route("/subscribe/sse", get(subscribe_sse))
                 .route("/transactions/:tx_hash", get(get_transaction))
                 .route("/transactions", get(get_transactions))
                 .layer(middleware::from_fn_with_state(
@@ -94,8 +144,17 @@ impl ApiServer {
                 .route("/state/:key", get(get_state))
                 .route("/state/query", post(query_state))
                 .route("/state/:key/history", get(get_state_history))
+                .route("/state/:key/smt-proof", get(get_state_smt_proof))
+                .route("/kv/batch", post(kv_batch))
+                .nest("/admin", Router::new()
+                    .route("/db", get(get_db_info))
+                    .route("/db/compact", post(compact_db))
+                    .route("/db/checkpoint", post(checkpoint_db))
+                    .route("/db/checkpoint/verify", post(verify_checkpoint))
+                    .layer(middleware::from_fn(require_permission("admin:node")))
+                )
                 .layer(middleware::from_fn_with_state(
-                    self.state.clone(), 
+                    self.state.clone(),
                     auth_middleware
                 ))
             )
@@ -106,6 +165,7 @@ impl ApiServer {
                 rate_limit_middleware
             ))
             .layer(middleware::from_fn(logging_middleware))
+            .layer(middleware::from_fn(metrics_middleware))
             .layer(middleware::from_fn(security_headers_middleware))
             .layer(middleware::from_fn(cors_middleware))
             .layer(TraceLayer::new_for_http())
@@ -126,7 +186,8 @@ async fn server_info(State(_state): State<AppState>) -> Result<Json<Value>, Stat
             "state_queries",
             "chaincode_invocation",
             "authentication",
-            "rate_limiting"
+            "rate_limiting",
+            "json_rpc"
         ],
         "statistics": {
             "latest_block_number": 0,
@@ -135,11 +196,14 @@ async fn server_info(State(_state): State<AppState>) -> Result<Json<Value>, Stat
         },
         "endpoints": {
             "health": "/health",
+            "health_live": "/health/live",
+            "health_ready": "/health/ready",
             "blockchain": "/api/v1/blocks/*",
             "transactions": "/api/v1/transactions/*",
             "state": "/api/v1/state/*",
             "chaincode": "/api/v1/chaincode/*",
-            "auth": "/auth/*"
+            "auth": "/auth/*",
+            "rpc": "/rpc"
         }
     })))
 }
@@ -0,0 +1,82 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Capacity of the broadcast channel backing `/api/v1/subscribe`. Slow
+/// subscribers that fall this far behind the feed get a `Lagged` error and
+/// simply miss the skipped events rather than blocking publishers.
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 1000;
+
+/// A new block committed to the chain
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockNotification {
+    pub number: u64,
+    pub hash: String,
+    pub transaction_count: usize,
+}
+
+/// A transaction that was submitted or confirmed
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionNotification {
+    pub transaction_id: String,
+    pub chaincode_id: String,
+    pub function: String,
+    pub status: String,
+}
+
+/// A chain reorganization, derived from `BlockchainStorage`'s tree-route machinery
+#[derive(Debug, Clone, Serialize)]
+pub struct ReorgNotification {
+    pub ancestor: String,
+    pub retracted: Vec<String>,
+    pub enacted: Vec<String>,
+}
+
+/// One message on the `/api/v1/subscribe` push feed
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum SubscriptionEvent {
+    NewBlock(BlockNotification),
+    NewTransaction(TransactionNotification),
+    Reorg(ReorgNotification),
+}
+
+/// Broadcasts new blocks, transactions, and reorgs to subscribers of the
+/// WebSocket/SSE feed. Cheap to clone; every publisher and subscriber shares
+/// the same underlying channel.
+#[derive(Clone)]
+pub struct SubscriptionHub {
+    sender: broadcast::Sender<SubscriptionEvent>,
+}
+
+impl SubscriptionHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Subscribe to the feed; each receiver gets every event published from here on
+    pub fn subscribe(&self) -> broadcast::Receiver<SubscriptionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish a newly committed block. No-op if there are no subscribers.
+    pub fn publish_block(&self, block: BlockNotification) {
+        let _ = self.sender.send(SubscriptionEvent::NewBlock(block));
+    }
+
+    /// Publish a transaction event. No-op if there are no subscribers.
+    pub fn publish_transaction(&self, transaction: TransactionNotification) {
+        let _ = self.sender.send(SubscriptionEvent::NewTransaction(transaction));
+    }
+
+    /// Publish a reorg notification. No-op if there are no subscribers.
+    pub fn publish_reorg(&self, reorg: ReorgNotification) {
+        let _ = self.sender.send(SubscriptionEvent::Reorg(reorg));
+    }
+}
+
+impl Default for SubscriptionHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
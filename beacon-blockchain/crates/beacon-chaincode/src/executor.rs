@@ -3,14 +3,22 @@ use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::process::{Child, Command};
-use tokio::sync::{Mutex, RwLock};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{oneshot, Mutex, RwLock};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use beacon_core::{BeaconError, BeaconResult, Transaction};
-use crate::grpc_server::{ChaincodeContext, ChaincodeShimService};
+use beacon_core::{Address, BeaconError, BeaconResult, ServiceTransactionPolicy, Transaction};
+use crate::grpc_server::{ChaincodeContext, ChaincodeShimService, GAS_BASE_INVOCATION_COST};
+
+/// Synthetic `ChaincodeExecutionResult::status` reported when an execution's
+/// cumulative gas usage (see `ChaincodeShimService::charge_gas`) exceeded
+/// `transaction.gas_limit` - distinct from the chaincode's own exit codes so
+/// `BlockImportPipeline::run_chaincode` can map it to `TransactionStatus::OutOfGas`
+/// rather than a generic `Failed`. Mirrors the `-1` MVCC_READ_CONFLICT sentinel.
+pub const GAS_EXCEEDED_STATUS: i32 = -2;
 
 /// Configuration for chaincode execution
 #[derive(Debug, Clone)]
@@ -25,6 +33,39 @@ pub struct ChaincodeExecutorConfig {
     pub grpc_addr: String,
     /// Whether to enable debug logging for chaincode processes
     pub debug_logging: bool,
+    /// Whether to record an ordered shim-interaction trace (GetState hits/
+    /// misses, PutState/DeleteState, SetEvent, each with a timing delta)
+    /// for every execution - see `ChaincodeExecutionResult::trace` and
+    /// `ChaincodeExecutor::get_trace`. Off by default: a production node
+    /// pays no bookkeeping cost for a debugging/audit feature it hasn't
+    /// opted into. `ChaincodeExecutor` does not read this flag itself:
+    /// traces are recorded only when the `ChaincodeShimService` passed to
+    /// `ChaincodeExecutor::new` was constructed with `trace_execution` set.
+    pub trace_execution: bool,
+    /// Warm worker processes kept alive per `chaincode_id`. Each one
+    /// services many invocations over its lifetime instead of being spawned
+    /// and killed per transaction.
+    pub pool_size_per_chaincode: usize,
+    /// An idle pooled worker is killed and removed once it hasn't serviced
+    /// an invocation for this long.
+    pub worker_idle_ttl: Duration,
+    /// How often the supervisor sweeps the pool for dead/idle/expired workers.
+    pub health_check_interval: Duration,
+    /// Ceiling on the supervisor's exponential restart backoff for a
+    /// `chaincode_id` whose workers keep crashing.
+    pub max_restart_backoff: Duration,
+    /// Admission policy for zero-gas-price "service transactions" reaching
+    /// the executor - see `Transaction::validate_service_transaction`. This
+    /// is the policy's primary enforcement point: it guards the compute a
+    /// free admission bypass would actually be targeting, so it's checked
+    /// again here even when a transaction already passed the same policy at
+    /// submission time (e.g. `BeaconNode::submit_transaction`).
+    pub service_transaction_policy: ServiceTransactionPolicy,
+    /// Sender addresses permitted past `service_transaction_policy` when
+    /// it's `AllowlistOnly`. Only the statically configured allowlist - the
+    /// on-chain allowlist chaincode, if any, is resolved once at submission
+    /// time rather than re-queried for every execution.
+    pub service_transaction_allowlist: Vec<Address>,
 }
 
 impl Default for ChaincodeExecutorConfig {
@@ -35,21 +76,125 @@ impl Default for ChaincodeExecutorConfig {
             max_concurrent: 10,
             grpc_addr: "127.0.0.1:9090".to_string(),
             debug_logging: false,
+            trace_execution: false,
+            pool_size_per_chaincode: 2,
+            worker_idle_ttl: Duration::from_secs(300),
+            health_check_interval: Duration::from_secs(30),
+            max_restart_backoff: Duration::from_secs(60),
+            service_transaction_policy: ServiceTransactionPolicy::default(),
+            service_transaction_allowlist: Vec::new(),
         }
     }
 }
 
-/// Information about a running chaincode process
-#[derive(Debug)]
-struct ChaincodeProcess {
-    /// Process handle
-    child: Child,
-    /// When the process was started
-    started_at: Instant,
-    /// Transaction ID being executed
-    transaction_id: String,
-    /// Chaincode ID
+/// A pooled chaincode worker: spawned once per `chaincode_id` and, as long
+/// as it stays healthy, dispatched many invocations over its lifetime
+/// rather than being torn down after one. Invocations are sent as a single
+/// NUL-separated line on the worker's stdin (`execution_id`, `transaction_id`,
+/// `function`, then each arg) - the same null-separator convention
+/// `get_state_by_partial_composite_key` uses for composite keys - and the
+/// worker reports completion by writing `BEACON_DONE <execution_id> <status>`
+/// to its stdout, read by this worker's dedicated reader task.
+struct PooledWorker {
     chaincode_id: String,
+    child: Child,
+    stdin: ChildStdin,
+    /// Execution ID this worker is currently dispatching, if any - read by
+    /// its stdout reader task so a worker that dies mid-invocation can fail
+    /// that invocation's `pending` completion instead of leaving it to hang
+    /// until the timeout.
+    current_execution: Arc<Mutex<Option<String>>>,
+    spawned_at: Instant,
+    last_used: Instant,
+}
+
+/// Per-`chaincode_id` restart bookkeeping: tracks consecutive crashes so the
+/// supervisor can back off exponentially instead of respawning a
+/// perpetually-crashing binary in a tight loop.
+#[derive(Debug, Default)]
+struct RestartBackoff {
+    consecutive_failures: u32,
+    retry_after: Option<Instant>,
+}
+
+/// The keyed pool of pooled chaincode workers: idle ones available for
+/// immediate dispatch, and ones currently checked out to an in-flight
+/// execution. Replaces the old per-transaction `running_processes` map.
+struct ChaincodeProcessPool {
+    idle: Mutex<HashMap<String, Vec<PooledWorker>>>,
+    checked_out: Mutex<HashMap<String, PooledWorker>>,
+    backoff: Mutex<HashMap<String, RestartBackoff>>,
+    /// Every `chaincode_id` a worker has ever been requested for, so the
+    /// supervisor knows which pools to keep topped up to
+    /// `pool_size_per_chaincode` without having to guess in advance.
+    known_ids: Mutex<std::collections::HashSet<String>>,
+}
+
+impl ChaincodeProcessPool {
+    fn new() -> Self {
+        Self {
+            idle: Mutex::new(HashMap::new()),
+            checked_out: Mutex::new(HashMap::new()),
+            backoff: Mutex::new(HashMap::new()),
+            known_ids: Mutex::new(std::collections::HashSet::new()),
+        }
+    }
+
+    async fn note_known(&self, chaincode_id: &str) {
+        self.known_ids.lock().await.insert(chaincode_id.to_string());
+    }
+
+    /// Idle + checked-out workers currently held for `chaincode_id`.
+    async fn total_for(&self, chaincode_id: &str) -> usize {
+        let idle_count = self.idle.lock().await.get(chaincode_id).map(Vec::len).unwrap_or(0);
+        let checked_out_count = self
+            .checked_out
+            .lock()
+            .await
+            .values()
+            .filter(|w| w.chaincode_id == chaincode_id)
+            .count();
+        idle_count + checked_out_count
+    }
+
+    async fn take_idle(&self, chaincode_id: &str) -> Option<PooledWorker> {
+        self.idle.lock().await.get_mut(chaincode_id).and_then(Vec::pop)
+    }
+
+    async fn return_idle(&self, mut worker: PooledWorker) {
+        worker.last_used = Instant::now();
+        self.idle.lock().await.entry(worker.chaincode_id.clone()).or_default().push(worker);
+    }
+
+    async fn checkout(&self, execution_id: &str, worker: PooledWorker) {
+        self.checked_out.lock().await.insert(execution_id.to_string(), worker);
+    }
+
+    async fn take_checked_out(&self, execution_id: &str) -> Option<PooledWorker> {
+        self.checked_out.lock().await.remove(execution_id)
+    }
+
+    async fn is_backed_off(&self, chaincode_id: &str) -> bool {
+        self.backoff
+            .lock()
+            .await
+            .get(chaincode_id)
+            .and_then(|b| b.retry_after)
+            .map(|retry_after| Instant::now() < retry_after)
+            .unwrap_or(false)
+    }
+
+    async fn note_failure(&self, chaincode_id: &str, max_backoff: Duration) {
+        let mut backoff = self.backoff.lock().await;
+        let entry = backoff.entry(chaincode_id.to_string()).or_default();
+        entry.consecutive_failures += 1;
+        let delay = Duration::from_secs(2u64.saturating_pow(entry.consecutive_failures.min(6))).min(max_backoff);
+        entry.retry_after = Some(Instant::now() + delay);
+    }
+
+    async fn note_success(&self, chaincode_id: &str) {
+        self.backoff.lock().await.remove(chaincode_id);
+    }
 }
 
 /// Result of chaincode execution
@@ -65,6 +210,12 @@ pub struct ChaincodeExecutionResult {
     pub events: Vec<ChaincodeEvent>,
     /// State changes made during execution
     pub state_changes: Vec<ChaincodeStateChange>,
+    /// Cumulative gas charged during execution - see `GAS_EXCEEDED_STATUS`.
+    pub gas_used: u64,
+    /// Ordered trace of shim interactions this execution made, if
+    /// `ChaincodeExecutorConfig::trace_execution` was enabled - see
+    /// `ChaincodeTraceStep`. Empty otherwise.
+    pub trace: Vec<ChaincodeTraceStep>,
 }
 
 #[derive(Debug, Clone)]
@@ -80,21 +231,121 @@ pub struct ChaincodeStateChange {
     pub operation: String, // PUT, DELETE
 }
 
-/// Manages chaincode execution in Go subprocesses
+/// One recorded shim interaction - a deterministic, replayable record of
+/// what a chaincode touched, for diagnosing non-deterministic state
+/// divergence across nodes. See `ChaincodeExecutorConfig::trace_execution`,
+/// `ChaincodeExecutor::get_trace`, and the shim-side `grpc_server::TraceStep`
+/// this is converted from.
+#[derive(Debug, Clone)]
+pub struct ChaincodeTraceStep {
+    /// GET_STATE, PUT_STATE, DELETE_STATE, or SET_EVENT
+    pub operation: String,
+    /// The state key touched, or the event name for SET_EVENT
+    pub key: String,
+    /// Size in bytes of the value read/written/emitted - 0 for a GET_STATE miss
+    pub size: usize,
+    /// Whether a GET_STATE read found an existing value - always `true` for
+    /// every other operation
+    pub found: bool,
+    /// Milliseconds elapsed since the execution began
+    pub elapsed_ms: u64,
+}
+
+/// Manages chaincode execution in Go subprocesses, dispatching invocations
+/// to a supervised pool of long-lived worker processes (see
+/// `ChaincodeProcessPool`) instead of spawning and killing one per
+/// transaction.
 pub struct ChaincodeExecutor {
     config: ChaincodeExecutorConfig,
     grpc_service: Arc<ChaincodeShimService>,
-    running_processes: Arc<Mutex<HashMap<String, ChaincodeProcess>>>,
+    pool: Arc<ChaincodeProcessPool>,
+    /// Completion channels for in-flight invocations, keyed by execution ID
+    /// and fulfilled by the dispatched worker's stdout reader task.
+    pending: Arc<Mutex<HashMap<String, oneshot::Sender<i32>>>>,
     active_executions: Arc<RwLock<usize>>,
 }
 
 impl ChaincodeExecutor {
     pub fn new(config: ChaincodeExecutorConfig, grpc_service: Arc<ChaincodeShimService>) -> Self {
-        Self {
+        let executor = Self {
             config,
             grpc_service,
-            running_processes: Arc::new(Mutex::new(HashMap::new())),
+            pool: Arc::new(ChaincodeProcessPool::new()),
+            pending: Arc::new(Mutex::new(HashMap::new())),
             active_executions: Arc::new(RwLock::new(0)),
+        };
+        executor.spawn_supervisor();
+        executor
+    }
+
+    /// Launch the background task that health-checks idle pooled workers,
+    /// evicts ones past `worker_idle_ttl`, and tops pools for previously-seen
+    /// chaincode IDs back up to `pool_size_per_chaincode`, backing off a
+    /// chaincode that keeps failing to spawn.
+    fn spawn_supervisor(&self) {
+        let config = self.config.clone();
+        let pool = self.pool.clone();
+        let pending = self.pending.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(config.health_check_interval).await;
+                Self::supervise_once(&config, &pool, &pending).await;
+            }
+        });
+    }
+
+    async fn supervise_once(
+        config: &ChaincodeExecutorConfig,
+        pool: &Arc<ChaincodeProcessPool>,
+        pending: &Arc<Mutex<HashMap<String, oneshot::Sender<i32>>>>,
+    ) {
+        // Health-check and TTL-evict idle workers.
+        let mut removed: Vec<(String, PooledWorker, bool)> = Vec::new();
+        {
+            let mut idle = pool.idle.lock().await;
+            for (chaincode_id, workers) in idle.iter_mut() {
+                let mut i = 0;
+                while i < workers.len() {
+                    let crashed = matches!(workers[i].child.try_wait(), Ok(Some(_)));
+                    let expired = !crashed && workers[i].last_used.elapsed() > config.worker_idle_ttl;
+                    if crashed || expired {
+                        removed.push((chaincode_id.clone(), workers.remove(i), crashed));
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+        }
+        for (chaincode_id, mut worker, crashed) in removed {
+            if crashed {
+                warn!("Pooled chaincode worker for {} exited while idle", chaincode_id);
+                pool.note_failure(&chaincode_id, config.max_restart_backoff).await;
+            } else {
+                debug!("Evicting idle chaincode worker for {} past TTL", chaincode_id);
+            }
+            let _ = worker.child.kill().await;
+        }
+
+        // Top up pools for every chaincode we've been asked to run before.
+        let known_ids: Vec<String> = pool.known_ids.lock().await.iter().cloned().collect();
+        for chaincode_id in known_ids {
+            if pool.is_backed_off(&chaincode_id).await {
+                continue;
+            }
+            if pool.total_for(&chaincode_id).await >= config.pool_size_per_chaincode {
+                continue;
+            }
+            match Self::spawn_worker(config, pending.clone(), &chaincode_id).await {
+                Ok(worker) => {
+                    pool.note_success(&chaincode_id).await;
+                    pool.return_idle(worker).await;
+                }
+                Err(e) => {
+                    warn!("Failed to top up chaincode worker pool for {}: {}", chaincode_id, e);
+                    pool.note_failure(&chaincode_id, config.max_restart_backoff).await;
+                }
+            }
         }
     }
 
@@ -121,7 +372,7 @@ impl ChaincodeExecutor {
         }
 
         let execution_id = Uuid::new_v4().to_string();
-        
+
         let result = self.execute_chaincode_internal(transaction, creator, &execution_id).await;
 
         // Decrement active executions count
@@ -139,79 +390,153 @@ impl ChaincodeExecutor {
         creator: Vec<u8>,
         execution_id: &str,
     ) -> BeaconResult<ChaincodeExecutionResult> {
-        let chaincode_binary = self.find_chaincode_binary(&transaction.input.chaincode_id)?;
-        
+        let chaincode_id = &transaction.input.chaincode_id;
+
+        transaction.validate_service_transaction(
+            &self.config.service_transaction_policy,
+            &self.config.service_transaction_allowlist,
+        )?;
+
         info!(
             "Executing chaincode {} function {} for transaction {}",
-            transaction.input.chaincode_id, transaction.input.function, transaction.id.as_str()
+            chaincode_id, transaction.input.function, transaction.id.as_str()
         );
 
         // Set up the execution context in the gRPC service
         let context = ChaincodeContext {
+            execution_id: execution_id.to_string(),
             transaction_id: transaction.id.as_str().to_string(),
             channel_id: "beacon".to_string(), // Default channel
             creator,
             timestamp: transaction.timestamp.0.timestamp(),
-            chaincode_id: transaction.input.chaincode_id.clone(),
+            chaincode_id: chaincode_id.clone(),
+            gas_limit: transaction.gas_limit,
+            call_depth: 0,
+            call_stack: vec![chaincode_id.clone()],
+            started_at: Instant::now(),
         };
 
         self.grpc_service.set_context(context).await;
 
-        // Start the chaincode process
-        let child = self.start_chaincode_process(&chaincode_binary, transaction, execution_id).await?;
+        // Base cost is charged even if `gas_limit` alone can't cover it, so an
+        // invocation with a limit below the floor is rejected up front rather
+        // than dispatched to a worker at all.
+        if let Err(e) = self.grpc_service.charge_gas(execution_id, GAS_BASE_INVOCATION_COST).await {
+            self.grpc_service.clear_context(execution_id).await;
+            return Err(BeaconError::chaincode(format!("Chaincode invocation rejected: {}", e)));
+        }
 
-        let process_info = ChaincodeProcess {
-            child: child,
-            started_at: Instant::now(),
-            transaction_id: transaction.id.as_str().to_string(),
-            chaincode_id: transaction.input.chaincode_id.clone(),
+        let mut worker = match self.checkout_worker(chaincode_id).await {
+            Ok(worker) => worker,
+            Err(e) => {
+                self.grpc_service.clear_context(execution_id).await;
+                return Err(e);
+            }
         };
 
-        // Store the process info
-        {
-            let mut processes = self.running_processes.lock().await;
-            processes.insert(execution_id.to_string(), process_info);
-        }
-
-        // Wait for the process to complete with timeout
-        let result = timeout(
-            self.config.execution_timeout,
-            self.wait_for_completion(execution_id),
-        ).await;
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(execution_id.to_string(), tx);
+        *worker.current_execution.lock().await = Some(execution_id.to_string());
 
-        // Clean up the process
-        self.cleanup_process(execution_id).await;
-        
-        // Clear the context
-        self.grpc_service.clear_context().await;
+        if let Err(e) = Self::dispatch_invocation(&mut worker, transaction, execution_id).await {
+            self.pending.lock().await.remove(execution_id);
+            self.fail_worker(worker).await;
+            self.grpc_service.clear_context(execution_id).await;
+            return Err(e);
+        }
 
-        match result {
-            Ok(Ok(execution_result)) => {
+        self.pool.checkout(execution_id, worker).await;
+
+        let result = timeout(self.config.execution_timeout, rx).await;
+        let worker = self.pool.take_checked_out(execution_id).await;
+
+        let outcome = match result {
+            Ok(Ok(exit_status)) => {
+                let execution_result = self
+                    .collect_execution_result(execution_id, exit_status, transaction.gas_limit)
+                    .await;
+                if let Some(worker) = worker {
+                    if execution_result.status == 0 {
+                        self.pool.return_idle(worker).await;
+                    } else {
+                        // Also covers an out-of-gas override: the worker ran
+                        // past its budget, so it's killed rather than reused
+                        // even though it exited cleanly.
+                        self.fail_worker(worker).await;
+                    }
+                }
                 info!(
                     "Chaincode execution completed: {} status={}",
                     transaction.id.as_str(), execution_result.status
                 );
                 Ok(execution_result)
             }
-            Ok(Err(e)) => {
-                error!("Chaincode execution failed: {}", e);
-                Err(e)
+            Ok(Err(_)) => {
+                self.pending.lock().await.remove(execution_id);
+                if let Some(worker) = worker {
+                    self.fail_worker(worker).await;
+                }
+                error!("Chaincode worker for {} dropped its completion signal", transaction.id.as_str());
+                Err(BeaconError::chaincode("Chaincode worker completion channel closed unexpectedly".to_string()))
             }
             Err(_) => {
+                self.pending.lock().await.remove(execution_id);
+                if let Some(worker) = worker {
+                    self.fail_worker(worker).await;
+                }
                 error!("Chaincode execution timed out: {}", transaction.id.as_str());
                 Err(BeaconError::chaincode("Chaincode execution timed out".to_string()))
             }
+        };
+
+        self.grpc_service.clear_context(execution_id).await;
+        outcome
+    }
+
+    /// Take an idle worker for `chaincode_id` from the pool, or spawn a new
+    /// one if the pool for this chaincode isn't at `pool_size_per_chaincode` yet.
+    async fn checkout_worker(&self, chaincode_id: &str) -> BeaconResult<PooledWorker> {
+        self.pool.note_known(chaincode_id).await;
+
+        if let Some(worker) = self.pool.take_idle(chaincode_id).await {
+            return Ok(worker);
+        }
+
+        if self.pool.total_for(chaincode_id).await >= self.config.pool_size_per_chaincode {
+            return Err(BeaconError::chaincode(format!(
+                "Chaincode worker pool exhausted for {}",
+                chaincode_id
+            )));
+        }
+
+        match Self::spawn_worker(&self.config, self.pending.clone(), chaincode_id).await {
+            Ok(worker) => {
+                self.pool.note_success(chaincode_id).await;
+                Ok(worker)
+            }
+            Err(e) => {
+                self.pool.note_failure(chaincode_id, self.config.max_restart_backoff).await;
+                Err(e)
+            }
         }
     }
 
-    fn find_chaincode_binary(&self, chaincode_id: &str) -> BeaconResult<PathBuf> {
+    /// Kill a worker that crashed, timed out, or failed mid-dispatch, and
+    /// record the failure against its chaincode's restart backoff.
+    async fn fail_worker(&self, mut worker: PooledWorker) {
+        warn!("Killing chaincode worker for {}", worker.chaincode_id);
+        let _ = worker.child.kill().await;
+        self.pool.note_failure(&worker.chaincode_id, self.config.max_restart_backoff).await;
+    }
+
+    fn find_chaincode_binary(chaincode_dir: &Path, chaincode_id: &str) -> BeaconResult<PathBuf> {
         let binary_name = if cfg!(windows) {
             format!("{}.exe", chaincode_id)
         } else {
             chaincode_id.to_string()
         };
 
-        let binary_path = self.config.chaincode_dir.join(&binary_name);
+        let binary_path = chaincode_dir.join(&binary_name);
 
         if binary_path.exists() && binary_path.is_file() {
             Ok(binary_path)
@@ -223,55 +548,144 @@ impl ChaincodeExecutor {
         }
     }
 
-    async fn start_chaincode_process(
-        &self,
-        binary_path: &Path,
+    /// Spawn a new pooled worker for `chaincode_id` and start the background
+    /// task that reads its stdout for `BEACON_DONE <execution_id> <status>`
+    /// completion lines, fulfilling the matching entry in `pending`.
+    async fn spawn_worker(
+        config: &ChaincodeExecutorConfig,
+        pending: Arc<Mutex<HashMap<String, oneshot::Sender<i32>>>>,
+        chaincode_id: &str,
+    ) -> BeaconResult<PooledWorker> {
+        let binary_path = Self::find_chaincode_binary(&config.chaincode_dir, chaincode_id)?;
+        debug!("Spawning pooled chaincode worker: {}", binary_path.display());
+
+        let mut cmd = Command::new(&binary_path);
+        cmd.env("BEACON_GRPC_ADDRESS", &config.grpc_addr)
+            .env("BEACON_CHAINCODE_ID", chaincode_id)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(&config.chaincode_dir);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| BeaconError::chaincode(format!("Failed to start chaincode worker: {}", e)))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| BeaconError::chaincode("Chaincode worker has no stdin".to_string()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| BeaconError::chaincode("Chaincode worker has no stdout".to_string()))?;
+
+        let current_execution = Arc::new(Mutex::new(None));
+        let reader_chaincode_id = chaincode_id.to_string();
+        let reader_current_execution = current_execution.clone();
+
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => {
+                        if let Some(rest) = line.strip_prefix("BEACON_DONE ") {
+                            let mut parts = rest.splitn(2, ' ');
+                            if let (Some(execution_id), Some(status)) = (parts.next(), parts.next()) {
+                                if let Ok(status) = status.trim().parse::<i32>() {
+                                    *reader_current_execution.lock().await = None;
+                                    if let Some(tx) = pending.lock().await.remove(execution_id) {
+                                        let _ = tx.send(status);
+                                    }
+                                }
+                            }
+                        } else {
+                            debug!("[chaincode:{}] {}", reader_chaincode_id, line);
+                        }
+                    }
+                    Ok(None) => {
+                        if let Some(execution_id) = reader_current_execution.lock().await.take() {
+                            warn!(
+                                "Chaincode worker for {} exited mid-invocation {}",
+                                reader_chaincode_id, execution_id
+                            );
+                            if let Some(tx) = pending.lock().await.remove(&execution_id) {
+                                let _ = tx.send(-1);
+                            }
+                        }
+                        break;
+                    }
+                    Err(e) => {
+                        warn!("Chaincode worker {} stdout closed: {}", reader_chaincode_id, e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(PooledWorker {
+            chaincode_id: chaincode_id.to_string(),
+            child,
+            stdin,
+            current_execution,
+            spawned_at: Instant::now(),
+            last_used: Instant::now(),
+        })
+    }
+
+    /// Send one invocation to an already-running worker as a single line on
+    /// its stdin: `execution_id`, `transaction_id`, and `function`, each
+    /// separated by the NUL byte `get_state_by_partial_composite_key` already
+    /// uses to join composite-key segments, followed by the function's args
+    /// the same way.
+    async fn dispatch_invocation(
+        worker: &mut PooledWorker,
         transaction: &Transaction,
         execution_id: &str,
-    ) -> BeaconResult<Child> {
-        debug!("Starting chaincode process: {}", binary_path.display());
-
-        let mut cmd = Command::new(binary_path);
-        
-        // Set environment variables
-        cmd.env("BEACON_GRPC_ADDRESS", &self.config.grpc_addr)
-           .env("BEACON_TRANSACTION_ID", transaction.id.as_str())
-           .env("BEACON_CHAINCODE_ID", &transaction.input.chaincode_id)
-           .env("BEACON_FUNCTION", &transaction.input.function)
-           .env("BEACON_EXECUTION_ID", execution_id);
-
-        // Pass function arguments as command line arguments
-        cmd.args(&transaction.input.args);
-
-        // Configure stdio
-        cmd.stdin(Stdio::null())
-           .stdout(Stdio::piped())
-           .stderr(Stdio::piped());
-
-        // Set working directory to chaincode directory
-        cmd.current_dir(&self.config.chaincode_dir);
-
-        // Spawn the process
-        cmd.spawn()
-            .map_err(|e| BeaconError::chaincode(format!("Failed to start chaincode process: {}", e)))
+    ) -> BeaconResult<()> {
+        let mut line = format!(
+            "{}\u{0}{}\u{0}{}",
+            execution_id,
+            transaction.id.as_str(),
+            transaction.input.function
+        );
+        for arg in &transaction.input.args {
+            line.push('\u{0}');
+            line.push_str(arg);
+        }
+        line.push('\n');
+
+        worker
+            .stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| BeaconError::chaincode(format!("Failed to dispatch invocation to chaincode worker: {}", e)))?;
+        worker
+            .stdin
+            .flush()
+            .await
+            .map_err(|e| BeaconError::chaincode(format!("Failed to flush invocation to chaincode worker: {}", e)))
     }
 
-    async fn wait_for_completion(&self, execution_id: &str) -> BeaconResult<ChaincodeExecutionResult> {
-        // Wait for the process to exit
-        let exit_status = {
-            let mut processes = self.running_processes.lock().await;
-            if let Some(process) = processes.get_mut(execution_id) {
-                let status = process.child.wait().await
-                    .map_err(|e| BeaconError::chaincode(format!("Process wait failed: {}", e)))?;
-                
-                status.code().unwrap_or(-1)
-            } else {
-                return Err(BeaconError::chaincode("Process not found".to_string()));
-            }
-        };
-
-        // Collect events and state changes from the gRPC service
-        let (events, state_changes) = self.grpc_service.get_execution_results().await;
+    async fn collect_execution_result(
+        &self,
+        execution_id: &str,
+        exit_status: i32,
+        gas_limit: u64,
+    ) -> ChaincodeExecutionResult {
+        // Collect events, state changes, and the read-set from the gRPC service
+        let (events, state_changes, read_set) = self.grpc_service.get_execution_results(execution_id).await;
+        let gas_used = self.grpc_service.gas_used(execution_id).await;
+        let trace: Vec<ChaincodeTraceStep> = self.grpc_service.get_trace(execution_id).await
+            .into_iter()
+            .map(|t| ChaincodeTraceStep {
+                operation: t.operation,
+                key: t.key,
+                size: t.size,
+                found: t.found,
+                elapsed_ms: t.elapsed_ms,
+            })
+            .collect();
 
         // Convert to our result types
         let events: Vec<ChaincodeEvent> = events.into_iter()
@@ -281,7 +695,7 @@ impl ChaincodeExecutor {
             })
             .collect();
 
-        let state_changes: Vec<ChaincodeStateChange> = state_changes.into_iter()
+        let mut state_changes: Vec<ChaincodeStateChange> = state_changes.into_iter()
             .map(|sc| ChaincodeStateChange {
                 key: sc.key,
                 value: sc.value,
@@ -289,53 +703,86 @@ impl ChaincodeExecutor {
             })
             .collect();
 
-        Ok(ChaincodeExecutionResult {
-            status: exit_status,
+        // MVCC validation: re-check every key/range/prefix this execution
+        // read against the current version. Writes already landed eagerly
+        // on `StateStorage` during execution (see `validate_read_set`'s doc
+        // comment) so this can't stop them happening - but it can still stop
+        // them being committed, by emptying `state_changes` below so
+        // `BlockImportPipeline::run_chaincode`'s unconditional apply has
+        // nothing of this transaction's left to commit, and by forcing a
+        // non-zero status so the transaction is recorded as `Failed`.
+        let mut status = exit_status;
+        let mut message = if exit_status == 0 { "Success".to_string() } else { "Failed".to_string() };
+        if exit_status == 0 {
+            if gas_used > gas_limit {
+                // Gas is charged (and can already abort an in-flight shim RPC)
+                // as it's spent in `ChaincodeShimService::charge_gas`, but a
+                // worker that ignores the resulting RPC error and presses on
+                // regardless can still exit 0 - this is the backstop that
+                // catches that case and forces the commit-time rejection the
+                // real-time charge couldn't guarantee on its own.
+                warn!(
+                    "Execution {} rejected: out of gas ({} used of {} allowed)",
+                    execution_id, gas_used, gas_limit
+                );
+                status = GAS_EXCEEDED_STATUS;
+                message = format!("OUT_OF_GAS: used {} of limit {}", gas_used, gas_limit);
+                state_changes.clear();
+            } else {
+                let conflicts = self.grpc_service.validate_read_set(&read_set).await;
+                if !conflicts.is_empty() {
+                    warn!(
+                        "Execution {} rejected: {} MVCC read conflict(s): {:?}",
+                        execution_id,
+                        conflicts.len(),
+                        conflicts,
+                    );
+                    status = -1;
+                    message = format!("MVCC_READ_CONFLICT: {} conflicting read(s)", conflicts.len());
+                    state_changes.clear();
+                }
+            }
+        }
+
+        ChaincodeExecutionResult {
+            status,
             payload: vec![], // For now, we don't capture stdout as payload
-            message: if exit_status == 0 { "Success".to_string() } else { "Failed".to_string() },
+            message,
             events,
             state_changes,
-        })
+            gas_used,
+            trace,
+        }
     }
 
-    async fn cleanup_process(&self, execution_id: &str) {
-        let mut processes = self.running_processes.lock().await;
-        
-        if let Some(mut process) = processes.remove(execution_id) {
-            // Try to kill the process if it's still running
-            if let Ok(None) = process.child.try_wait() {
-                warn!("Killing chaincode process: {}", execution_id);
-                let _ = process.child.kill().await;
-            }
-        }
+    /// Ordered trace of shim interactions (`GetState`/`PutState`/`DeleteState`/
+    /// `SetEvent`) made by a given execution, if tracing was enabled via
+    /// `ChaincodeExecutorConfig::trace_execution`. Empty if it wasn't, or if
+    /// `execution_id` is unknown.
+    pub async fn get_trace(&self, execution_id: &str) -> Vec<ChaincodeTraceStep> {
+        self.grpc_service.get_trace(execution_id).await
+            .into_iter()
+            .map(|t| ChaincodeTraceStep {
+                operation: t.operation,
+                key: t.key,
+                size: t.size,
+                found: t.found,
+                elapsed_ms: t.elapsed_ms,
+            })
+            .collect()
     }
 
-    /// Get information about currently running processes
+    /// Chaincode IDs with at least one pooled worker (idle or checked out)
     pub async fn get_running_processes(&self) -> Vec<String> {
-        let processes = self.running_processes.lock().await;
-        processes.keys().cloned().collect()
+        let idle = self.pool.idle.lock().await;
+        let checked_out = self.pool.checked_out.lock().await;
+        let mut ids: std::collections::HashSet<String> = idle.keys().cloned().collect();
+        ids.extend(checked_out.values().map(|w| w.chaincode_id.clone()));
+        ids.into_iter().collect()
     }
 
     /// Get the number of active executions
     pub async fn get_active_count(&self) -> usize {
         *self.active_executions.read().await
     }
-
-    /// Cleanup expired processes (processes that have been running too long)
-    pub async fn cleanup_expired_processes(&self) {
-        let mut processes = self.running_processes.lock().await;
-        let mut to_remove = Vec::new();
-
-        for (execution_id, process) in processes.iter_mut() {
-            if process.started_at.elapsed() > self.config.execution_timeout {
-                warn!("Killing expired chaincode process: {}", execution_id);
-                let _ = process.child.kill().await;
-                to_remove.push(execution_id.clone());
-            }
-        }
-
-        for execution_id in to_remove {
-            processes.remove(&execution_id);
-        }
-    }
 }
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info, warn};
 use beacon_core::{BeaconResult, BeaconError};
@@ -15,14 +18,72 @@ use chaincode::{
     *,
 };
 
+/// Page size used for `get_state_by_range`/`get_state_by_partial_composite_key`
+/// when the request's `page_size` is zero.
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Bounded channel capacity for `get_state_by_range_stream`; applies
+/// backpressure to the page-fetching producer task when the client is slow
+/// to consume entries.
+const STREAM_CHANNEL_CAPACITY: usize = 64;
+
+/// Maximum depth of nested `invoke_chaincode` calls before a transaction is
+/// rejected - guards against runaway/infinite cross-chaincode recursion.
+const MAX_CHAINCODE_CALL_DEPTH: u32 = 5;
+
+/// gRPC request metadata key carrying the execution ID a chaincode process
+/// was dispatched with, so the shim can tell which of several concurrent
+/// invocations a given RPC belongs to. Set by the chaincode-side SDK from
+/// the `execution_id` it was handed on its stdin invocation line (see
+/// `ChaincodeExecutor::dispatch_invocation`).
+const EXECUTION_ID_METADATA_KEY: &str = "beacon-execution-id";
+
+/// EVM-style fixed gas costs charged against `transaction.gas_limit` for
+/// shim operations observed during a chaincode invocation - see
+/// `ChaincodeShimService::charge_gas`. Values are arbitrary but proportioned
+/// the way the EVM's are: a flat per-invocation floor, reads cheaper than
+/// writes, and writes/events additionally priced per byte so a chaincode
+/// can't store its way around the limit with one gigantic value.
+pub(crate) const GAS_BASE_INVOCATION_COST: u64 = 1_000;
+const GAS_GET_STATE_COST: u64 = 200;
+const GAS_PUT_STATE_BASE_COST: u64 = 500;
+const GAS_PUT_STATE_PER_BYTE_COST: u64 = 3;
+const GAS_DELETE_STATE_COST: u64 = 300;
+const GAS_RANGE_QUERY_COST: u64 = 500;
+const GAS_PREFIX_QUERY_COST: u64 = 500;
+const GAS_SET_EVENT_BASE_COST: u64 = 200;
+const GAS_SET_EVENT_PER_BYTE_COST: u64 = 1;
+const GAS_INVOKE_CHAINCODE_COST: u64 = 1_000;
+
 /// Chaincode execution context that maintains state during a transaction
 #[derive(Debug, Clone)]
 pub struct ChaincodeContext {
+    /// Identifies which concurrent execution this context belongs to - the
+    /// key `ChaincodeShimService` uses to route a request to the right
+    /// context/events/state_changes/read_set slot instead of a single
+    /// shared one. See `EXECUTION_ID_METADATA_KEY`.
+    pub execution_id: String,
     pub transaction_id: String,
     pub channel_id: String,
     pub creator: Vec<u8>,
     pub timestamp: i64,
     pub chaincode_id: String,
+    /// Ceiling on cumulative gas this execution may charge (see
+    /// `ChaincodeShimService::charge_gas`) before it's aborted as `OutOfGas`
+    /// - the transaction's `gas_limit`.
+    pub gas_limit: u64,
+    /// Number of `invoke_chaincode` hops from the transaction's top-level
+    /// chaincode to this context - 0 for the chaincode the transaction
+    /// directly names.
+    pub call_depth: u32,
+    /// Chaincode IDs on the current call path, top-level first, used to
+    /// detect a direct or indirect invocation cycle before it recurses
+    /// forever.
+    pub call_stack: Vec<String>,
+    /// When this execution began - `TraceStep::elapsed_ms` is measured
+    /// from here, so a captured trace can be replayed with real timing
+    /// deltas between steps.
+    pub started_at: Instant,
 }
 
 #[derive(Debug, Clone)]
@@ -38,48 +99,479 @@ pub struct StateChange {
     pub operation: String, // PUT, DELETE
 }
 
-/// The gRPC server that handles chaincode communication
+/// A single write in a `BatchRequest`
+#[derive(Debug, Clone)]
+pub struct WriteOp {
+    pub key: String,
+    pub value: Vec<u8>,
+    pub operation: String, // PUT, DELETE
+}
+
+/// K2V-style batch request: reads and writes applied in one round trip.
+/// Reads observe a snapshot taken before this batch's writes are applied.
+#[derive(Debug, Clone, Default)]
+pub struct BatchRequest {
+    pub read_batch: Vec<String>,
+    pub write_batch: Vec<WriteOp>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchReadResult {
+    pub key: String,
+    pub value: Vec<u8>,
+    pub found: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchWriteResult {
+    pub key: String,
+    pub success: bool,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchResponse {
+    pub reads: Vec<BatchReadResult>,
+    pub writes: Vec<BatchWriteResult>,
+}
+
+/// One entry in a transaction's read-set, recorded for MVCC conflict
+/// detection under simulate-then-validate - see `ChaincodeShimService::validate_read_set`.
+#[derive(Debug, Clone)]
+pub enum ReadSetEntry {
+    /// A single-key `get_state`: the key and the version observed via
+    /// `StateStorage::key_version`.
+    Key { key: String, version: u64 },
+    /// A `get_state_by_range`/`get_state_by_range_stream` scan: the bounds
+    /// and the global `StateStorage::state_version` observed, so a phantom
+    /// insert/delete inside the range is caught even if no individual key
+    /// the scan returned changed its own version.
+    Range { start_key: String, end_key: String, version: u64 },
+    /// A `get_state_by_partial_composite_key` scan: the key prefix and the
+    /// global `state_version` observed, for the same phantom-read reason as `Range`.
+    Prefix { prefix: String, version: u64 },
+}
+
+/// A read-set entry (see `ReadSetEntry`) whose recorded version no longer
+/// matches the current state - MVCC_READ_CONFLICT.
+#[derive(Debug, Clone)]
+pub struct MvccReadConflict {
+    pub entry: ReadSetEntry,
+}
+
+impl std::fmt::Display for MvccReadConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MVCC_READ_CONFLICT: {:?}", self.entry)
+    }
+}
+
+impl std::error::Error for MvccReadConflict {}
+
+/// One shim interaction recorded for an execution's audit trace, when
+/// `ChaincodeShimService::trace_execution` is enabled - see
+/// `ChaincodeShimService::record_trace` and `ChaincodeExecutor::get_trace`.
+/// Modeled on `StateChange`'s flat, string-tagged shape rather than an enum
+/// like `ReadSetEntry`, since a trace step is really an annotated
+/// read/write/event plus a recorded key and timing delta.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    /// GET_STATE, PUT_STATE, DELETE_STATE, or SET_EVENT
+    pub operation: String,
+    /// The state key touched, or the event name for SET_EVENT
+    pub key: String,
+    /// Size in bytes of the value read/written/emitted - 0 for a GET_STATE miss
+    pub size: usize,
+    /// Whether a GET_STATE read found an existing value - always `true` for
+    /// every other operation
+    pub found: bool,
+    /// Milliseconds elapsed since `ChaincodeContext::started_at` when this
+    /// step was recorded.
+    pub elapsed_ms: u64,
+}
+
+/// Per-execution collected results: the slot `events`/`state_changes`/
+/// `read_set`/`trace` occupy for one execution ID, bundled together so
+/// inserting and clearing an execution's entry is one map operation
+/// instead of four.
+#[derive(Debug, Clone, Default)]
+struct ExecutionResults {
+    events: Vec<Event>,
+    state_changes: Vec<StateChange>,
+    read_set: Vec<ReadSetEntry>,
+    /// Cumulative gas charged so far via `ChaincodeShimService::charge_gas`.
+    gas_used: u64,
+    /// Ordered shim-interaction trace - see `TraceStep`. Empty unless
+    /// `ChaincodeShimService::trace_execution` is set.
+    trace: Vec<TraceStep>,
+}
+
+/// The gRPC server that handles chaincode communication.
+///
+/// With `max_concurrent` executions able to run at once, a single shared
+/// context/events/state_changes/read_set would let concurrent chaincodes
+/// clobber each other's creator/transaction_id and mix up collected
+/// results. Every RPC instead carries its execution ID in request metadata
+/// (`EXECUTION_ID_METADATA_KEY`) and is routed to that execution's own slot
+/// in these maps - the same session-multiplexing approach a single gRPC
+/// endpoint needs to serve many concurrent clients.
 #[derive(Clone)]
 pub struct ChaincodeShimService {
     state_storage: Arc<StateStorage>,
-    current_context: Arc<RwLock<Option<ChaincodeContext>>>,
-    events: Arc<RwLock<Vec<Event>>>,
-    state_changes: Arc<RwLock<Vec<StateChange>>>,
+    contexts: Arc<RwLock<HashMap<String, ChaincodeContext>>>,
+    results: Arc<RwLock<HashMap<String, ExecutionResults>>>,
+    /// Whether to record a `TraceStep` for every GetState/PutState/DeleteState/
+    /// SetEvent call - see `ChaincodeExecutorConfig::trace_execution`. Off by
+    /// default so production nodes pay no bookkeeping cost for a debugging
+    /// feature they haven't opted into.
+    trace_execution: bool,
 }
 
 impl ChaincodeShimService {
-    pub fn new(state_storage: Arc<StateStorage>) -> Self {
+    pub fn new(state_storage: Arc<StateStorage>, trace_execution: bool) -> Self {
         Self {
             state_storage,
-            current_context: Arc::new(RwLock::new(None)),
-            events: Arc::new(RwLock::new(Vec::new())),
-            state_changes: Arc::new(RwLock::new(Vec::new())),
+            contexts: Arc::new(RwLock::new(HashMap::new())),
+            results: Arc::new(RwLock::new(HashMap::new())),
+            trace_execution,
         }
     }
 
-    /// Set the current execution context
+    /// Extract the execution ID a request's metadata was tagged with.
+    fn execution_id_from_metadata<T>(request: &Request<T>) -> Result<String, Status> {
+        request
+            .metadata()
+            .get(EXECUTION_ID_METADATA_KEY)
+            .ok_or_else(|| Status::invalid_argument(format!("Missing {} metadata", EXECUTION_ID_METADATA_KEY)))?
+            .to_str()
+            .map(str::to_string)
+            .map_err(|_| Status::invalid_argument(format!("{} metadata is not ASCII", EXECUTION_ID_METADATA_KEY)))
+    }
+
+    /// Look up the calling context for a request's execution ID.
+    async fn context_for(&self, execution_id: &str) -> Result<ChaincodeContext, Status> {
+        self.contexts
+            .read()
+            .await
+            .get(execution_id)
+            .cloned()
+            .ok_or_else(|| Status::failed_precondition("No transaction context"))
+    }
+
+    /// Charge `cost` gas against `execution_id`'s cumulative usage and abort
+    /// with `Status::resource_exhausted` the moment it exceeds the
+    /// transaction's `gas_limit`. Callers are expected to propagate the
+    /// error with `?` before performing the operation they were about to
+    /// charge for, so a rejected call never takes effect.
+    pub async fn charge_gas(&self, execution_id: &str, cost: u64) -> Result<(), Status> {
+        let gas_limit = self
+            .contexts
+            .read()
+            .await
+            .get(execution_id)
+            .map(|ctx| ctx.gas_limit)
+            .unwrap_or(u64::MAX);
+
+        let mut results = self.results.write().await;
+        let Some(results) = results.get_mut(execution_id) else {
+            return Ok(());
+        };
+        results.gas_used = results.gas_used.saturating_add(cost);
+
+        if results.gas_used > gas_limit {
+            warn!(
+                "Execution {} exceeded gas limit: {} used of {} allowed",
+                execution_id, results.gas_used, gas_limit
+            );
+            return Err(Status::resource_exhausted(format!(
+                "Out of gas: used {} of limit {}",
+                results.gas_used, gas_limit
+            )));
+        }
+        Ok(())
+    }
+
+    /// Cumulative gas charged for `execution_id` so far.
+    pub async fn gas_used(&self, execution_id: &str) -> u64 {
+        self.results.read().await.get(execution_id).map(|r| r.gas_used).unwrap_or(0)
+    }
+
+    /// Append a `TraceStep` for `execution_id`, timestamped as milliseconds
+    /// since its `ChaincodeContext::started_at`. A no-op when
+    /// `trace_execution` is disabled, so tracing adds no overhead on a node
+    /// that hasn't opted in.
+    async fn record_trace(&self, execution_id: &str, operation: &str, key: &str, size: usize, found: bool) {
+        if !self.trace_execution {
+            return;
+        }
+        let elapsed_ms = self
+            .contexts
+            .read()
+            .await
+            .get(execution_id)
+            .map(|ctx| ctx.started_at.elapsed().as_millis() as u64)
+            .unwrap_or(0);
+        if let Some(results) = self.results.write().await.get_mut(execution_id) {
+            results.trace.push(TraceStep {
+                operation: operation.to_string(),
+                key: key.to_string(),
+                size,
+                found,
+                elapsed_ms,
+            });
+        }
+    }
+
+    /// The ordered shim-interaction trace recorded for `execution_id` so
+    /// far - empty unless `trace_execution` is enabled. Callable both while
+    /// an execution is still in flight (for live diagnosis) and afterwards
+    /// via `ChaincodeExecutor::get_trace`.
+    pub async fn get_trace(&self, execution_id: &str) -> Vec<TraceStep> {
+        self.results.read().await.get(execution_id).map(|r| r.trace.clone()).unwrap_or_default()
+    }
+
+    /// Register a new execution's context and reset its results slot.
     pub async fn set_context(&self, context: ChaincodeContext) {
-        let mut ctx = self.current_context.write().await;
-        *ctx = Some(context);
-        
-        // Clear previous execution results
-        let mut events = self.events.write().await;
-        events.clear();
-        let mut state_changes = self.state_changes.write().await;
-        state_changes.clear();
+        let execution_id = context.execution_id.clone();
+        self.contexts.write().await.insert(execution_id.clone(), context);
+        self.results.write().await.insert(execution_id, ExecutionResults::default());
+    }
+
+    /// Drop an execution's context and results slot once it's finished.
+    pub async fn clear_context(&self, execution_id: &str) {
+        self.contexts.write().await.remove(execution_id);
+        self.results.write().await.remove(execution_id);
     }
 
-    /// Clear the current execution context
-    pub async fn clear_context(&self) {
-        let mut ctx = self.current_context.write().await;
-        *ctx = None;
+    /// Take the events, state changes, and accumulated read-set collected
+    /// for `execution_id`.
+    pub async fn get_execution_results(&self, execution_id: &str) -> (Vec<Event>, Vec<StateChange>, Vec<ReadSetEntry>) {
+        match self.results.read().await.get(execution_id) {
+            Some(results) => (results.events.clone(), results.state_changes.clone(), results.read_set.clone()),
+            None => (Vec::new(), Vec::new(), Vec::new()),
+        }
+    }
+
+    /// Record a `get_state` read into `execution_id`'s read-set. Logged and
+    /// dropped on failure rather than surfaced to the caller, matching how
+    /// the shim already treats bookkeeping as best-effort: losing a version
+    /// means `validate_read_set` conservatively treats it as version `0`,
+    /// which can only cause a spurious conflict, never a missed one.
+    async fn record_key_read(&self, execution_id: &str, key: &str) {
+        match self.state_storage.key_version(key).await {
+            Ok(version) => {
+                if let Some(results) = self.results.write().await.get_mut(execution_id) {
+                    results.read_set.push(ReadSetEntry::Key { key: key.to_string(), version });
+                }
+            }
+            Err(e) => warn!("Failed to record read-set entry for key {}: {}", key, e),
+        }
     }
 
-    /// Get events and state changes from the current execution
-    pub async fn get_execution_results(&self) -> (Vec<Event>, Vec<StateChange>) {
-        let events = self.events.read().await;
-        let state_changes = self.state_changes.read().await;
-        (events.clone(), state_changes.clone())
+    /// Record a `get_state_by_range`/`get_state_by_range_stream` read into
+    /// `execution_id`'s read-set.
+    async fn record_range_read(&self, execution_id: &str, start_key: &str, end_key: &str) {
+        match self.state_storage.state_version().await {
+            Ok(version) => {
+                if let Some(results) = self.results.write().await.get_mut(execution_id) {
+                    results.read_set.push(ReadSetEntry::Range {
+                        start_key: start_key.to_string(),
+                        end_key: end_key.to_string(),
+                        version,
+                    });
+                }
+            }
+            Err(e) => warn!("Failed to record read-set entry for range {}..{}: {}", start_key, end_key, e),
+        }
+    }
+
+    /// Record a `get_state_by_partial_composite_key` read into
+    /// `execution_id`'s read-set.
+    async fn record_prefix_read(&self, execution_id: &str, prefix: &str) {
+        match self.state_storage.state_version().await {
+            Ok(version) => {
+                if let Some(results) = self.results.write().await.get_mut(execution_id) {
+                    results.read_set.push(ReadSetEntry::Prefix { prefix: prefix.to_string(), version });
+                }
+            }
+            Err(e) => warn!("Failed to record read-set entry for prefix {}: {}", prefix, e),
+        }
+    }
+
+    /// Re-check every entry recorded in `read_set` against the current state
+    /// and return the conflicts, if any.
+    ///
+    /// This only detects conflicts - it doesn't prevent them. Writes in this
+    /// shim land on `StateStorage` eagerly, as each `put_state`/`delete_state`
+    /// call is handled during execution, not staged and applied atomically
+    /// at commit time. So by the time this runs (after execution completes,
+    /// in `ChaincodeExecutor::collect_execution_result`), this transaction's own
+    /// writes are already visible in `StateStorage` and already bumped the
+    /// versions this read-set records - a `put_state` that wrote back the
+    /// same key it had read would conflict with itself. Callers must read
+    /// this return value, not rely on it to have blocked anything, and on
+    /// conflict must discard `state_changes` before they're committed by the
+    /// block pipeline rather than expect the writes to have been rolled back
+    /// here.
+    pub async fn validate_read_set(&self, read_set: &[ReadSetEntry]) -> Vec<MvccReadConflict> {
+        let mut conflicts = Vec::new();
+        for entry in read_set {
+            let current = match entry {
+                ReadSetEntry::Key { key, .. } => self.state_storage.key_version(key).await,
+                ReadSetEntry::Range { .. } | ReadSetEntry::Prefix { .. } => self.state_storage.state_version().await,
+            };
+            let recorded = match entry {
+                ReadSetEntry::Key { version, .. } => *version,
+                ReadSetEntry::Range { version, .. } => *version,
+                ReadSetEntry::Prefix { version, .. } => *version,
+            };
+            match current {
+                Ok(current) if current != recorded => {
+                    conflicts.push(MvccReadConflict { entry: entry.clone() });
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Failed to validate read-set entry {:?}: {}", entry, e);
+                    conflicts.push(MvccReadConflict { entry: entry.clone() });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Server-streaming counterpart to `get_state_by_range`: streams
+    /// `KeyValue` entries for `[start_key, end_key)` as they're read instead
+    /// of collecting the whole range into one response. There's no lazy
+    /// row iterator behind the `StorageBackend` trait to drive directly, so
+    /// this reads `StateStorage` a bounded page at a time and forwards each
+    /// entry through a bounded mpsc channel - shim memory stays O(page_size)
+    /// rather than O(range), and a slow client throttles the producer via
+    /// the channel filling up. If the client drops the returned stream, the
+    /// next send fails, the producing task returns, and the in-flight page
+    /// read is released without fetching any more.
+    pub fn get_state_by_range_stream(
+        &self,
+        execution_id: String,
+        req: GetStateByRangeRequest,
+    ) -> ReceiverStream<Result<KeyValue, Status>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(STREAM_CHANNEL_CAPACITY);
+        let state_storage = self.state_storage.clone();
+        let results = self.results.clone();
+
+        tokio::spawn(async move {
+            // Recorded up front rather than after the scan completes: the
+            // global version only moves forward, so a version observed
+            // before the first page is a safe (if slightly conservative)
+            // lower bound for what the whole streamed range saw.
+            match state_storage.state_version().await {
+                Ok(version) => {
+                    if let Some(results) = results.write().await.get_mut(&execution_id) {
+                        results.read_set.push(ReadSetEntry::Range {
+                            start_key: req.start_key.clone(),
+                            end_key: req.end_key.clone(),
+                            version,
+                        });
+                    }
+                }
+                Err(e) => warn!("Failed to record read-set entry for streamed range {}..{}: {}", req.start_key, req.end_key, e),
+            }
+
+            let page_size = if req.page_size == 0 { DEFAULT_PAGE_SIZE } else { req.page_size as usize };
+            let mut bookmark: Option<Vec<u8>> = None;
+
+            loop {
+                let (entries, has_more) = match state_storage
+                    .get_state_range_page(&req.start_key, &req.end_key, bookmark.as_deref(), page_size)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(e) => {
+                        let _ = tx
+                            .send(Err(Status::internal(format!("Failed to get state by range: {}", e))))
+                            .await;
+                        return;
+                    }
+                };
+
+                let last_key = entries.last().map(|(key, _)| key.clone());
+                for (key, value) in entries {
+                    let entry = KeyValue { key: String::from_utf8_lossy(&key).into_owned(), value };
+                    if tx.send(Ok(entry)).await.is_err() {
+                        return;
+                    }
+                }
+
+                if !has_more {
+                    return;
+                }
+                bookmark = last_key;
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Apply a `BatchRequest`'s reads and writes in one round trip. Reads
+    /// are served first, against the state as it stood before this batch's
+    /// writes land, then every write is committed to `StateStorage` in a
+    /// single atomic `apply_batch` call - either all of them take effect or
+    /// none do - and recorded into `state_changes` as one contiguous group,
+    /// the same bookkeeping `put_state`/`delete_state` do individually.
+    /// Removes the N round-trips chaincode otherwise pays calling those in
+    /// a loop, and gives multi-key updates a single failure boundary.
+    pub async fn batch(&self, execution_id: &str, req: BatchRequest) -> Result<BatchResponse, Status> {
+        let mut reads = Vec::with_capacity(req.read_batch.len());
+        for key in &req.read_batch {
+            match self.state_storage.get_state(key).await {
+                Ok(Some(value)) => reads.push(BatchReadResult { key: key.clone(), value, found: true }),
+                Ok(None) => reads.push(BatchReadResult { key: key.clone(), value: Vec::new(), found: false }),
+                Err(e) => return Err(Status::internal(format!("Failed to read {} in batch: {}", key, e))),
+            }
+        }
+
+        if req.write_batch.is_empty() {
+            return Ok(BatchResponse { reads, writes: Vec::new() });
+        }
+
+        let ops: Vec<(String, Option<Vec<u8>>)> = req
+            .write_batch
+            .iter()
+            .map(|op| {
+                if op.operation == "DELETE" {
+                    (op.key.clone(), None)
+                } else {
+                    (op.key.clone(), Some(op.value.clone()))
+                }
+            })
+            .collect();
+
+        let writes = match self.state_storage.apply_batch(ops).await {
+            Ok(()) => {
+                if let Some(results) = self.results.write().await.get_mut(execution_id) {
+                    results.state_changes.extend(req.write_batch.iter().map(|op| StateChange {
+                        key: op.key.clone(),
+                        value: op.value.clone(),
+                        operation: op.operation.clone(),
+                    }));
+                }
+                req.write_batch
+                    .iter()
+                    .map(|op| BatchWriteResult { key: op.key.clone(), success: true, error: String::new() })
+                    .collect()
+            }
+            Err(e) => {
+                error!("Failed to apply batch writes: {}", e);
+                req.write_batch
+                    .iter()
+                    .map(|op| BatchWriteResult {
+                        key: op.key.clone(),
+                        success: false,
+                        error: format!("Failed to apply batch: {}", e),
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(BatchResponse { reads, writes })
     }
 }
 
@@ -89,12 +581,17 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<GetStateRequest>,
     ) -> Result<Response<GetStateResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Getting state for key: {}", req.key);
 
+        self.charge_gas(&execution_id, GAS_GET_STATE_COST).await?;
+
         match self.state_storage.get_state(&req.key).await {
             Ok(Some(value)) => {
                 debug!("Found state for key {}: {} bytes", req.key, value.len());
+                self.record_key_read(&execution_id, &req.key).await;
+                self.record_trace(&execution_id, "GET_STATE", &req.key, value.len(), true).await;
                 Ok(Response::new(GetStateResponse {
                     value,
                     found: true,
@@ -102,6 +599,10 @@ impl ChaincodeShim for ChaincodeShimService {
             }
             Ok(None) => {
                 debug!("No state found for key: {}", req.key);
+                // A miss is still a read - if the key is inserted before commit,
+                // that's a conflict the read-set must catch.
+                self.record_key_read(&execution_id, &req.key).await;
+                self.record_trace(&execution_id, "GET_STATE", &req.key, 0, false).await;
                 Ok(Response::new(GetStateResponse {
                     value: vec![],
                     found: false,
@@ -118,18 +619,22 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<PutStateRequest>,
     ) -> Result<Response<PutStateResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Putting state for key: {} ({} bytes)", req.key, req.value.len());
 
+        let put_cost = GAS_PUT_STATE_BASE_COST + req.value.len() as u64 * GAS_PUT_STATE_PER_BYTE_COST;
+        self.charge_gas(&execution_id, put_cost).await?;
+
         // Record the state change
-        {
-            let mut changes = self.state_changes.write().await;
-            changes.push(StateChange {
+        if let Some(results) = self.results.write().await.get_mut(&execution_id) {
+            results.state_changes.push(StateChange {
                 key: req.key.clone(),
                 value: req.value.clone(),
                 operation: "PUT".to_string(),
             });
         }
+        self.record_trace(&execution_id, "PUT_STATE", &req.key, req.value.len(), true).await;
 
         match self.state_storage.put_state(&req.key, req.value).await {
             Ok(_) => {
@@ -153,18 +658,21 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<DeleteStateRequest>,
     ) -> Result<Response<DeleteStateResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Deleting state for key: {}", req.key);
 
+        self.charge_gas(&execution_id, GAS_DELETE_STATE_COST).await?;
+
         // Record the state change
-        {
-            let mut changes = self.state_changes.write().await;
-            changes.push(StateChange {
+        if let Some(results) = self.results.write().await.get_mut(&execution_id) {
+            results.state_changes.push(StateChange {
                 key: req.key.clone(),
                 value: vec![],
                 operation: "DELETE".to_string(),
             });
         }
+        self.record_trace(&execution_id, "DELETE_STATE", &req.key, 0, true).await;
 
         match self.state_storage.delete_state(&req.key).await {
             Ok(_) => {
@@ -188,21 +696,48 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<GetStateByRangeRequest>,
     ) -> Result<Response<GetStateByRangeResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Getting state by range: {} to {}", req.start_key, req.end_key);
 
-        match self.state_storage.get_state_range(&req.start_key, &req.end_key).await {
-            Ok(results) => {
+        self.charge_gas(&execution_id, GAS_RANGE_QUERY_COST).await?;
+
+        let page_size = if req.page_size == 0 { DEFAULT_PAGE_SIZE } else { req.page_size as usize };
+        // An empty bookmark means "start from start_key"; otherwise it's the
+        // storage-level key the previous page ended at, so resuming is exact
+        // even if keys were inserted/deleted in between.
+        let bookmark = (!req.bookmark.is_empty()).then(|| req.bookmark.as_bytes());
+
+        match self
+            .state_storage
+            .get_state_range_page(&req.start_key, &req.end_key, bookmark, page_size)
+            .await
+        {
+            Ok((results, has_more)) => {
+                let next_bookmark = if has_more {
+                    results
+                        .last()
+                        .map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+
                 let key_values: Vec<KeyValue> = results
                     .into_iter()
-                    .map(|(key, value)| KeyValue { key, value })
+                    .map(|(key, value)| KeyValue { key: String::from_utf8_lossy(&key).into_owned(), value })
                     .collect();
 
-                debug!("Found {} results for range query", key_values.len());
+                debug!(
+                    "Found {} results for range query (has_more: {})",
+                    key_values.len(),
+                    has_more
+                );
+                self.record_range_read(&execution_id, &req.start_key, &req.end_key).await;
                 Ok(Response::new(GetStateByRangeResponse {
                     results: key_values,
-                    has_more: false, // For simplicity, assume no pagination for now
-                    bookmark: String::new(),
+                    has_more,
+                    bookmark: next_bookmark,
                 }))
             }
             Err(e) => {
@@ -216,8 +751,9 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<GetStateByPartialCompositeKeyRequest>,
     ) -> Result<Response<GetStateByPartialCompositeKeyResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
-        
+
         // Build composite key prefix
         let mut prefix = req.object_type;
         for key in req.keys {
@@ -227,18 +763,37 @@ impl ChaincodeShim for ChaincodeShimService {
         
         debug!("Getting state by partial composite key: {}", prefix);
 
-        match self.state_storage.get_state_with_prefix(&prefix).await {
-            Ok(results) => {
+        self.charge_gas(&execution_id, GAS_PREFIX_QUERY_COST).await?;
+
+        let page_size = if req.page_size == 0 { DEFAULT_PAGE_SIZE } else { req.page_size as usize };
+        let bookmark = (!req.bookmark.is_empty()).then(|| req.bookmark.as_bytes());
+
+        match self.state_storage.get_state_with_prefix_page(&prefix, bookmark, page_size).await {
+            Ok((results, has_more)) => {
+                let next_bookmark = if has_more {
+                    results
+                        .last()
+                        .map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+
                 let key_values: Vec<KeyValue> = results
                     .into_iter()
-                    .map(|(key, value)| KeyValue { key, value })
+                    .map(|(key, value)| KeyValue { key: String::from_utf8_lossy(&key).into_owned(), value })
                     .collect();
 
-                debug!("Found {} results for composite key query", key_values.len());
+                debug!(
+                    "Found {} results for composite key query (has_more: {})",
+                    key_values.len(),
+                    has_more
+                );
+                self.record_prefix_read(&execution_id, &prefix).await;
                 Ok(Response::new(GetStateByPartialCompositeKeyResponse {
                     results: key_values,
-                    has_more: false,
-                    bookmark: String::new(),
+                    has_more,
+                    bookmark: next_bookmark,
                 }))
             }
             Err(e) => {
@@ -250,95 +805,72 @@ impl ChaincodeShim for ChaincodeShimService {
 
     async fn get_transaction_id(
         &self,
-        _request: Request<Empty>,
+        request: Request<Empty>,
     ) -> Result<Response<GetTransactionIdResponse>, Status> {
-        let context = self.current_context.read().await;
-        match &*context {
-            Some(ctx) => {
-                debug!("Returning transaction ID: {}", ctx.transaction_id);
-                Ok(Response::new(GetTransactionIdResponse {
-                    transaction_id: ctx.transaction_id.clone(),
-                }))
-            }
-            None => {
-                warn!("No transaction context available");
-                Err(Status::failed_precondition("No transaction context"))
-            }
-        }
+        let execution_id = Self::execution_id_from_metadata(&request)?;
+        let ctx = self.context_for(&execution_id).await?;
+        debug!("Returning transaction ID: {}", ctx.transaction_id);
+        Ok(Response::new(GetTransactionIdResponse {
+            transaction_id: ctx.transaction_id,
+        }))
     }
 
     async fn get_channel_id(
         &self,
-        _request: Request<Empty>,
+        request: Request<Empty>,
     ) -> Result<Response<GetChannelIdResponse>, Status> {
-        let context = self.current_context.read().await;
-        match &*context {
-            Some(ctx) => {
-                debug!("Returning channel ID: {}", ctx.channel_id);
-                Ok(Response::new(GetChannelIdResponse {
-                    channel_id: ctx.channel_id.clone(),
-                }))
-            }
-            None => {
-                warn!("No transaction context available");
-                Err(Status::failed_precondition("No transaction context"))
-            }
-        }
+        let execution_id = Self::execution_id_from_metadata(&request)?;
+        let ctx = self.context_for(&execution_id).await?;
+        debug!("Returning channel ID: {}", ctx.channel_id);
+        Ok(Response::new(GetChannelIdResponse {
+            channel_id: ctx.channel_id,
+        }))
     }
 
     async fn get_creator(
         &self,
-        _request: Request<Empty>,
+        request: Request<Empty>,
     ) -> Result<Response<GetCreatorResponse>, Status> {
-        let context = self.current_context.read().await;
-        match &*context {
-            Some(ctx) => {
-                debug!("Returning creator: {} bytes", ctx.creator.len());
-                Ok(Response::new(GetCreatorResponse {
-                    creator: ctx.creator.clone(),
-                }))
-            }
-            None => {
-                warn!("No transaction context available");
-                Err(Status::failed_precondition("No transaction context"))
-            }
-        }
+        let execution_id = Self::execution_id_from_metadata(&request)?;
+        let ctx = self.context_for(&execution_id).await?;
+        debug!("Returning creator: {} bytes", ctx.creator.len());
+        Ok(Response::new(GetCreatorResponse {
+            creator: ctx.creator,
+        }))
     }
 
     async fn get_transaction_timestamp(
         &self,
-        _request: Request<Empty>,
+        request: Request<Empty>,
     ) -> Result<Response<GetTransactionTimestampResponse>, Status> {
-        let context = self.current_context.read().await;
-        match &*context {
-            Some(ctx) => {
-                debug!("Returning transaction timestamp: {}", ctx.timestamp);
-                Ok(Response::new(GetTransactionTimestampResponse {
-                    timestamp: ctx.timestamp,
-                }))
-            }
-            None => {
-                warn!("No transaction context available");
-                Err(Status::failed_precondition("No transaction context"))
-            }
-        }
+        let execution_id = Self::execution_id_from_metadata(&request)?;
+        let ctx = self.context_for(&execution_id).await?;
+        debug!("Returning transaction timestamp: {}", ctx.timestamp);
+        Ok(Response::new(GetTransactionTimestampResponse {
+            timestamp: ctx.timestamp,
+        }))
     }
 
     async fn set_event(
         &self,
         request: Request<SetEventRequest>,
     ) -> Result<Response<SetEventResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Setting event: {} ({} bytes)", req.name, req.payload.len());
 
+        let event_cost = GAS_SET_EVENT_BASE_COST + req.payload.len() as u64 * GAS_SET_EVENT_PER_BYTE_COST;
+        self.charge_gas(&execution_id, event_cost).await?;
+
         // Add event to collection
-        {
-            let mut events = self.events.write().await;
-            events.push(Event {
+        let payload_size = req.payload.len();
+        if let Some(results) = self.results.write().await.get_mut(&execution_id) {
+            results.events.push(Event {
                 name: req.name.clone(),
                 payload: req.payload,
             });
         }
+        self.record_trace(&execution_id, "SET_EVENT", &req.name, payload_size, true).await;
 
         Ok(Response::new(SetEventResponse {
             success: true,
@@ -350,13 +882,66 @@ impl ChaincodeShim for ChaincodeShimService {
         &self,
         request: Request<InvokeChaincodeRequest>,
     ) -> Result<Response<InvokeChaincodeResponse>, Status> {
+        let execution_id = Self::execution_id_from_metadata(&request)?;
         let req = request.into_inner();
         debug!("Invoking chaincode: {} function: {}", req.chaincode_name, req.function);
 
-        // For now, return a not implemented error
-        // In a full implementation, this would invoke another chaincode
-        warn!("Cross-chaincode invocation not yet implemented");
-        Err(Status::unimplemented("Cross-chaincode invocation not yet implemented"))
+        let caller = self.context_for(&execution_id).await?;
+        self.charge_gas(&execution_id, GAS_INVOKE_CHAINCODE_COST).await?;
+
+        if caller.call_depth >= MAX_CHAINCODE_CALL_DEPTH {
+            warn!(
+                "Rejecting invocation of {} from {}: call depth {} at max {}",
+                req.chaincode_name, caller.chaincode_id, caller.call_depth, MAX_CHAINCODE_CALL_DEPTH
+            );
+            return Err(Status::resource_exhausted(format!(
+                "Max chaincode call depth ({}) exceeded",
+                MAX_CHAINCODE_CALL_DEPTH
+            )));
+        }
+
+        if caller.call_stack.contains(&req.chaincode_name) {
+            warn!(
+                "Rejecting invocation of {} from {}: would cycle through {:?}",
+                req.chaincode_name, caller.chaincode_id, caller.call_stack
+            );
+            return Err(Status::failed_precondition(format!(
+                "Cyclic chaincode invocation: {} already on call stack {:?}",
+                req.chaincode_name, caller.call_stack
+            )));
+        }
+
+        let mut call_stack = caller.call_stack.clone();
+        call_stack.push(req.chaincode_name.clone());
+        let _nested_context = ChaincodeContext {
+            // A nested invocation is its own execution for routing purposes,
+            // so it would need a freshly generated execution ID here rather
+            // than inheriting the caller's - there's nowhere to dispatch it
+            // yet (see below), so none is minted.
+            execution_id: String::new(),
+            transaction_id: caller.transaction_id.clone(),
+            channel_id: caller.channel_id.clone(),
+            creator: caller.creator.clone(),
+            timestamp: caller.timestamp,
+            chaincode_id: req.chaincode_name.clone(),
+            // Nested invocation shares the remaining gas budget of its caller.
+            gas_limit: caller.gas_limit.saturating_sub(self.gas_used(&execution_id).await),
+            call_depth: caller.call_depth + 1,
+            call_stack,
+            started_at: Instant::now(),
+        };
+
+        // The recursion/cycle guards above are real and enforced. What's
+        // missing is a way to actually run `req.chaincode_name`'s function:
+        // this shim is the server the *target* chaincode process talks to
+        // for state access - it doesn't hold a handle back to
+        // `ChaincodeExecutor`, which is what spawns and drives chaincode
+        // processes, so there's no process here to dispatch `_nested_context`
+        // to and no events/state_changes to merge back yet. Wiring that
+        // needs `ChaincodeExecutor` to expose an entry point this service
+        // can call into, which is a bigger change than this RPC alone.
+        warn!("Cross-chaincode invocation of {} passed recursion guards but execution dispatch is not wired", req.chaincode_name);
+        Err(Status::unimplemented("Cross-chaincode invocation guard checks passed, but execution dispatch is not yet wired"))
     }
 
     async fn log_message(
@@ -385,9 +970,9 @@ pub struct ChaincodeGrpcServer {
 }
 
 impl ChaincodeGrpcServer {
-    pub fn new(state_storage: Arc<StateStorage>, addr: String) -> Self {
+    pub fn new(state_storage: Arc<StateStorage>, addr: String, trace_execution: bool) -> Self {
         Self {
-            service: Arc::new(ChaincodeShimService::new(state_storage)),
+            service: Arc::new(ChaincodeShimService::new(state_storage, trace_execution)),
             server_addr: addr,
         }
     }
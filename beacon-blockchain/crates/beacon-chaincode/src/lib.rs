@@ -1,5 +1,5 @@
 pub mod executor;
 pub mod grpc_server;
 
-pub use executor::{ChaincodeExecutor, ChaincodeExecutorConfig, ChaincodeExecutionResult, ChaincodeEvent, ChaincodeStateChange};
-pub use grpc_server::{ChaincodeShimService, ChaincodeContext};
+pub use executor::{ChaincodeExecutor, ChaincodeExecutorConfig, ChaincodeExecutionResult, ChaincodeEvent, ChaincodeStateChange, ChaincodeTraceStep, GAS_EXCEEDED_STATUS};
+pub use grpc_server::{ChaincodeShimService, ChaincodeContext, TraceStep};
@@ -0,0 +1,120 @@
+use beacon_core::{hash_message, BlockIndex, Transaction, TransactionInput, TransactionType};
+
+use crate::QuorumCert;
+
+/// Function name a `ValidatorSetChange` is carried under inside a
+/// `TransactionType::Config` transaction's `TransactionInput` - see
+/// `ValidatorSetChange::to_transaction_input`/`decode_from`.
+const VALIDATOR_SET_CHANGE_FUNCTION: &str = "validator_set_change";
+
+/// A governance-submitted change to the active validator set, letting the
+/// set change at runtime through an on-chain proposal instead of a config
+/// edit and restart. Carried as a `TransactionType::Config` transaction;
+/// `quorum_cert` must certify a quorum of the validator set that was active
+/// when it was submitted (see `Consensus::accept_validator_set_change`),
+/// and once accepted it is folded into `Consensus::active_validators_at`
+/// starting at `effective_height` - so every node that replays the chain
+/// computes the same set at the same height.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ValidatorSetChange {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+    pub effective_height: BlockIndex,
+    pub quorum_cert: QuorumCert,
+}
+
+impl ValidatorSetChange {
+    /// Deterministic hash of this change's content, excluding `quorum_cert`
+    /// itself - the value signers certify over. `quorum_cert.block_hash`
+    /// (reused here as a generic signed-content hash, same as `Checkpoint`
+    /// reuses it for a block hash) must equal this for the certificate to
+    /// be considered over the right payload.
+    pub fn content_hash(&self) -> String {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"BEACON_VALIDATOR_SET_CHANGE_V1");
+        for validator in &self.add {
+            buf.extend_from_slice(validator.as_bytes());
+            buf.push(0);
+        }
+        buf.push(0xff);
+        for validator in &self.remove {
+            buf.extend_from_slice(validator.as_bytes());
+            buf.push(0);
+        }
+        buf.push(0xff);
+        buf.extend_from_slice(&self.effective_height.to_be_bytes());
+        hash_message(&buf)
+    }
+
+    /// Encode this change as a `TransactionType::Config` transaction's
+    /// input, so it can be proposed and propagated like any other
+    /// transaction - see `decode_from`.
+    pub fn to_transaction_input(&self, chaincode_id: String) -> TransactionInput {
+        TransactionInput {
+            chaincode_id,
+            function: VALIDATOR_SET_CHANGE_FUNCTION.to_string(),
+            args: vec![serde_json::to_string(self).unwrap_or_default()],
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Recover a `ValidatorSetChange` from a `Config` transaction, if it
+    /// carries one - `None` for `Config` transactions used for anything
+    /// else, or a malformed payload.
+    pub fn decode_from(transaction: &Transaction) -> Option<Self> {
+        if transaction.tx_type != TransactionType::Config {
+            return None;
+        }
+        if transaction.input.function != VALIDATOR_SET_CHANGE_FUNCTION {
+            return None;
+        }
+        serde_json::from_str(transaction.input.args.first()?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_change() -> ValidatorSetChange {
+        let mut change = ValidatorSetChange {
+            add: vec!["new-validator".to_string()],
+            remove: vec!["old-validator".to_string()],
+            effective_height: 100,
+            quorum_cert: QuorumCert::new(String::new()),
+        };
+        change.quorum_cert.block_hash = change.content_hash();
+        change
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_payload() {
+        let change = sample_change();
+        let mut other = change.clone();
+        other.effective_height += 1;
+        assert_ne!(change.content_hash(), other.content_hash());
+    }
+
+    #[test]
+    fn test_transaction_input_round_trips_through_a_config_transaction() {
+        let change = sample_change();
+        let input = change.to_transaction_input("governance".to_string());
+
+        let transaction = Transaction::new(TransactionType::Config, beacon_core::Address::new("proposer"), None, input, 0);
+
+        assert_eq!(ValidatorSetChange::decode_from(&transaction), Some(change));
+    }
+
+    #[test]
+    fn test_decode_from_ignores_non_governance_config_transactions() {
+        let input = TransactionInput {
+            chaincode_id: "governance".to_string(),
+            function: "something_else".to_string(),
+            args: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let transaction = Transaction::new(TransactionType::Config, beacon_core::Address::new("proposer"), None, input, 0);
+
+        assert_eq!(ValidatorSetChange::decode_from(&transaction), None);
+    }
+}
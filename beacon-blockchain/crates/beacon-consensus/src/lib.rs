@@ -1,27 +1,63 @@
 pub mod proof_of_authority;
 pub mod validator;
 pub mod engine;
+pub mod quorum;
+pub mod governance;
+pub mod pipeline;
+pub mod metrics;
 
 pub use proof_of_authority::*;
 pub use validator::*;
 pub use engine::*;
+pub use quorum::*;
+pub use governance::*;
+pub use pipeline::*;
 
-use beacon_core::{BeaconResult, Block};
+use beacon_core::{BeaconResult, Block, BlockIndex, ForkActivation, ForkId};
 
 /// Consensus trait that all consensus algorithms must implement
 #[async_trait::async_trait]
 pub trait Consensus: Send + Sync {
-    /// Validate a block according to consensus rules
-    async fn validate_block(&self, block: &Block) -> BeaconResult<bool>;
-    
-    /// Create a new block (for validators)
-    async fn create_block(&self, transactions: Vec<beacon_core::Transaction>) -> BeaconResult<Block>;
-    
+    /// Validate a block according to consensus rules, given the locally
+    /// known chain tip (`None` only before genesis has been stored).
+    async fn validate_block(&self, block: &Block, tip: Option<&Block>) -> BeaconResult<bool>;
+
+    /// Create a new block extending `tip` (for validators)
+    async fn create_block(&self, transactions: Vec<beacon_core::Transaction>, tip: Option<&Block>) -> BeaconResult<Block>;
+
     /// Check if this node can create blocks
     fn can_create_blocks(&self) -> bool;
-    
-    /// Get the current consensus state
-    fn get_state(&self) -> ConsensusState;
+
+    /// Get the current consensus state for the block due at `next_height`
+    fn get_state(&self, next_height: u64) -> ConsensusState;
+
+    /// The fork active at `height`, per this engine's configured
+    /// `ForkSchedule` - used during replay so an old block is judged by the
+    /// rules that were live when it was produced, not the node's current
+    /// ones.
+    fn fork_at(&self, height: BlockIndex) -> ForkId;
+
+    /// The validator set active at `height`: the genesis-configured set with
+    /// every accepted `ValidatorSetChange` whose `effective_height` is
+    /// `<= height` folded in, in acceptance order. Mirrors `fork_at` folding
+    /// over a `ForkSchedule` - used so a block is judged by the membership
+    /// that was live at its own height, which is essential for correct
+    /// historical replay after the set has since changed.
+    fn active_validators_at(&self, height: BlockIndex) -> Vec<String>;
+
+    /// Admit a governance-submitted `ValidatorSetChange` carried by a
+    /// `TransactionType::Config` transaction at `height`: its `quorum_cert`
+    /// must certify at least a quorum of `active_validators_at(height)`.
+    /// Returns `Ok(true)` and records the change (to take effect at its own
+    /// `effective_height`) if quorum was met, `Ok(false)` otherwise.
+    fn accept_validator_set_change(&self, height: BlockIndex, change: ValidatorSetChange) -> BeaconResult<bool>;
+
+    /// Confirm that `cp` is trustworthy as a weak-subjectivity sync root:
+    /// its `quorum_cert` must carry valid signatures from at least a quorum
+    /// of this engine's configured validator set over `cp.block_hash`. A
+    /// node should only adopt a checkpoint as its trusted root when this
+    /// returns `Ok(true)` - see `SyncMode::Checkpoint`.
+    async fn verify_checkpoint(&self, cp: &Checkpoint) -> BeaconResult<bool>;
 }
 
 /// Consensus state information
@@ -31,4 +67,8 @@ pub struct ConsensusState {
     pub next_validator: Option<String>,
     pub validator_count: usize,
     pub is_synced: bool,
+    /// The fork active at the state's `next_height` - see `Consensus::fork_at`.
+    pub active_fork: ForkId,
+    /// The next scheduled fork activation after `next_height`, if any.
+    pub next_fork: Option<ForkActivation>,
 }
@@ -0,0 +1,128 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram, IntCounter,
+    IntGauge, IntGaugeVec, Registry,
+};
+use std::time::Duration;
+
+/// Registry for consensus-level gauges/counters/histograms, kept separate
+/// from `beacon-api`'s own `REGISTRY` so this crate doesn't need to depend
+/// on it - same split as `beacon-networking::metrics`; `gather` exposes the
+/// same `MetricFamily`s for the `/metrics` handler to merge in.
+pub static CONSENSUS_REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Height of the most recently imported block.
+pub static BLOCK_HEIGHT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge_with_registry!(
+        "beacon_consensus_block_height",
+        "Height of the most recently imported block",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Total blocks a `BlockImportPipeline` successfully committed.
+pub static BLOCKS_VALIDATED_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter_with_registry!(
+        "beacon_consensus_blocks_validated_total",
+        "Total blocks successfully imported and committed",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Total blocks a consensus engine rejected in `validate_block`.
+pub static BLOCKS_REJECTED_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter_with_registry!(
+        "beacon_consensus_blocks_rejected_total",
+        "Total blocks that failed Consensus::validate_block",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Total transactions committed into a block, across every import.
+pub static TRANSACTIONS_PROCESSED_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter_with_registry!(
+        "beacon_consensus_transactions_processed_total",
+        "Total transactions committed into a block",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Wall-clock time for one `BlockImportPipeline::import_block` call that
+/// produced a block, validation through commit.
+pub static CONSENSUS_ROUND_DURATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram_with_registry!(
+        "beacon_consensus_round_duration_seconds",
+        "Wall-clock time for one import_block call that produced a block",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Wall-clock time spent in the `execute_chaincode` pipeline stage.
+pub static CHAINCODE_EXECUTION_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram_with_registry!(
+        "beacon_consensus_chaincode_execution_latency_seconds",
+        "Wall-clock time spent in the execute_chaincode pipeline stage",
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// "Info"-style gauge: `beacon_consensus_validator_info{validator="...",
+/// role="current"|"next"} 1` for whichever validator currently holds each
+/// role, mirroring how `ConsensusState::current_validator`/`next_validator`
+/// are derived.
+pub static VALIDATOR_INFO: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec_with_registry!(
+        "beacon_consensus_validator_info",
+        "1 for the validator currently holding the given role (current/next proposer)",
+        &["validator", "role"],
+        CONSENSUS_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Record a `BlockImportPipeline::import_block` call that produced `block`:
+/// bumps the height gauge, the validated/transaction counters and the
+/// round-duration histogram.
+pub fn record_block_imported(height: u64, transaction_count: usize, round_duration: Duration) {
+    BLOCK_HEIGHT.set(height as i64);
+    BLOCKS_VALIDATED_TOTAL.inc();
+    TRANSACTIONS_PROCESSED_TOTAL.inc_by(transaction_count as u64);
+    CONSENSUS_ROUND_DURATION_SECONDS.observe(round_duration.as_secs_f64());
+}
+
+/// Record a block that failed `Consensus::validate_block`.
+pub fn record_block_rejected() {
+    BLOCKS_REJECTED_TOTAL.inc();
+}
+
+/// Record time spent in the `execute_chaincode` pipeline stage.
+pub fn record_chaincode_latency(duration: Duration) {
+    CHAINCODE_EXECUTION_LATENCY_SECONDS.observe(duration.as_secs_f64());
+}
+
+/// Refresh the current/next validator "info" gauges from a fresh
+/// `ConsensusState`, clearing whichever validator previously held each role
+/// first so a rotation doesn't leave a stale `1` behind.
+pub fn set_validators(current: Option<&str>, next: Option<&str>) {
+    VALIDATOR_INFO.reset();
+    if let Some(validator) = current {
+        VALIDATOR_INFO.with_label_values(&[validator, "current"]).set(1);
+    }
+    if let Some(validator) = next {
+        VALIDATOR_INFO.with_label_values(&[validator, "next"]).set(1);
+    }
+}
+
+/// Snapshot of `CONSENSUS_REGISTRY`'s metric families, for a caller (e.g.
+/// `beacon-api`'s `/metrics` handler) to append to its own scrape before
+/// encoding it in Prometheus text-exposition format.
+pub fn gather() -> Vec<prometheus::proto::MetricFamily> {
+    CONSENSUS_REGISTRY.gather()
+}
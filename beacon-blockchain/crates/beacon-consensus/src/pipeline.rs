@@ -0,0 +1,788 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use ed25519_dalek::VerifyingKey;
+use tokio::sync::RwLock;
+use tracing::warn;
+use x25519_dalek::StaticSecret;
+
+use beacon_chaincode::{ChaincodeExecutor, GAS_EXCEEDED_STATUS};
+use beacon_core::{
+    verifying_key_from_hex, BeaconError, BeaconResult, Block, BlockIndex, ConfidentialTransaction, RelativeLock,
+    SignatureScheme, StateMap, Timestamp, Transaction, TransactionEvent, TransactionResult, TransactionStatus,
+    TransactionType,
+};
+use beacon_storage::{BlockchainStorage, StateStorage, TransactionStorage, TreeRoute};
+
+use crate::{metrics, Consensus, Slasher, SlashingEvidence, ValidatorSetChange};
+
+/// Per-transaction derived data that every pipeline stage would otherwise
+/// have to recompute: the decoded sender verifying key and the outcome of
+/// ed25519 signature verification, keyed by transaction id. `validate_signatures`
+/// populates it; `execute_chaincode` reuses the decoded key as the chaincode
+/// invocation's creator identity instead of hex-decoding it a second time.
+#[derive(Default)]
+pub struct ConsensusContext {
+    entries: RwLock<HashMap<String, CachedTransaction>>,
+}
+
+struct CachedTransaction {
+    verifying_key: Option<VerifyingKey>,
+    signature_valid: bool,
+}
+
+impl ConsensusContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify `transaction`'s signature, caching the decoded key and the
+    /// result under its transaction id so later stages never redo the work.
+    /// A `Secp256k1Recoverable` transaction self-authenticates against its
+    /// declared `from` address (see `Transaction::verify_secp256k1_self_authenticating`)
+    /// and so has no ed25519 `verifying_key` to cache.
+    async fn verify_and_cache(&self, transaction: &Transaction) -> bool {
+        if let Some(cached) = self.entries.read().await.get(transaction.id.as_str()) {
+            return cached.signature_valid;
+        }
+
+        let (verifying_key, signature_valid) = match transaction.scheme {
+            SignatureScheme::Ed25519 => {
+                let verifying_key = verifying_key_from_hex(transaction.from.as_str()).ok();
+                let signature_valid = verifying_key
+                    .as_ref()
+                    .map(|key| transaction.verify_signature(key))
+                    .unwrap_or(false);
+                (verifying_key, signature_valid)
+            }
+            SignatureScheme::Secp256k1Recoverable => {
+                (None, transaction.verify_secp256k1_self_authenticating())
+            }
+        };
+
+        self.entries.write().await.insert(
+            transaction.id.as_str().to_string(),
+            CachedTransaction { verifying_key, signature_valid },
+        );
+
+        signature_valid
+    }
+
+    /// The verifying key decoded while validating `transaction`'s signature,
+    /// serialized to bytes for use as a chaincode execution's creator identity.
+    async fn creator_bytes(&self, transaction: &Transaction) -> Option<Vec<u8>> {
+        self.entries
+            .read()
+            .await
+            .get(transaction.id.as_str())
+            .and_then(|cached| cached.verifying_key.as_ref())
+            .map(|key| key.to_bytes().to_vec())
+    }
+}
+
+/// Wall-clock time spent in each stage of a single `import_block` call, in
+/// milliseconds, for profiling the import pipeline.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct StageTiming {
+    pub validate_signatures_ms: u64,
+    pub check_state_preconditions_ms: u64,
+    pub execute_chaincode_ms: u64,
+    pub commit_ms: u64,
+}
+
+/// A block assembled and persisted by `BlockImportPipeline::import_block`,
+/// together with the per-stage timing of the run that produced it.
+pub struct ImportedBlock {
+    pub block: Block,
+    pub timing: StageTiming,
+    /// Set if committing `block` revealed that its proposer already signed a
+    /// different block at the same height - see `Slasher::check_for_slashing`.
+    /// The caller is the consensus hook: it should broadcast this evidence to
+    /// peers and drive the offending validator's removal from the set.
+    pub slashing_evidence: Option<SlashingEvidence>,
+}
+
+/// Stages a batch of candidate transactions through signature validation,
+/// state-precondition checks, chaincode execution and commit. Stages run in
+/// order of increasing cost so bad transactions are rejected as cheaply as
+/// possible before any state is touched.
+pub struct BlockImportPipeline {
+    state_storage: Arc<StateStorage>,
+    transaction_storage: Arc<TransactionStorage>,
+    blockchain_storage: Arc<BlockchainStorage>,
+    chaincode_executor: Arc<ChaincodeExecutor>,
+    /// Proposer-equivocation detector, checked against every committed
+    /// block. `None` disables slashing detection entirely.
+    slasher: Option<Slasher>,
+    /// This node's X25519 identity for unwrapping `ConfidentialTransaction`
+    /// payloads it holds a wrapped-key entry for. `None` means this node
+    /// can still order confidential transactions, but only ever sees the
+    /// opaque ciphertext - same as a node without a wrapped-key entry.
+    confidential_key: Option<StaticSecret>,
+}
+
+impl BlockImportPipeline {
+    pub fn new(
+        state_storage: Arc<StateStorage>,
+        transaction_storage: Arc<TransactionStorage>,
+        blockchain_storage: Arc<BlockchainStorage>,
+        chaincode_executor: Arc<ChaincodeExecutor>,
+    ) -> Self {
+        Self {
+            state_storage,
+            transaction_storage,
+            blockchain_storage,
+            chaincode_executor,
+            slasher: None,
+            confidential_key: None,
+        }
+    }
+
+    /// Enable proposer-equivocation detection on every block this pipeline commits.
+    pub fn with_slasher(mut self, slasher: Slasher) -> Self {
+        self.slasher = Some(slasher);
+        self
+    }
+
+    /// Let this pipeline unwrap and execute `ConfidentialTransaction`
+    /// payloads wrapped for `key`'s public half, instead of only ordering
+    /// them as opaque ciphertext.
+    pub fn with_confidential_key(mut self, key: StaticSecret) -> Self {
+        self.confidential_key = Some(key);
+        self
+    }
+
+    /// Run `transactions` through the full import pipeline and, if any
+    /// survive, assemble and persist a new block via `consensus`. Returns
+    /// `None` if every transaction was rejected before commit.
+    pub async fn import_block(
+        &self,
+        transactions: Vec<Transaction>,
+        consensus: &dyn Consensus,
+        context: &ConsensusContext,
+    ) -> BeaconResult<Option<ImportedBlock>> {
+        let mut timing = StageTiming::default();
+        let pipeline_start = Instant::now();
+
+        let start = Instant::now();
+        let validated = self.validate_signatures(transactions, context).await;
+        timing.validate_signatures_ms = start.elapsed().as_millis() as u64;
+        if validated.is_empty() {
+            return Ok(None);
+        }
+
+        let start = Instant::now();
+        let precondition_checked = self.check_state_preconditions(validated).await?;
+        timing.check_state_preconditions_ms = start.elapsed().as_millis() as u64;
+        if precondition_checked.is_empty() {
+            return Ok(None);
+        }
+
+        let start = Instant::now();
+        let executed = self.execute_chaincode(precondition_checked, context, consensus).await?;
+        let chaincode_elapsed = start.elapsed();
+        timing.execute_chaincode_ms = chaincode_elapsed.as_millis() as u64;
+        metrics::record_chaincode_latency(chaincode_elapsed);
+        if executed.is_empty() {
+            return Ok(None);
+        }
+
+        let transaction_count = executed.len();
+        let start = Instant::now();
+        let block = self.commit(executed, consensus).await?;
+        timing.commit_ms = start.elapsed().as_millis() as u64;
+
+        let slashing_evidence = match &self.slasher {
+            Some(slasher) => slasher.check_for_slashing(&block).await?,
+            None => None,
+        };
+
+        metrics::record_block_imported(block.header.index, transaction_count, pipeline_start.elapsed());
+
+        Ok(Some(ImportedBlock { block, timing, slashing_evidence }))
+    }
+
+    /// Cheapest possible rejection: structural validation plus ed25519
+    /// signature verification. Also primes `context` for every later stage.
+    async fn validate_signatures(
+        &self,
+        transactions: Vec<Transaction>,
+        context: &ConsensusContext,
+    ) -> Vec<Transaction> {
+        let mut accepted = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            if let Err(e) = transaction.validate() {
+                warn!("Rejected transaction {}: {}", transaction.id.as_str(), e);
+                continue;
+            }
+            if !context.verify_and_cache(&transaction).await {
+                warn!(
+                    "Rejected transaction {}: signature verification failed",
+                    transaction.id.as_str()
+                );
+                continue;
+            }
+            accepted.push(transaction);
+        }
+        accepted
+    }
+
+    /// Rejects transactions that have already been committed, the cheapest
+    /// check that still requires a storage read, and transactions whose
+    /// `relative_lock` hasn't matured yet against the prospective next block.
+    async fn check_state_preconditions(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> BeaconResult<Vec<Transaction>> {
+        let next_index = self
+            .blockchain_storage
+            .get_latest_block()
+            .await?
+            .map(|block| block.header.index + 1)
+            .unwrap_or(0);
+
+        let mut accepted = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            if self.transaction_storage.transaction_exists(&transaction.id).await? {
+                warn!(
+                    "Rejected transaction {}: already committed",
+                    transaction.id.as_str()
+                );
+                continue;
+            }
+
+            if let Some(reason) = self.immaturity_reason(&transaction, next_index).await? {
+                warn!("Rejected transaction {}: {}", transaction.id.as_str(), reason);
+                continue;
+            }
+
+            accepted.push(transaction);
+        }
+        Ok(accepted)
+    }
+
+    /// `Some(reason)` if `transaction`'s `relative_lock` hasn't matured yet,
+    /// measured against `lock_key`'s last write (see `StateStorage::key_provenance`).
+    /// A key that has never been written has nothing to lock against, so it
+    /// always passes.
+    async fn immaturity_reason(
+        &self,
+        transaction: &Transaction,
+        next_index: u64,
+    ) -> BeaconResult<Option<String>> {
+        let (Some(lock_key), Some(lock)) = (&transaction.lock_key, &transaction.relative_lock) else {
+            return Ok(None);
+        };
+
+        let Some((last_index, last_timestamp)) = self.state_storage.key_provenance(lock_key).await? else {
+            return Ok(None);
+        };
+
+        match lock {
+            RelativeLock::Blocks(n) => {
+                let matured = next_index.saturating_sub(last_index) >= *n as u64;
+                if matured {
+                    Ok(None)
+                } else {
+                    Ok(Some(format!(
+                        "relative lock on {} requires {} blocks since height {}, only at {}",
+                        lock_key, n, last_index, next_index
+                    )))
+                }
+            }
+            RelativeLock::Seconds(n) => {
+                let elapsed_ms = Timestamp::now().to_millis() - last_timestamp.to_millis();
+                let matured = elapsed_ms >= (*n as i64) * 1000;
+                if matured {
+                    Ok(None)
+                } else {
+                    Ok(Some(format!(
+                        "relative lock on {} requires {} seconds since last write, only {} elapsed",
+                        lock_key,
+                        n,
+                        elapsed_ms / 1000
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Invokes chaincode for `Invoke`/`Deploy` transactions and applies the
+    /// resulting state changes; `Transfer` transactions have no chaincode to
+    /// run and succeed trivially; `Config` transactions are handled by
+    /// `execute_config` (today, governance-submitted validator set changes);
+    /// `Confidential` transactions are handled by `execute_confidential`.
+    async fn execute_chaincode(
+        &self,
+        transactions: Vec<Transaction>,
+        context: &ConsensusContext,
+        consensus: &dyn Consensus,
+    ) -> BeaconResult<Vec<(Transaction, TransactionResult)>> {
+        let next_index = self
+            .blockchain_storage
+            .get_latest_block()
+            .await?
+            .map(|block| block.header.index + 1)
+            .unwrap_or(0);
+
+        let mut executed = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let result = match transaction.tx_type {
+                TransactionType::Invoke | TransactionType::Deploy => {
+                    let creator = context.creator_bytes(&transaction).await.unwrap_or_default();
+                    self.run_chaincode(&transaction, creator).await
+                }
+                TransactionType::Transfer => TransactionResult {
+                    transaction: transaction.clone(),
+                    status: TransactionStatus::Success,
+                    gas_used: 0,
+                    return_value: None,
+                    error: None,
+                    state_changes: StateMap::new(),
+                    events: Vec::new(),
+                },
+                TransactionType::Config => self.execute_config(&transaction, next_index, consensus)?,
+                TransactionType::Confidential => {
+                    let creator = context.creator_bytes(&transaction).await.unwrap_or_default();
+                    self.execute_confidential(&transaction, creator).await
+                }
+            };
+
+            if !result.state_changes.is_empty() {
+                // Journaled under the prospective block index rather than a
+                // hash - the block's real hash isn't known until `commit`
+                // computes `state_root`/`state_smt_root` and folds them in.
+                // `commit` promotes this journal to the real hash via
+                // `finalize_block_journal` once it has it, so every
+                // committed block ends up with a real undo journal that
+                // `reorganize_to` can roll back.
+                self.state_storage
+                    .apply_state_changes_for_block(&next_index.to_string(), &result.state_changes)
+                    .await?;
+            }
+
+            executed.push((transaction, result));
+        }
+        Ok(executed)
+    }
+
+    /// Handles a `Config` transaction. If it carries a `ValidatorSetChange`
+    /// (see `ValidatorSetChange::decode_from`), it is admitted only once
+    /// `consensus.accept_validator_set_change` confirms a quorum of the
+    /// validator set active at `next_index` signed off on it - otherwise the
+    /// transaction fails rather than silently applying an unauthorized
+    /// change. Any other `Config` payload is accepted as a no-op, same as
+    /// `Transfer`, since this pipeline has no other use for the type yet.
+    fn execute_config(
+        &self,
+        transaction: &Transaction,
+        next_index: BlockIndex,
+        consensus: &dyn Consensus,
+    ) -> BeaconResult<TransactionResult> {
+        let no_op = TransactionResult {
+            transaction: transaction.clone(),
+            status: TransactionStatus::Success,
+            gas_used: 0,
+            return_value: None,
+            error: None,
+            state_changes: StateMap::new(),
+            events: Vec::new(),
+        };
+
+        let Some(change) = ValidatorSetChange::decode_from(transaction) else {
+            return Ok(no_op);
+        };
+
+        // effective_height must lie strictly in the future relative to the
+        // block this change would be accepted in - otherwise it would
+        // silently rewrite history that active_validators_at has already
+        // been asked about for heights up to next_index.
+        if change.effective_height <= next_index {
+            return Ok(TransactionResult {
+                transaction: transaction.clone(),
+                status: TransactionStatus::Failed,
+                gas_used: 0,
+                return_value: None,
+                error: Some(format!(
+                    "validator set change rejected: effective_height {} is not after the current height {}",
+                    change.effective_height, next_index
+                )),
+                state_changes: StateMap::new(),
+                events: Vec::new(),
+            });
+        }
+
+        if consensus.accept_validator_set_change(next_index, change)? {
+            return Ok(no_op);
+        }
+
+        Ok(TransactionResult {
+            transaction: transaction.clone(),
+            status: TransactionStatus::Failed,
+            gas_used: 0,
+            return_value: None,
+            error: Some("validator set change rejected: quorum not met".to_string()),
+            state_changes: StateMap::new(),
+            events: Vec::new(),
+        })
+    }
+
+    /// Handles a `Confidential` transaction: verifies its sealed commitment
+    /// and signature without ever needing the cleartext, then - only if this
+    /// pipeline holds a `confidential_key` with a wrapped-key entry in the
+    /// payload - unwraps the per-transaction key, decrypts the payload as a
+    /// chaincode invocation, and runs it exactly like an ordinary
+    /// `Invoke`/`Deploy` via `run_chaincode`. A node with no wrapped-key
+    /// entry (or no `confidential_key` configured at all) still orders the
+    /// transaction, but as an opaque no-op - it stored and ordered the
+    /// ciphertext without ever seeing the plaintext. Either way, the result
+    /// handed back always carries the original opaque `transaction`, never
+    /// the decrypted invocation, so the cleartext is never persisted by
+    /// `TransactionStorage`.
+    async fn execute_confidential(&self, transaction: &Transaction, creator: Vec<u8>) -> TransactionResult {
+        let opaque = |status: TransactionStatus, error: Option<String>| TransactionResult {
+            transaction: transaction.clone(),
+            status,
+            gas_used: 0,
+            return_value: None,
+            error,
+            state_changes: StateMap::new(),
+            events: Vec::new(),
+        };
+
+        let Some(confidential) = ConfidentialTransaction::decode_from(transaction) else {
+            return opaque(
+                TransactionStatus::Invalid,
+                Some("malformed confidential transaction payload".to_string()),
+            );
+        };
+
+        match confidential.verify_commitment() {
+            Ok(true) => {}
+            Ok(false) => {
+                return opaque(
+                    TransactionStatus::Invalid,
+                    Some("confidential transaction commitment signature is invalid".to_string()),
+                )
+            }
+            Err(e) => return opaque(TransactionStatus::Invalid, Some(e.to_string())),
+        }
+
+        let Some(secret) = &self.confidential_key else {
+            return opaque(TransactionStatus::Success, None);
+        };
+
+        let plaintext = match confidential.decrypt(secret) {
+            Ok(Some(plaintext)) => plaintext,
+            Ok(None) => return opaque(TransactionStatus::Success, None),
+            Err(e) => return opaque(TransactionStatus::Failed, Some(e.to_string())),
+        };
+
+        let input = match serde_json::from_slice(&plaintext) {
+            Ok(input) => input,
+            Err(e) => {
+                return opaque(
+                    TransactionStatus::Failed,
+                    Some(format!("decrypted confidential payload is not a valid chaincode invocation: {}", e)),
+                )
+            }
+        };
+
+        let mut invocation = transaction.clone();
+        invocation.input = input;
+        let mut result = self.run_chaincode(&invocation, creator).await;
+        result.transaction = transaction.clone();
+        result
+    }
+
+    async fn run_chaincode(&self, transaction: &Transaction, creator: Vec<u8>) -> TransactionResult {
+        match self.chaincode_executor.execute_chaincode(transaction, creator).await {
+            Ok(execution) => {
+                let mut state_changes = StateMap::new();
+                for change in &execution.state_changes {
+                    if change.operation == "PUT" {
+                        state_changes.insert(change.key.clone(), change.value.clone());
+                    }
+                }
+                let events = execution
+                    .events
+                    .into_iter()
+                    .map(|event| TransactionEvent {
+                        event_type: event.name,
+                        data: event.payload,
+                        topics: Vec::new(),
+                    })
+                    .collect();
+
+                TransactionResult {
+                    transaction: transaction.clone(),
+                    status: if execution.status == 0 {
+                        TransactionStatus::Success
+                    } else if execution.status == GAS_EXCEEDED_STATUS {
+                        TransactionStatus::OutOfGas
+                    } else {
+                        TransactionStatus::Failed
+                    },
+                    gas_used: execution.gas_used,
+                    return_value: Some(execution.payload),
+                    error: if execution.status == 0 { None } else { Some(execution.message) },
+                    state_changes,
+                    events,
+                }
+            }
+            Err(e) => TransactionResult {
+                transaction: transaction.clone(),
+                status: TransactionStatus::Failed,
+                gas_used: 0,
+                return_value: None,
+                error: Some(e.to_string()),
+                state_changes: StateMap::new(),
+                events: Vec::new(),
+            },
+        }
+    }
+
+    /// Assembles the accepted, executed transactions into a block via
+    /// `consensus` and persists the block and each transaction's result.
+    async fn commit(
+        &self,
+        executed: Vec<(Transaction, TransactionResult)>,
+        consensus: &dyn Consensus,
+    ) -> BeaconResult<Block> {
+        let (transactions, results): (Vec<Transaction>, Vec<TransactionResult>) =
+            executed.into_iter().unzip();
+
+        let tip = self.blockchain_storage.get_latest_block().await?;
+        let mut block = consensus.create_block(transactions.clone(), tip.as_ref()).await?;
+        block.transaction_results = results.clone();
+
+        let state_root = self.state_storage.state_root().await?;
+        block.header.metadata.insert("state_root".to_string(), state_root);
+        let state_smt_root = self.state_storage.state_smt_root().await?;
+        block.header.metadata.insert("state_smt_root".to_string(), state_smt_root);
+        block.hash = block.header.calculate_hash();
+
+        // Promote the journal `execute_chaincode` wrote under the
+        // prospective index to the block's now-known real hash, so
+        // `undo_block`/`reorganize_to` can find it by the key every other
+        // block-hash-keyed lookup uses.
+        self.state_storage
+            .finalize_block_journal(&block.header.index.to_string(), &block.hash)
+            .await?;
+
+        self.blockchain_storage.store_block(&block).await?;
+        for (index, (transaction, result)) in transactions.iter().zip(results.iter()).enumerate() {
+            self.transaction_storage
+                .store_transaction_with_result(transaction, result, block.header.index, index)
+                .await?;
+        }
+
+        let written_keys = results.iter().flat_map(|result| result.state_changes.keys().cloned());
+        self.state_storage
+            .record_provenance(written_keys, block.header.index, block.header.timestamp.clone())
+            .await?;
+
+        Ok(block)
+    }
+
+    /// Reorganize the canonical chain onto `new_head_hash`, rolling back each
+    /// retracted block's state writes (`StateStorage::undo_block`) and
+    /// replaying each enacted block's already-computed state changes
+    /// (`StateStorage::apply_state_changes_for_block`), so the state store
+    /// ends up exactly where it would if the new path had been canonical all
+    /// along. Called by `import_foreign_block` once it decides a competing
+    /// chain outweighs the current one; not meant to be called directly by a
+    /// peer-sync subsystem, since it assumes `new_head_hash` and its whole
+    /// ancestry back to the common ancestor are already in `blockchain_storage`.
+    pub async fn reorganize_to(&self, new_head_hash: &str) -> BeaconResult<TreeRoute> {
+        let route = self.blockchain_storage.reorganize(new_head_hash).await?;
+
+        for hash in &route.retracted {
+            self.state_storage.undo_block(hash).await?;
+        }
+
+        for hash in &route.enacted {
+            let block = self.blockchain_storage.get_block_by_hash(hash).await?.ok_or_else(|| {
+                BeaconError::storage(format!("reorganize_to: missing enacted block {}", hash))
+            })?;
+
+            let mut changes = StateMap::new();
+            for result in &block.transaction_results {
+                changes.extend(result.state_changes.clone());
+            }
+            if !changes.is_empty() {
+                self.state_storage.apply_state_changes_for_block(hash, &changes).await?;
+            }
+        }
+
+        Ok(route)
+    }
+
+    /// The hook a peer-sync subsystem calls on observing a block from a
+    /// competing chain - the entry point for recovering from validator
+    /// disagreement under PoA. Stores `block` via `BlockchainStorage::store_block`
+    /// (which already tolerates side-branch blocks that don't extend the
+    /// current canonical head), then, only if `block` is now strictly longer
+    /// than the local canonical tip, reorganizes onto it via `reorganize_to`.
+    /// A side branch that hasn't yet overtaken the local tip is left stored
+    /// but not canonical, awaiting either a longer block on top of it or
+    /// being pruned by whatever retention policy the caller applies.
+    ///
+    /// Returns `Ok(None)` if `block` was stored but didn't trigger a reorg.
+    /// Returns an error (propagated from `tree_route`) if `block`'s ancestry
+    /// back to a common ancestor with the local chain isn't fully present -
+    /// the caller is expected to have backfilled it (e.g. via block-sync)
+    /// before calling this.
+    pub async fn import_foreign_block(&self, block: Block) -> BeaconResult<Option<TreeRoute>> {
+        let current_tip_index = self.blockchain_storage.get_latest_block_index().await?;
+        let block_hash = block.hash.clone();
+        let block_index = block.header.index;
+
+        self.blockchain_storage.store_block(&block).await?;
+
+        let is_longer = match current_tip_index {
+            Some(tip_index) => block_index > tip_index,
+            None => true,
+        };
+        if !is_longer {
+            return Ok(None);
+        }
+
+        Ok(Some(self.reorganize_to(&block_hash).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_core::crypto::KeyPair;
+    use beacon_core::{Address, TransactionInput};
+    use beacon_storage::InMemoryBackend;
+    use std::collections::HashMap;
+
+    fn test_pipeline() -> (BlockImportPipeline, Arc<BlockchainStorage>) {
+        let backend: Arc<dyn beacon_storage::StorageBackend> = Arc::new(InMemoryBackend::new());
+        let state_storage = Arc::new(StateStorage::new(backend.clone()));
+        let transaction_storage = Arc::new(TransactionStorage::new(backend.clone()));
+        let blockchain_storage = Arc::new(BlockchainStorage::new(backend));
+        let chaincode_config = beacon_chaincode::ChaincodeExecutorConfig::default();
+        let shim_service = Arc::new(beacon_chaincode::ChaincodeShimService::new(
+            state_storage.clone(),
+            chaincode_config.trace_execution,
+        ));
+        let chaincode_executor = Arc::new(ChaincodeExecutor::new(chaincode_config, shim_service));
+        let pipeline = BlockImportPipeline::new(
+            state_storage,
+            transaction_storage,
+            blockchain_storage.clone(),
+            chaincode_executor,
+        );
+        (pipeline, blockchain_storage)
+    }
+
+    fn signed_transfer(signer: &KeyPair, nonce: u64) -> Transaction {
+        let input = TransactionInput {
+            chaincode_id: String::new(),
+            function: String::new(),
+            args: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        let mut tx = Transaction::new(
+            TransactionType::Transfer,
+            Address::new(&signer.verifying_key_hex()),
+            Some(Address::new("bob")),
+            input,
+            nonce,
+        );
+        tx.sign(&signer.signing_key).unwrap();
+        tx
+    }
+
+    #[tokio::test]
+    async fn test_import_block_commits_a_block_with_the_given_transactions() {
+        let (pipeline, blockchain_storage) = test_pipeline();
+        blockchain_storage.initialize("test-network").await.unwrap();
+
+        let validator = KeyPair::generate();
+        let consensus = crate::ProofOfAuthority::new(
+            vec![validator.verifying_key_hex()],
+            validator.verifying_key_hex(),
+            Some(validator.signing_key.clone()),
+        );
+        let context = ConsensusContext::new();
+
+        let tx = signed_transfer(&validator, 0);
+        let imported = pipeline
+            .import_block(vec![tx.clone()], &consensus, &context)
+            .await
+            .unwrap()
+            .expect("a valid transaction should produce a block");
+
+        assert_eq!(imported.block.header.index, 1);
+        assert_eq!(imported.block.transactions.len(), 1);
+        assert_eq!(imported.block.transactions[0].id, tx.id);
+        assert_eq!(blockchain_storage.get_latest_block_index().await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_execute_config_rejects_effective_height_not_after_next_index() {
+        let (pipeline, _blockchain_storage) = test_pipeline();
+
+        let change = ValidatorSetChange {
+            add: vec!["newcomer".to_string()],
+            remove: Vec::new(),
+            effective_height: 10,
+            quorum_cert: crate::QuorumCert::new(String::new()),
+        };
+        let input = change.to_transaction_input(String::new());
+        let transaction = Transaction::new(TransactionType::Config, Address::new("alice"), None, input, 0);
+
+        let validator = KeyPair::generate();
+        let consensus = crate::ProofOfAuthority::new(
+            vec![validator.verifying_key_hex()],
+            validator.verifying_key_hex(),
+            Some(validator.signing_key.clone()),
+        );
+
+        // next_index (10) is not strictly before effective_height (10), so
+        // this must be rejected even though no quorum was ever attempted.
+        let result = pipeline.execute_config(&transaction, 10, &consensus).unwrap();
+        assert_eq!(result.status, TransactionStatus::Failed);
+        assert!(result.error.unwrap().contains("effective_height"));
+    }
+
+    #[tokio::test]
+    async fn test_import_foreign_block_reorganizes_onto_a_longer_competing_chain() {
+        let (pipeline, blockchain_storage) = test_pipeline();
+        blockchain_storage.initialize("test-network").await.unwrap();
+        let genesis = blockchain_storage.get_latest_block().await.unwrap().unwrap();
+
+        // Our local canonical chain: genesis -> block_a (height 1).
+        let block_a = Block::new(1, genesis.hash.clone(), Vec::new(), "validator-a".to_string());
+        blockchain_storage.store_block(&block_a).await.unwrap();
+        assert_eq!(blockchain_storage.get_latest_block_index().await.unwrap(), Some(1));
+
+        // A competing branch off the same genesis: block_b (height 1, stored
+        // as a side branch) -> block_c (height 2), which is longer than our
+        // local tip once it arrives.
+        let block_b = Block::new(1, genesis.hash.clone(), Vec::new(), "validator-b".to_string());
+        blockchain_storage.store_block(&block_b).await.unwrap();
+        let block_c = Block::new(2, block_b.hash.clone(), Vec::new(), "validator-b".to_string());
+
+        let route = pipeline
+            .import_foreign_block(block_c.clone())
+            .await
+            .unwrap()
+            .expect("a longer competing chain should trigger a reorg");
+        assert_eq!(route.ancestor, genesis.hash);
+        assert_eq!(route.retracted, vec![block_a.hash.clone()]);
+        assert_eq!(route.enacted, vec![block_b.hash.clone(), block_c.hash.clone()]);
+
+        assert_eq!(blockchain_storage.get_latest_block_index().await.unwrap(), Some(2));
+        assert_eq!(
+            blockchain_storage.get_block_by_index(1).await.unwrap().unwrap().hash,
+            block_b.hash
+        );
+    }
+}
@@ -1,65 +1,348 @@
-use beacon_core::{BeaconResult, Block, Transaction};
-use crate::{Consensus, ConsensusState};
+use std::sync::RwLock;
 
-/// Proof of Authority consensus implementation
+use ed25519_dalek::SigningKey;
+use beacon_core::{verifying_key_from_hex, BeaconError, BeaconResult, Block, BlockIndex, ForkId, ForkSchedule, Transaction};
+use crate::{metrics, quorum_threshold, verify_quorum, Checkpoint, Consensus, ConsensusState, ValidatorSetChange};
+
+/// Proof of Authority consensus implementation. Validators take turns
+/// proposing blocks in a round-robin over the set active at each height:
+/// `active_validators_at(height)[height % len]` is the validator due to
+/// propose the block at `height`.
 pub struct ProofOfAuthority {
     validators: Vec<String>,
-    current_validator_index: usize,
     is_validator: bool,
     node_id: String,
+    /// This node's signing key, used to sign blocks it proposes. `None` for
+    /// non-validator nodes, which only ever validate.
+    signing_key: Option<SigningKey>,
+    /// Scheduled protocol upgrades - see `ForkSchedule`. Empty means every
+    /// height is judged by the same rules.
+    fork_schedule: ForkSchedule,
+    /// Weak-subjectivity checkpoint this node started from, if it used
+    /// `SyncMode::Checkpoint` instead of replaying from genesis. `is_synced`
+    /// reports caught-up once the chain has advanced past `checkpoint.height`.
+    checkpoint: Option<Checkpoint>,
+    /// Governance-accepted changes to the validator set, kept sorted by
+    /// `effective_height` (ties broken by `content_hash`) regardless of the
+    /// order they were accepted in - see `active_validators_at` and
+    /// `accept_validator_set_change`. This mirrors `ForkSchedule`'s
+    /// strictly-ordered-by-height invariant, so folding is unambiguous and
+    /// gives every node the same result even if they observed quorum for
+    /// these changes in a different order. `Arc<dyn Consensus>` only hands
+    /// out `&self`, so this needs interior mutability to grow at runtime.
+    validator_set_changes: RwLock<Vec<ValidatorSetChange>>,
 }
 
 impl ProofOfAuthority {
-    pub fn new(validators: Vec<String>, node_id: String) -> Self {
+    pub fn new(validators: Vec<String>, node_id: String, signing_key: Option<SigningKey>) -> Self {
+        Self::with_fork_schedule(validators, node_id, signing_key, ForkSchedule::default())
+    }
+
+    pub fn with_fork_schedule(
+        validators: Vec<String>,
+        node_id: String,
+        signing_key: Option<SigningKey>,
+        fork_schedule: ForkSchedule,
+    ) -> Self {
         let is_validator = validators.contains(&node_id);
-        
+
         Self {
             validators,
-            current_validator_index: 0,
             is_validator,
             node_id,
+            signing_key,
+            fork_schedule,
+            checkpoint: None,
+            validator_set_changes: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Adopt `checkpoint` as this node's trusted sync root. Callers must
+    /// have already confirmed it via `verify_checkpoint` - this is a plain
+    /// setter, not a re-verification.
+    pub fn set_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.checkpoint = Some(checkpoint);
+    }
+
+    /// The validator (by hex-encoded public key) due to propose the block at
+    /// `height`, drawn from the set active at that height (see
+    /// `active_validators_at`) rather than the genesis-configured set, so
+    /// proposer authorization stays correct across validator set changes.
+    fn proposer_for_height(&self, height: u64) -> Option<String> {
+        let active = self.active_validators_at(height);
+        if active.is_empty() {
+            return None;
         }
+        let index = (height as usize) % active.len();
+        active.into_iter().nth(index)
     }
 }
 
 #[async_trait::async_trait]
 impl Consensus for ProofOfAuthority {
-    async fn validate_block(&self, block: &Block) -> BeaconResult<bool> {
-        // Basic PoA validation
-        // 1. Check if block was created by a valid validator
-        // 2. Check if it's the validator's turn
-        // 3. Validate signature
-        
-        // For now, return true (implement full validation later)
-        Ok(true)
+    async fn validate_block(&self, block: &Block, tip: Option<&Block>) -> BeaconResult<bool> {
+        if block.is_genesis() {
+            return Ok(true);
+        }
+
+        if block.validate().is_err() {
+            metrics::record_block_rejected();
+            return Ok(false);
+        }
+
+        let required_version = self.fork_schedule.required_version_at(block.header.index);
+        if block.header.version < required_version {
+            return Err(BeaconError::feature_not_activated(format!(
+                "block {} declares header version {} but height {} requires at least {}",
+                block.header.index, block.header.version, block.header.index, required_version
+            )));
+        }
+
+        let expected_proposer = match self.proposer_for_height(block.header.index) {
+            Some(proposer) => proposer,
+            None => {
+                metrics::record_block_rejected();
+                return Ok(false);
+            }
+        };
+        if block.header.validator != expected_proposer {
+            metrics::record_block_rejected();
+            return Ok(false);
+        }
+
+        let signature_valid = verifying_key_from_hex(&block.header.validator)
+            .map(|key| block.verify_signature(&key))
+            .unwrap_or(false);
+        if !signature_valid {
+            metrics::record_block_rejected();
+            return Ok(false);
+        }
+
+        let chains_from_tip = match tip {
+            Some(tip) => block.header.index == tip.header.index + 1 && block.header.previous_hash == tip.hash,
+            None => block.header.index == 0,
+        };
+
+        if !chains_from_tip {
+            metrics::record_block_rejected();
+        }
+
+        Ok(chains_from_tip)
     }
-    
-    async fn create_block(&self, transactions: Vec<Transaction>) -> BeaconResult<Block> {
+
+    async fn create_block(&self, transactions: Vec<Transaction>, tip: Option<&Block>) -> BeaconResult<Block> {
         if !self.can_create_blocks() {
-            return Err(beacon_core::BeaconError::consensus("Node is not a validator"));
-        }
-        
-        // Create a new block with the given transactions
-        let block = Block::new(
-            0, // This should be actual next block index
-            "0".repeat(64), // This should be actual previous block hash
-            transactions,
-            self.node_id.clone(),
-        );
-        
+            return Err(BeaconError::consensus("Node is not a validator"));
+        }
+
+        let next_index = tip.map(|block| block.header.index + 1).unwrap_or(0);
+        let expected_proposer = self
+            .proposer_for_height(next_index)
+            .ok_or_else(|| BeaconError::consensus("no validators configured"))?;
+        if expected_proposer != self.node_id {
+            return Err(BeaconError::consensus(format!(
+                "not this validator's turn: height {} belongs to {}",
+                next_index, expected_proposer
+            )));
+        }
+
+        let signing_key = self
+            .signing_key
+            .as_ref()
+            .ok_or_else(|| BeaconError::consensus("validator has no signing key loaded"))?;
+
+        let previous_hash = tip.map(|block| block.hash.clone()).unwrap_or_else(|| "0".repeat(64));
+        let mut block = Block::new(next_index, previous_hash, transactions, self.node_id.clone());
+        block.header.version = block.header.version.max(self.fork_schedule.required_version_at(next_index));
+        block.hash = block.header.calculate_hash();
+        block.sign(signing_key)?;
+
         Ok(block)
     }
-    
+
     fn can_create_blocks(&self) -> bool {
         self.is_validator
     }
-    
-    fn get_state(&self) -> ConsensusState {
+
+    fn fork_at(&self, height: BlockIndex) -> ForkId {
+        self.fork_schedule.fork_at(height)
+    }
+
+    /// `next_height` is the height of the block that is next due to be
+    /// proposed (the chain tip's index + 1, or 0 before genesis).
+    fn get_state(&self, next_height: u64) -> ConsensusState {
+        let current_validator = self.proposer_for_height(next_height);
+        let next_validator = self.proposer_for_height(next_height + 1);
+        metrics::set_validators(current_validator.as_deref(), next_validator.as_deref());
+
         ConsensusState {
-            current_validator: self.validators.get(self.current_validator_index).cloned(),
-            next_validator: self.validators.get((self.current_validator_index + 1) % self.validators.len()).cloned(),
-            validator_count: self.validators.len(),
-            is_synced: true, // Simplified for now
+            current_validator: current_validator.clone(),
+            next_validator: next_validator.clone(),
+            // The set active at `next_height`, not the genesis-configured
+            // one - a validator set change folds in here as soon as it's
+            // accepted.
+            validator_count: self.active_validators_at(next_height).len(),
+            // A full-sync node has no external target to catch up to as far
+            // as this engine is concerned. A checkpoint-synced node is
+            // caught up once it has produced or imported at least one block
+            // past the checkpoint it started from.
+            is_synced: self
+                .checkpoint
+                .as_ref()
+                .map(|checkpoint| next_height > checkpoint.height + 1)
+                .unwrap_or(true),
+            active_fork: self.fork_schedule.fork_at(next_height),
+            next_fork: self.fork_schedule.next_after(next_height).cloned(),
         }
     }
+
+    async fn verify_checkpoint(&self, cp: &Checkpoint) -> BeaconResult<bool> {
+        if cp.quorum_cert.block_hash != cp.block_hash {
+            return Ok(false);
+        }
+        // The set active at the checkpoint's own height, so a checkpoint
+        // taken after a validator set change is still verified against the
+        // membership that was actually live when it signed.
+        let active = self.active_validators_at(cp.height);
+        let threshold = quorum_threshold(active.len());
+        Ok(verify_quorum(&cp.quorum_cert, &active, threshold))
+    }
+
+    fn active_validators_at(&self, height: BlockIndex) -> Vec<String> {
+        let mut active = self.validators.clone();
+        for change in self.validator_set_changes.read().unwrap().iter() {
+            if change.effective_height > height {
+                continue;
+            }
+            active.retain(|validator| !change.remove.contains(validator));
+            for validator in &change.add {
+                if !active.contains(validator) {
+                    active.push(validator.clone());
+                }
+            }
+        }
+        active
+    }
+
+    fn accept_validator_set_change(&self, height: BlockIndex, change: ValidatorSetChange) -> BeaconResult<bool> {
+        if change.quorum_cert.block_hash != change.content_hash() {
+            return Ok(false);
+        }
+
+        let active = self.active_validators_at(height);
+        let threshold = quorum_threshold(active.len());
+        if !verify_quorum(&change.quorum_cert, &active, threshold) {
+            return Ok(false);
+        }
+
+        let content_hash = change.content_hash();
+        let mut changes = self.validator_set_changes.write().unwrap();
+        // Insert in effective_height order (ties broken by content_hash) so
+        // the Vec stays sorted no matter what order changes clear quorum in
+        // - active_validators_at's fold depends on that.
+        let position = changes
+            .iter()
+            .position(|existing| {
+                (existing.effective_height, existing.content_hash()) > (change.effective_height, content_hash.clone())
+            })
+            .unwrap_or(changes.len());
+        changes.insert(position, change);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_core::crypto::KeyPair;
+
+    fn quorum_certified_change(
+        validators: &[KeyPair],
+        add: Vec<String>,
+        remove: Vec<String>,
+        effective_height: BlockIndex,
+    ) -> ValidatorSetChange {
+        let mut change = ValidatorSetChange {
+            add,
+            remove,
+            effective_height,
+            quorum_cert: crate::QuorumCert::new(String::new()),
+        };
+        let content_hash = change.content_hash();
+        change.quorum_cert = crate::QuorumCert::new(content_hash.clone());
+        for validator in validators {
+            change
+                .quorum_cert
+                .add_signature(validator.verifying_key_hex(), validator.sign(content_hash.as_bytes()));
+        }
+        change
+    }
+
+    #[test]
+    fn test_accept_validator_set_change_with_quorum_folds_into_active_set() {
+        let genesis: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate()).collect();
+        let genesis_ids: Vec<String> = genesis.iter().map(|kp| kp.verifying_key_hex()).collect();
+        let poa = ProofOfAuthority::new(genesis_ids.clone(), "node-0".to_string(), None);
+
+        let newcomer = KeyPair::generate();
+        let change = quorum_certified_change(&genesis, vec![newcomer.verifying_key_hex()], vec![], 10);
+
+        assert!(poa.accept_validator_set_change(5, change).unwrap());
+        assert!(!poa.active_validators_at(9).contains(&newcomer.verifying_key_hex()));
+        assert!(poa.active_validators_at(10).contains(&newcomer.verifying_key_hex()));
+        assert_eq!(poa.active_validators_at(10).len(), genesis_ids.len() + 1);
+    }
+
+    #[test]
+    fn test_accept_validator_set_change_without_quorum_is_rejected() {
+        let genesis: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate()).collect();
+        let genesis_ids: Vec<String> = genesis.iter().map(|kp| kp.verifying_key_hex()).collect();
+        let poa = ProofOfAuthority::new(genesis_ids.clone(), "node-0".to_string(), None);
+
+        let newcomer = KeyPair::generate();
+        // Only one of four genesis validators signs - below quorum_threshold(4) == 3.
+        let change = quorum_certified_change(&genesis[..1], vec![newcomer.verifying_key_hex()], vec![], 10);
+
+        assert!(!poa.accept_validator_set_change(5, change).unwrap());
+        assert_eq!(poa.active_validators_at(10), genesis_ids);
+    }
+
+    #[test]
+    fn test_active_validators_at_folds_by_effective_height_not_acceptance_order() {
+        let genesis: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate()).collect();
+        let genesis_ids: Vec<String> = genesis.iter().map(|kp| kp.verifying_key_hex()).collect();
+        let poa = ProofOfAuthority::new(genesis_ids.clone(), "node-0".to_string(), None);
+        let v = genesis_ids[0].clone();
+
+        // B (add V back, effective_height=80) is accepted *before* A (remove
+        // V, effective_height=40) - out of effective_height order. Folding
+        // by acceptance order would apply B (no-op, V already present) then
+        // A (remove), leaving V absent at 90; folding by effective_height
+        // applies A (remove) then B (add), leaving V present.
+        let add_change = quorum_certified_change(&genesis, vec![v.clone()], vec![], 80);
+        let remove_change = quorum_certified_change(&genesis, vec![], vec![v.clone()], 40);
+        assert!(poa.accept_validator_set_change(0, add_change).unwrap());
+        assert!(poa.accept_validator_set_change(0, remove_change).unwrap());
+
+        assert!(poa.active_validators_at(90).contains(&v));
+        assert!(!poa.active_validators_at(50).contains(&v));
+    }
+
+    #[test]
+    fn test_proposer_for_height_reflects_validator_set_changes_at_their_effective_height() {
+        let genesis: Vec<KeyPair> = (0..1).map(|_| KeyPair::generate()).collect();
+        let genesis_ids: Vec<String> = genesis.iter().map(|kp| kp.verifying_key_hex()).collect();
+        let poa = ProofOfAuthority::new(genesis_ids.clone(), "node-0".to_string(), None);
+
+        let newcomer = KeyPair::generate();
+        let change = quorum_certified_change(&genesis, vec![newcomer.verifying_key_hex()], vec![], 10);
+        assert!(poa.accept_validator_set_change(5, change).unwrap());
+
+        // Before effective_height, the lone genesis validator proposes every block.
+        assert_eq!(poa.proposer_for_height(9), Some(genesis_ids[0].clone()));
+        // From effective_height on, proposals round-robin over both validators.
+        let proposer_at_10 = poa.proposer_for_height(10).unwrap();
+        let proposer_at_11 = poa.proposer_for_height(11).unwrap();
+        assert_ne!(proposer_at_10, proposer_at_11);
+    }
 }
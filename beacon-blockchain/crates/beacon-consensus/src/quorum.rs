@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use beacon_core::{verify_signature, verifying_key_from_hex, BlockIndex, Hash};
+
+/// An aggregated multi-signature certificate over a block hash
+///
+/// A block is only authoritative once `signatures` carries a signature from
+/// at least a quorum threshold of distinct, authorized validators.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct QuorumCert {
+    pub block_hash: String,
+    pub signatures: Vec<(String, String)>,
+}
+
+impl QuorumCert {
+    /// Start an empty certificate for the given block hash
+    pub fn new(block_hash: String) -> Self {
+        Self {
+            block_hash,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Fold in a partial signature collected from a peer, ignoring repeats of
+    /// a signer that has already contributed
+    pub fn add_signature(&mut self, verifying_key_hex: String, signature_hex: String) {
+        if !self.signatures.iter().any(|(key, _)| *key == verifying_key_hex) {
+            self.signatures.push((verifying_key_hex, signature_hex));
+        }
+    }
+
+    /// Number of distinct signers collected so far
+    pub fn signer_count(&self) -> usize {
+        self.signatures.len()
+    }
+}
+
+/// Canonical Byzantine-tolerant threshold for `validator_count` validators:
+/// `2f+1` distinct signers, where `f = (validator_count - 1) / 3`
+pub fn quorum_threshold(validator_count: usize) -> usize {
+    if validator_count == 0 {
+        return 0;
+    }
+    let f = (validator_count - 1) / 3;
+    2 * f + 1
+}
+
+/// Verify that `cert` carries valid signatures over its block hash from at
+/// least `threshold` distinct members of `validator_set`
+pub fn verify_quorum(cert: &QuorumCert, validator_set: &[String], threshold: usize) -> bool {
+    let mut seen = HashSet::new();
+    let mut valid_signers = 0;
+
+    for (verifying_key_hex, signature_hex) in &cert.signatures {
+        if !validator_set.contains(verifying_key_hex) {
+            continue;
+        }
+        if !seen.insert(verifying_key_hex.clone()) {
+            continue;
+        }
+
+        let Ok(verifying_key) = verifying_key_from_hex(verifying_key_hex) else {
+            continue;
+        };
+
+        if verify_signature(&verifying_key, cert.block_hash.as_bytes(), signature_hex) {
+            valid_signers += 1;
+        }
+    }
+
+    valid_signers >= threshold
+}
+
+/// A weak-subjectivity checkpoint: a recent block a new node trusts as its
+/// root instead of replaying the whole chain from genesis, mirroring how
+/// light clients (Lighthouse/Helios) bootstrap from a checkpoint rather
+/// than from genesis. `quorum_cert` is the evidence that a quorum of the
+/// configured PoA validator set signed off on `block_hash` - see
+/// `Consensus::verify_checkpoint`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Checkpoint {
+    pub block_hash: Hash,
+    pub height: BlockIndex,
+    pub quorum_cert: QuorumCert,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_core::crypto::KeyPair;
+
+    #[test]
+    fn test_quorum_threshold() {
+        assert_eq!(quorum_threshold(0), 0);
+        assert_eq!(quorum_threshold(1), 1);
+        assert_eq!(quorum_threshold(4), 3);
+        assert_eq!(quorum_threshold(7), 5);
+    }
+
+    #[test]
+    fn test_verify_quorum_accepts_enough_distinct_signatures() {
+        let block_hash = "a".repeat(64);
+        let validators: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate()).collect();
+        let validator_set: Vec<String> = validators.iter().map(|kp| kp.verifying_key_hex()).collect();
+
+        let mut cert = QuorumCert::new(block_hash.clone());
+        for validator in validators.iter().take(3) {
+            cert.add_signature(validator.verifying_key_hex(), validator.sign(block_hash.as_bytes()));
+        }
+
+        assert!(verify_quorum(&cert, &validator_set, quorum_threshold(4)));
+    }
+
+    #[test]
+    fn test_verify_quorum_rejects_duplicate_and_unknown_signers() {
+        let block_hash = "b".repeat(64);
+        let validators: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate()).collect();
+        let validator_set: Vec<String> = validators.iter().map(|kp| kp.verifying_key_hex()).collect();
+        let outsider = KeyPair::generate();
+
+        let mut cert = QuorumCert::new(block_hash.clone());
+        // Same validator's signature added twice should only count once.
+        cert.add_signature(validators[0].verifying_key_hex(), validators[0].sign(block_hash.as_bytes()));
+        cert.add_signature(validators[0].verifying_key_hex(), validators[0].sign(block_hash.as_bytes()));
+        cert.signatures.push((validators[1].verifying_key_hex(), validators[1].sign(block_hash.as_bytes())));
+        // A signature from outside the validator set must not count.
+        cert.signatures.push((outsider.verifying_key_hex(), outsider.sign(block_hash.as_bytes())));
+
+        assert!(!verify_quorum(&cert, &validator_set, quorum_threshold(4)));
+        assert!(verify_quorum(&cert, &validator_set, 2));
+    }
+}
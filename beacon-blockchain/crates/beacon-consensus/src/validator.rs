@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use beacon_core::{verify_signature, verifying_key_from_hex, BeaconResult, Block, BlockIndex, Hash};
+
 // Placeholder for validator management
 pub struct ValidatorManager {
     validators: Vec<String>,
@@ -7,8 +11,214 @@ impl ValidatorManager {
     pub fn new(validators: Vec<String>) -> Self {
         Self { validators }
     }
-    
+
     pub fn get_validators(&self) -> &[String] {
         &self.validators
     }
 }
+
+/// Persisted backing store for `Slasher`'s seen-proposals index, keyed by
+/// `(validator_id, height)`. A trait so a RocksDB-backed implementation can
+/// be plugged in without this crate depending on `beacon-storage` directly -
+/// mirrors how `PeerStore`/`DiscoveryStore` are declared in
+/// `beacon-networking` and backed from `beacon-node`.
+#[async_trait::async_trait]
+pub trait SlasherStore: Send + Sync {
+    /// The block hash and signature `validator_id` already proposed at
+    /// `height`, if any.
+    async fn get(&self, validator_id: &str, height: BlockIndex) -> BeaconResult<Option<(Hash, String)>>;
+
+    /// Record that `validator_id` proposed `block_hash` (signed `signature`)
+    /// at `height`. Only called once per `(validator_id, height)` - a second
+    /// proposal at the same key is evidence, not a fresh record.
+    async fn put(&self, validator_id: &str, height: BlockIndex, block_hash: Hash, signature: String) -> BeaconResult<()>;
+
+    /// Drop every entry at a height strictly below `min_height`, bounding
+    /// storage growth - see `Slasher::check_for_slashing`.
+    async fn prune_below(&self, min_height: BlockIndex) -> BeaconResult<()>;
+}
+
+/// Portable proof that `validator` signed two different blocks at the same
+/// height: a double-proposal, the canonical PoA equivocation fault. Verified
+/// independently of the detecting node via `verify_slashing_evidence`, so it
+/// can be broadcast and checked by every peer before the validator is
+/// actually removed from the set.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SlashingEvidence {
+    pub validator: String,
+    pub height: BlockIndex,
+    pub block_a: Hash,
+    pub block_b: Hash,
+    pub sig_a: String,
+    pub sig_b: String,
+}
+
+/// Detects PoA proposer equivocation: a validator signing two different
+/// blocks at the same height. Ported from the detection concept in
+/// Lighthouse's slasher, adapted to BEACON's single flat validator set
+/// rather than attestation source/target voting.
+///
+/// Known limitation: `check_for_slashing` only ever sees blocks that pass
+/// through *this* node's own `BlockImportPipeline` - blocks it produces
+/// itself, or foreign blocks imported via `import_foreign_block`. A
+/// validator that equivocates by signing two different blocks on two
+/// different network partitions is only caught once both blocks reach the
+/// same node's pipeline; a node that only ever sees one side of the
+/// partition sees no equivocation at all. Closing this gap needs evidence
+/// (or the raw blocks) to actually be broadcast to peers once detected,
+/// which nothing in this crate does yet - see the call site in
+/// `beacon-node`'s `BeaconNode::run`.
+pub struct Slasher {
+    store: Arc<dyn SlasherStore>,
+    /// Entries at a height more than this many blocks behind the height most
+    /// recently checked are pruned, so the store doesn't grow unbounded.
+    prune_window: BlockIndex,
+}
+
+impl Slasher {
+    pub fn new(store: Arc<dyn SlasherStore>, prune_window: BlockIndex) -> Self {
+        Self { store, prune_window }
+    }
+
+    /// Look up `(block.header.validator, block.header.index)` in the store:
+    /// if an entry already exists with a *different* block hash, the
+    /// validator has equivocated and this returns the evidence. Otherwise
+    /// the block is recorded and `None` is returned. Also prunes entries
+    /// older than `prune_window` blocks behind this block's height.
+    pub async fn check_for_slashing(&self, block: &Block) -> BeaconResult<Option<SlashingEvidence>> {
+        if block.is_genesis() {
+            return Ok(None);
+        }
+
+        let validator = block.header.validator.as_str();
+        let height = block.header.index;
+        let signature = block.header.metadata.get("signature").cloned().unwrap_or_default();
+
+        let evidence = match self.store.get(validator, height).await? {
+            Some((existing_hash, existing_signature)) if existing_hash != block.hash => {
+                Some(SlashingEvidence {
+                    validator: validator.to_string(),
+                    height,
+                    block_a: existing_hash,
+                    block_b: block.hash.clone(),
+                    sig_a: existing_signature,
+                    sig_b: signature,
+                })
+            }
+            Some(_) => None,
+            None => {
+                self.store.put(validator, height, block.hash.clone(), signature).await?;
+                None
+            }
+        };
+
+        if let Some(min_height) = height.checked_sub(self.prune_window) {
+            self.store.prune_below(min_height).await?;
+        }
+
+        Ok(evidence)
+    }
+}
+
+/// Re-verify both signatures carried by `evidence` against the accused
+/// validator's registered public key (validator IDs are hex-encoded ed25519
+/// keys throughout this crate), so a fabricated report - one with a forged
+/// or mismatched signature - is rejected before the validator set acts on it.
+pub fn verify_slashing_evidence(evidence: &SlashingEvidence) -> bool {
+    let Ok(public_key) = verifying_key_from_hex(&evidence.validator) else {
+        return false;
+    };
+
+    evidence.block_a != evidence.block_b
+        && verify_signature(&public_key, evidence.block_a.as_bytes(), &evidence.sig_a)
+        && verify_signature(&public_key, evidence.block_b.as_bytes(), &evidence.sig_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_core::crypto::KeyPair;
+    use std::collections::HashMap;
+    use tokio::sync::RwLock;
+
+    #[derive(Default)]
+    struct InMemorySlasherStore {
+        entries: RwLock<HashMap<(String, BlockIndex), (Hash, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SlasherStore for InMemorySlasherStore {
+        async fn get(&self, validator_id: &str, height: BlockIndex) -> BeaconResult<Option<(Hash, String)>> {
+            Ok(self.entries.read().await.get(&(validator_id.to_string(), height)).cloned())
+        }
+
+        async fn put(&self, validator_id: &str, height: BlockIndex, block_hash: Hash, signature: String) -> BeaconResult<()> {
+            self.entries.write().await.insert((validator_id.to_string(), height), (block_hash, signature));
+            Ok(())
+        }
+
+        async fn prune_below(&self, min_height: BlockIndex) -> BeaconResult<()> {
+            self.entries.write().await.retain(|(_, height), _| *height >= min_height);
+            Ok(())
+        }
+    }
+
+    fn signed_block(validator: &KeyPair, height: BlockIndex, previous_hash: &str) -> Block {
+        let mut block = Block::new(height, previous_hash.to_string(), Vec::new(), validator.verifying_key_hex());
+        block.hash = block.header.calculate_hash();
+        block.sign(&validator.signing_key).unwrap();
+        block
+    }
+
+    #[tokio::test]
+    async fn test_first_proposal_at_a_height_is_not_slashable() {
+        let validator = KeyPair::generate();
+        let slasher = Slasher::new(Arc::new(InMemorySlasherStore::default()), 1000);
+
+        let block = signed_block(&validator, 5, &"0".repeat(64));
+        assert!(slasher.check_for_slashing(&block).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_double_proposal_at_same_height_is_detected() {
+        let validator = KeyPair::generate();
+        let slasher = Slasher::new(Arc::new(InMemorySlasherStore::default()), 1000);
+
+        let block_a = signed_block(&validator, 5, &"0".repeat(64));
+        let block_b = signed_block(&validator, 5, &"1".repeat(64));
+        assert!(slasher.check_for_slashing(&block_a).await.unwrap().is_none());
+
+        let evidence = slasher.check_for_slashing(&block_b).await.unwrap().expect("equivocation should be detected");
+        assert_eq!(evidence.validator, validator.verifying_key_hex());
+        assert_eq!(evidence.height, 5);
+        assert!(verify_slashing_evidence(&evidence));
+    }
+
+    #[tokio::test]
+    async fn test_same_block_proposed_twice_is_not_slashable() {
+        let validator = KeyPair::generate();
+        let slasher = Slasher::new(Arc::new(InMemorySlasherStore::default()), 1000);
+
+        let block = signed_block(&validator, 5, &"0".repeat(64));
+        assert!(slasher.check_for_slashing(&block).await.unwrap().is_none());
+        assert!(slasher.check_for_slashing(&block).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_verify_slashing_evidence_rejects_forged_signature() {
+        let validator = KeyPair::generate();
+        let outsider = KeyPair::generate();
+
+        let evidence = SlashingEvidence {
+            validator: validator.verifying_key_hex(),
+            height: 5,
+            block_a: "a".repeat(64),
+            block_b: "b".repeat(64),
+            sig_a: validator.sign("a".repeat(64).as_bytes()),
+            // Forged: signed by someone other than the accused validator.
+            sig_b: outsider.sign("b".repeat(64).as_bytes()),
+        };
+
+        assert!(!verify_slashing_evidence(&evidence));
+    }
+}
@@ -180,6 +180,18 @@ impl Block {
         Ok(())
     }
     
+    /// Build an inclusion proof for the transaction at `tx_index`, so a
+    /// client can trust that one transaction is committed without shipping
+    /// the whole block. Transaction hashes are already SHA-256 hex strings,
+    /// so the tree is built from the pre-hashed leaves rather than re-hashing
+    /// raw bytes - this is the same level construction (including odd-level
+    /// duplication) as `BlockHeader::calculate_merkle_root`, so the proof
+    /// verifies against `self.header.merkle_root`.
+    pub fn merkle_proof(&self, tx_index: usize) -> Option<Vec<crate::MerkleProofStep>> {
+        let leaves: Vec<String> = self.transactions.iter().map(|tx| tx.hash.clone()).collect();
+        crate::MerkleTree::from_leaf_hashes(leaves).generate_proof(tx_index)
+    }
+
     /// Get block size in bytes
     pub fn size(&self) -> usize {
         bincode::serialize(self).map(|data| data.len()).unwrap_or(0)
@@ -229,6 +241,81 @@ impl Block {
     }
 }
 
+/// Verify a merkle inclusion proof produced by `Block::merkle_proof` against
+/// a transaction hash and the block's merkle root.
+pub fn verify_merkle_proof(tx_hash: &Hash, proof: &[crate::MerkleProofStep], merkle_root: &Hash) -> bool {
+    crate::MerkleTree::verify_proof_from_leaf_hash(merkle_root, tx_hash, proof)
+}
+
+/// A `Block` with its header hash and per-transaction hash vector
+/// precomputed once at construction, so a block carried across multiple
+/// pipeline stages (sync, validation, storage) isn't re-hashed at each one.
+/// `verify` performs the same checks as `Block::validate` but reuses the
+/// cached values instead of recomputing them.
+pub struct IndexedBlock {
+    block: Block,
+    header_hash: Hash,
+    transaction_hashes: Vec<Hash>,
+    merkle_root: Hash,
+}
+
+impl IndexedBlock {
+    /// Index `block`, computing its header hash and transaction hash vector once.
+    pub fn new(block: Block) -> Self {
+        let header_hash = block.header.calculate_hash();
+        let transaction_hashes: Vec<Hash> = block.transactions.iter().map(|tx| tx.hash.clone()).collect();
+        let merkle_root = crate::MerkleTree::from_leaf_hashes(transaction_hashes.clone()).root();
+
+        Self { block, header_hash, transaction_hashes, merkle_root }
+    }
+
+    /// The wrapped block
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Unwrap back into the plain `Block`
+    pub fn into_block(self) -> Block {
+        self.block
+    }
+
+    /// The block header's hash, computed once at construction
+    pub fn header_hash(&self) -> &Hash {
+        &self.header_hash
+    }
+
+    /// Each transaction's hash, in block order, computed once at construction
+    pub fn transaction_hashes(&self) -> &[Hash] {
+        &self.transaction_hashes
+    }
+
+    /// Validate the block using the cached header hash and merkle root
+    /// instead of recomputing them - see `Block::validate`.
+    pub fn verify(&self) -> Result<(), crate::BeaconError> {
+        if self.block.hash != self.header_hash {
+            return Err(crate::BeaconError::InvalidBlock("Block hash mismatch".to_string()));
+        }
+
+        if self.block.header.merkle_root != self.merkle_root {
+            return Err(crate::BeaconError::InvalidBlock("Merkle root mismatch".to_string()));
+        }
+
+        for transaction in &self.block.transactions {
+            transaction.validate()?;
+        }
+
+        if !self.block.transaction_results.is_empty()
+            && self.block.transaction_results.len() != self.block.transactions.len()
+        {
+            return Err(crate::BeaconError::InvalidBlock(
+                "Transaction results count mismatch".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Block validation error types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BlockValidationError {
@@ -244,6 +331,9 @@ pub enum BlockValidationError {
     InvalidTimestamp,
     /// Block size exceeds limit
     BlockTooLarge(usize, usize), // actual, limit
+    /// A transaction's `relative_lock` has not yet matured - its `lock_key`
+    /// was written too recently, in blocks or in seconds
+    ImmatureTransaction(String),
 }
 
 impl std::fmt::Display for BlockValidationError {
@@ -259,6 +349,9 @@ impl std::fmt::Display for BlockValidationError {
             BlockValidationError::BlockTooLarge(actual, limit) => {
                 write!(f, "Block too large: {} bytes (limit: {} bytes)", actual, limit)
             }
+            BlockValidationError::ImmatureTransaction(reason) => {
+                write!(f, "Immature transaction: {}", reason)
+            }
         }
     }
 }
@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use aes_gcm::aead::{Aead, KeyInit};
+use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, StaticSecret};
+use ed25519_dalek::{Signer, SigningKey};
+use crate::crypto::{hash_message, verify_signature, verifying_key_from_hex};
+use crate::transaction::{Transaction, TransactionInput, TransactionType};
+use crate::{BeaconError, BeaconResult};
+
+/// Function name a `ConfidentialTransaction` is carried under inside a
+/// `TransactionType::Confidential` transaction's `TransactionInput` - see
+/// `ConfidentialTransaction::to_transaction_input`/`decode_from`. Mirrors
+/// `governance::VALIDATOR_SET_CHANGE_FUNCTION`'s role for `Config`
+/// transactions.
+const CONFIDENTIAL_PAYLOAD_FUNCTION: &str = "confidential_payload";
+
+/// A per-transaction symmetric key, wrapped for one authorized participant
+///
+/// The wrap key is an ECDH shared secret between a fresh ephemeral X25519 key
+/// and the recipient's X25519 public key, so only that recipient can recover
+/// the per-transaction key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub recipient_public_key: String,
+    pub ephemeral_public_key: String,
+    pub nonce: String,
+    pub wrapped_key: String,
+}
+
+/// A transaction whose payload never appears on-chain in the clear
+///
+/// Only `commitment` (a SHA-256 of the cleartext) and the AEAD `ciphertext`
+/// are ordered into blocks; consensus validates `signer`/`signature` over the
+/// commitment without ever touching the plaintext. Nodes holding a wrapped
+/// key for their X25519 identity can recover the payload with `decrypt`;
+/// everyone else stores and orders the opaque blob.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidentialTransaction {
+    pub signer: String,
+    pub signature: String,
+    pub commitment: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub wrapped_keys: Vec<WrappedKey>,
+}
+
+impl ConfidentialTransaction {
+    /// Seal `payload` for `recipients`, signing the commitment with `signing_key`
+    pub fn seal(
+        payload: &[u8],
+        recipients: &[X25519PublicKey],
+        signing_key: &SigningKey,
+    ) -> BeaconResult<Self> {
+        let commitment = hash_message(payload);
+
+        let mut symmetric_key = [0u8; 32];
+        OsRng.fill_bytes(&mut symmetric_key);
+
+        let mut payload_nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut payload_nonce);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&symmetric_key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&payload_nonce), payload)
+            .map_err(|e| BeaconError::crypto(format!("confidential payload encryption failed: {}", e)))?;
+
+        let wrapped_keys = recipients
+            .iter()
+            .map(|recipient| wrap_key_for_recipient(&symmetric_key, recipient))
+            .collect::<BeaconResult<Vec<_>>>()?;
+
+        let signature = hex::encode(signing_key.sign(commitment.as_bytes()).to_bytes());
+
+        Ok(Self {
+            signer: hex::encode(signing_key.verifying_key().to_bytes()),
+            signature,
+            commitment,
+            nonce: hex::encode(payload_nonce),
+            ciphertext: hex::encode(ciphertext),
+            wrapped_keys,
+        })
+    }
+
+    /// Verify the signer's signature over the commitment — the public
+    /// consensus path only ever needs this, never the cleartext
+    pub fn verify_commitment(&self) -> BeaconResult<bool> {
+        let verifying_key = verifying_key_from_hex(&self.signer)?;
+        Ok(verify_signature(&verifying_key, self.commitment.as_bytes(), &self.signature))
+    }
+
+    /// Unwrap the per-transaction key for `secret`, if authorized, and
+    /// decrypt the payload. Returns `Ok(None)` when `secret` has no wrapped
+    /// entry in this transaction.
+    pub fn decrypt(&self, secret: &StaticSecret) -> BeaconResult<Option<Vec<u8>>> {
+        let recipient_public_key = hex::encode(X25519PublicKey::from(secret).as_bytes());
+
+        let Some(wrapped) = self
+            .wrapped_keys
+            .iter()
+            .find(|w| w.recipient_public_key == recipient_public_key)
+        else {
+            return Ok(None);
+        };
+
+        let symmetric_key = unwrap_key(wrapped, secret)?;
+
+        let nonce_bytes = hex::decode(&self.nonce)
+            .map_err(|e| BeaconError::crypto(format!("invalid confidential tx nonce: {}", e)))?;
+        let ciphertext = hex::decode(&self.ciphertext)
+            .map_err(|e| BeaconError::crypto(format!("invalid confidential tx ciphertext: {}", e)))?;
+        if nonce_bytes.len() != 12 {
+            return Err(BeaconError::crypto("invalid confidential tx nonce: expected 12 bytes"));
+        }
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&symmetric_key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+            .map_err(|_| BeaconError::crypto("failed to decrypt confidential transaction payload"))?;
+
+        if hash_message(&plaintext) != self.commitment {
+            return Err(BeaconError::crypto("decrypted payload does not match commitment"));
+        }
+
+        Ok(Some(plaintext))
+    }
+
+    /// Carry this sealed transaction as a `TransactionType::Confidential`
+    /// transaction's input, so it can be submitted through
+    /// `BeaconNode::submit_transaction`, stored by `TransactionStorage` and
+    /// ordered into blocks like any other transaction - see `decode_from`.
+    /// The outer `TransactionInput` carries only this sealed transaction (as
+    /// JSON in `args[0]`, under `CONFIDENTIAL_PAYLOAD_FUNCTION`, with an empty
+    /// `chaincode_id`): the real `chaincode_id`/`function`/`args` are inside
+    /// `ciphertext` and only recovered once an authorized node calls `decrypt`.
+    pub fn to_transaction_input(&self) -> TransactionInput {
+        TransactionInput {
+            chaincode_id: String::new(),
+            function: CONFIDENTIAL_PAYLOAD_FUNCTION.to_string(),
+            args: vec![serde_json::to_string(self).unwrap_or_default()],
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Recover a `ConfidentialTransaction` from a `Confidential` transaction,
+    /// if it carries one - `None` for any other transaction type or a
+    /// malformed payload.
+    pub fn decode_from(transaction: &Transaction) -> Option<Self> {
+        if transaction.tx_type != TransactionType::Confidential {
+            return None;
+        }
+        if transaction.input.function != CONFIDENTIAL_PAYLOAD_FUNCTION {
+            return None;
+        }
+        serde_json::from_str(transaction.input.args.first()?).ok()
+    }
+}
+
+fn wrap_key_for_recipient(symmetric_key: &[u8; 32], recipient: &X25519PublicKey) -> BeaconResult<WrappedKey> {
+    let ephemeral_secret = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral_public = X25519PublicKey::from(&ephemeral_secret);
+    let shared_secret = ephemeral_secret.diffie_hellman(recipient);
+    let wrap_key = derive_wrap_key(shared_secret.as_bytes());
+
+    let mut nonce_bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce_bytes);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&wrap_key));
+    let wrapped = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), symmetric_key.as_ref())
+        .map_err(|e| BeaconError::crypto(format!("key wrap failed: {}", e)))?;
+
+    Ok(WrappedKey {
+        recipient_public_key: hex::encode(recipient.as_bytes()),
+        ephemeral_public_key: hex::encode(ephemeral_public.as_bytes()),
+        nonce: hex::encode(nonce_bytes),
+        wrapped_key: hex::encode(wrapped),
+    })
+}
+
+fn unwrap_key(wrapped: &WrappedKey, secret: &StaticSecret) -> BeaconResult<[u8; 32]> {
+    let ephemeral_bytes = hex::decode(&wrapped.ephemeral_public_key)
+        .map_err(|e| BeaconError::crypto(format!("invalid ephemeral key: {}", e)))?;
+    let ephemeral_array: [u8; 32] = ephemeral_bytes
+        .try_into()
+        .map_err(|_| BeaconError::crypto("invalid ephemeral key: expected 32 bytes"))?;
+    let ephemeral_public = X25519PublicKey::from(ephemeral_array);
+
+    let shared_secret = secret.diffie_hellman(&ephemeral_public);
+    let wrap_key = derive_wrap_key(shared_secret.as_bytes());
+
+    let nonce_bytes = hex::decode(&wrapped.nonce)
+        .map_err(|e| BeaconError::crypto(format!("invalid wrapped-key nonce: {}", e)))?;
+    let ciphertext = hex::decode(&wrapped.wrapped_key)
+        .map_err(|e| BeaconError::crypto(format!("invalid wrapped key: {}", e)))?;
+    if nonce_bytes.len() != 12 {
+        return Err(BeaconError::crypto("invalid wrapped-key nonce: expected 12 bytes"));
+    }
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&wrap_key));
+    let symmetric_key_bytes = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| BeaconError::crypto("not authorized to decrypt this transaction"))?;
+
+    symmetric_key_bytes
+        .try_into()
+        .map_err(|_| BeaconError::crypto("unwrapped key has unexpected length"))
+}
+
+/// Derive a 32-byte AES key from a raw X25519 shared secret
+fn derive_wrap_key(shared_secret: &[u8]) -> [u8; 32] {
+    let hex_hash = hash_message(shared_secret);
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hex::decode(hex_hash).unwrap());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidential_round_trip_for_authorized_recipient() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let alice = StaticSecret::random_from_rng(OsRng);
+        let bob = StaticSecret::random_from_rng(OsRng);
+
+        let recipients = vec![X25519PublicKey::from(&alice)];
+        let tx = ConfidentialTransaction::seal(b"transfer 10 to bob", &recipients, &signing_key).unwrap();
+
+        assert!(tx.verify_commitment().unwrap());
+
+        let decrypted = tx.decrypt(&alice).unwrap();
+        assert_eq!(decrypted, Some(b"transfer 10 to bob".to_vec()));
+
+        // Bob has no wrapped key for this transaction.
+        assert_eq!(tx.decrypt(&bob).unwrap(), None);
+    }
+
+    #[test]
+    fn test_confidential_commitment_rejects_tampering() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let alice = StaticSecret::random_from_rng(OsRng);
+        let recipients = vec![X25519PublicKey::from(&alice)];
+
+        let mut tx = ConfidentialTransaction::seal(b"payload", &recipients, &signing_key).unwrap();
+        tx.commitment = hash_message(b"different payload");
+
+        assert!(!tx.verify_commitment().unwrap());
+    }
+
+    #[test]
+    fn test_decode_from_round_trips_through_transaction_input() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let alice = StaticSecret::random_from_rng(OsRng);
+        let recipients = vec![X25519PublicKey::from(&alice)];
+        let tx = ConfidentialTransaction::seal(b"transfer 10 to bob", &recipients, &signing_key).unwrap();
+
+        let input = tx.to_transaction_input();
+        let transaction = crate::Transaction::new(
+            TransactionType::Confidential,
+            crate::Address::new("alice"),
+            None,
+            input,
+            0,
+        );
+
+        let decoded = ConfidentialTransaction::decode_from(&transaction).unwrap();
+        assert_eq!(decoded.commitment, tx.commitment);
+        assert_eq!(decoded.decrypt(&alice).unwrap(), Some(b"transfer 10 to bob".to_vec()));
+    }
+
+    #[test]
+    fn test_decrypt_rejects_malformed_wrapped_key_instead_of_panicking() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let alice = StaticSecret::random_from_rng(OsRng);
+        let recipients = vec![X25519PublicKey::from(&alice)];
+        let mut tx = ConfidentialTransaction::seal(b"payload", &recipients, &signing_key).unwrap();
+
+        tx.wrapped_keys[0].ephemeral_public_key = hex::encode([0u8; 16]);
+        assert!(tx.decrypt(&alice).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_malformed_nonce_instead_of_panicking() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let alice = StaticSecret::random_from_rng(OsRng);
+        let recipients = vec![X25519PublicKey::from(&alice)];
+        let mut tx = ConfidentialTransaction::seal(b"payload", &recipients, &signing_key).unwrap();
+
+        tx.nonce = hex::encode([0u8; 4]);
+        assert!(tx.decrypt(&alice).is_err());
+    }
+
+    #[test]
+    fn test_decode_from_ignores_non_confidential_transactions() {
+        let input = TransactionInput {
+            chaincode_id: "cc".to_string(),
+            function: CONFIDENTIAL_PAYLOAD_FUNCTION.to_string(),
+            args: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let transaction =
+            crate::Transaction::new(TransactionType::Invoke, crate::Address::new("alice"), None, input, 0);
+
+        assert!(ConfidentialTransaction::decode_from(&transaction).is_none());
+    }
+}
@@ -1,7 +1,118 @@
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use aes_gcm::aead::{Aead, KeyInit};
+use scrypt::Params as ScryptParams;
 use crate::{BeaconError, BeaconResult};
 
+/// Current version of the encrypted keystore JSON format
+pub const ENCRYPTED_KEYSTORE_VERSION: u32 = 1;
+
+/// Signature scheme a signed payload (currently just `Transaction`) was
+/// signed under. `Ed25519` requires the verifier to already have the
+/// signer's `VerifyingKey` out of band, same as every other ed25519 use in
+/// this module. `Secp256k1Recoverable` instead lets the signer's public key
+/// - and from it, their address - be recovered directly from the signature
+/// and the signed digest, the way Ethereum/Bitcoin tooling (e.g.
+/// ethcore's eth-secp256k1) authenticates without carrying a public key
+/// alongside the signature.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum SignatureScheme {
+    #[default]
+    Ed25519,
+    Secp256k1Recoverable,
+}
+
+impl SignatureScheme {
+    /// Stable one-byte discriminant for the canonical transaction encoding
+    /// (see `beacon_core::transaction::write_field`) - pinned explicitly so
+    /// reordering the enum's variants can never change an existing hash.
+    pub fn canonical_tag(&self) -> u8 {
+        match self {
+            SignatureScheme::Ed25519 => 0,
+            SignatureScheme::Secp256k1Recoverable => 1,
+        }
+    }
+}
+
+/// Sign `message` with a secp256k1 recoverable ECDSA signature, returning
+/// `r || s || recovery_id` hex-encoded - the 65-byte layout
+/// `secp256k1_recover_address` expects, so the signature alone is enough
+/// to recover the signer.
+pub fn secp256k1_sign_recoverable(signing_key: &k256::ecdsa::SigningKey, message: &[u8]) -> String {
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+
+    let digest = sha256_digest(message);
+    let (signature, recovery_id): (k256::ecdsa::Signature, k256::ecdsa::RecoveryId) = signing_key
+        .sign_prehash_recoverable(&digest)
+        .expect("secp256k1 signing over a fixed-size digest cannot fail");
+
+    let mut bytes = signature.to_bytes().to_vec();
+    bytes.push(recovery_id.to_byte());
+    hex::encode(bytes)
+}
+
+/// Recover the signer's address from a secp256k1 recoverable signature and
+/// the message it was signed over, with no separately supplied public key
+/// - see `SignatureScheme::Secp256k1Recoverable`. The address is the hex
+/// encoding of the recovered public key's SEC1-compressed form, the same
+/// "address is just the hex public key" convention `KeyPair::verifying_key_hex`
+/// uses for ed25519.
+pub fn secp256k1_recover_address(message: &[u8], signature_hex: &str) -> BeaconResult<String> {
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey as Secp256k1VerifyingKey};
+
+    let bytes = hex::decode(signature_hex)
+        .map_err(|e| BeaconError::crypto(format!("invalid secp256k1 signature hex: {}", e)))?;
+    if bytes.len() != 65 {
+        return Err(BeaconError::crypto(
+            "secp256k1 recoverable signature must be 65 bytes (r || s || recovery_id)",
+        ));
+    }
+    let (sig_bytes, recovery_byte) = bytes.split_at(64);
+
+    let signature = Signature::try_from(sig_bytes)
+        .map_err(|e| BeaconError::crypto(format!("invalid secp256k1 signature: {}", e)))?;
+    let recovery_id = RecoveryId::from_byte(recovery_byte[0])
+        .ok_or_else(|| BeaconError::crypto("invalid secp256k1 recovery id"))?;
+
+    let digest = sha256_digest(message);
+    let verifying_key = Secp256k1VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|e| BeaconError::crypto(format!("secp256k1 recovery failed: {}", e)))?;
+
+    Ok(hex::encode(verifying_key.to_encoded_point(true).as_bytes()))
+}
+
+/// SHA-256 digest of `message` as a fixed-size array, for the
+/// prehash-based secp256k1 signing/recovery APIs.
+fn sha256_digest(message: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+/// Versioned, at-rest-encrypted representation of a `KeyPair`
+///
+/// The signing key is never stored in the clear: a symmetric key is derived
+/// from the caller's passphrase with scrypt, and the secret bytes are sealed
+/// with AES-256-GCM using a random nonce. `mac` lets a wrong passphrase be
+/// rejected before the AEAD decrypt is even attempted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedKeyPair {
+    pub version: u32,
+    pub kdf: String,
+    pub kdf_salt: String,
+    pub kdf_log_n: u8,
+    pub kdf_r: u32,
+    pub kdf_p: u32,
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub mac: String,
+}
+
 /// Key pair for digital signatures
 #[derive(Debug, Clone)]
 pub struct KeyPair {
@@ -65,6 +176,91 @@ impl KeyPair {
     pub fn verify(&self, message: &[u8], signature_hex: &str) -> bool {
         verify_signature(&self.verifying_key, message, signature_hex)
     }
+
+    /// Seal this key pair into a versioned, passphrase-encrypted JSON document
+    pub fn to_encrypted_json(&self, passphrase: &str) -> BeaconResult<String> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+
+        let (log_n, r, p) = (15u8, 8u32, 1u32);
+        let derived_key = derive_keystore_key(passphrase, &salt, log_n, r, p)?;
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&derived_key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), self.signing_key_bytes().as_ref())
+            .map_err(|e| BeaconError::crypto(format!("keystore encryption failed: {}", e)))?;
+
+        let encrypted = EncryptedKeyPair {
+            version: ENCRYPTED_KEYSTORE_VERSION,
+            kdf: "scrypt".to_string(),
+            kdf_salt: hex::encode(salt),
+            kdf_log_n: log_n,
+            kdf_r: r,
+            kdf_p: p,
+            cipher: "aes-256-gcm".to_string(),
+            nonce: hex::encode(nonce_bytes),
+            ciphertext: hex::encode(ciphertext),
+            mac: hash_message(&derived_key),
+        };
+
+        serde_json::to_string_pretty(&encrypted).map_err(BeaconError::from)
+    }
+
+    /// Recover a key pair from a document produced by `to_encrypted_json`
+    pub fn from_encrypted_json(json: &str, passphrase: &str) -> BeaconResult<Self> {
+        let encrypted: EncryptedKeyPair = serde_json::from_str(json)?;
+
+        if encrypted.version != ENCRYPTED_KEYSTORE_VERSION {
+            return Err(BeaconError::crypto(format!(
+                "unsupported keystore version: {}",
+                encrypted.version
+            )));
+        }
+        if encrypted.kdf != "scrypt" {
+            return Err(BeaconError::crypto(format!("unsupported KDF: {}", encrypted.kdf)));
+        }
+
+        let salt = hex::decode(&encrypted.kdf_salt)
+            .map_err(|e| BeaconError::crypto(format!("invalid keystore salt: {}", e)))?;
+        let derived_key = derive_keystore_key(
+            passphrase,
+            &salt,
+            encrypted.kdf_log_n,
+            encrypted.kdf_r,
+            encrypted.kdf_p,
+        )?;
+
+        if !constant_time_eq(hash_message(&derived_key).as_bytes(), encrypted.mac.as_bytes()) {
+            return Err(BeaconError::crypto("incorrect passphrase"));
+        }
+
+        let nonce_bytes = hex::decode(&encrypted.nonce)
+            .map_err(|e| BeaconError::crypto(format!("invalid keystore nonce: {}", e)))?;
+        let ciphertext = hex::decode(&encrypted.ciphertext)
+            .map_err(|e| BeaconError::crypto(format!("invalid keystore ciphertext: {}", e)))?;
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&derived_key));
+        let secret_bytes = cipher
+            .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+            .map_err(|_| BeaconError::crypto("incorrect passphrase or corrupted keystore"))?;
+
+        Self::from_bytes(&secret_bytes)
+    }
+}
+
+/// Derive a 32-byte symmetric key from a passphrase and scrypt parameters
+fn derive_keystore_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> BeaconResult<[u8; 32]> {
+    let params = ScryptParams::new(log_n, r, p, 32)
+        .map_err(|e| BeaconError::crypto(format!("invalid scrypt parameters: {}", e)))?;
+
+    let mut derived_key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut derived_key)
+        .map_err(|e| BeaconError::crypto(format!("key derivation failed: {}", e)))?;
+
+    Ok(derived_key)
 }
 
 /// Create a verifying key from hex string
@@ -158,6 +354,24 @@ mod tests {
         assert!(!keypair.verify(wrong_message, &signature));
     }
 
+    #[test]
+    fn test_encrypted_keystore_round_trip() {
+        let original = KeyPair::generate();
+        let json = original.to_encrypted_json("correct horse battery staple").unwrap();
+
+        let restored = KeyPair::from_encrypted_json(&json, "correct horse battery staple").unwrap();
+        assert_eq!(original.signing_key_bytes(), restored.signing_key_bytes());
+    }
+
+    #[test]
+    fn test_encrypted_keystore_wrong_passphrase() {
+        let original = KeyPair::generate();
+        let json = original.to_encrypted_json("correct horse battery staple").unwrap();
+
+        let result = KeyPair::from_encrypted_json(&json, "wrong passphrase");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_hash_message() {
         let message = b"test message";
@@ -66,10 +66,20 @@ pub enum BeaconError {
     /// Rate limit exceeded
     #[error("Rate limit exceeded: {0}")]
     RateLimitExceeded(String),
-    
+
     /// Internal server error
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// A block uses a feature (signaled by its header `version`) that
+    /// hasn't activated yet at its height, per the node's `ForkSchedule`.
+    #[error("Feature not activated: {0}")]
+    FeatureNotActivated(String),
+
+    /// A validator was caught proposing two different blocks at the same
+    /// height - see `beacon_consensus::Slasher`.
+    #[error("Slashing violation: {0}")]
+    Slashing(String),
 }
 
 impl BeaconError {
@@ -122,11 +132,26 @@ impl BeaconError {
     pub fn not_found(msg: impl Into<String>) -> Self {
         Self::NotFound(msg.into())
     }
+
+    /// Create a permission denied error
+    pub fn permission_denied(msg: impl Into<String>) -> Self {
+        Self::PermissionDenied(msg.into())
+    }
     
     /// Create an internal error
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    /// Create a feature-not-activated error
+    pub fn feature_not_activated(msg: impl Into<String>) -> Self {
+        Self::FeatureNotActivated(msg.into())
+    }
+
+    /// Create a slashing error
+    pub fn slashing(msg: impl Into<String>) -> Self {
+        Self::Slashing(msg.into())
+    }
 }
 
 /// Result type alias for BEACON operations
@@ -3,9 +3,13 @@ pub mod transaction;
 pub mod block;
 pub mod error;
 pub mod crypto;
+pub mod merkle;
+pub mod confidential;
 
 pub use types::*;
 pub use transaction::*;
 pub use block::*;
 pub use error::*;
 pub use crypto::*;
+pub use merkle::*;
+pub use confidential::*;
@@ -0,0 +1,419 @@
+use serde::{Deserialize, Serialize};
+use crate::crypto::{constant_time_eq, hash_message};
+
+/// Which side of a parent node a proof's sibling hash sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MerkleSide {
+    Left,
+    Right,
+}
+
+/// One step of a Merkle inclusion proof: a sibling hash and which side it's on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProofStep {
+    pub sibling: String,
+    pub side: MerkleSide,
+}
+
+/// Binary Merkle tree over arbitrary byte items, built bottom-up with SHA-256
+///
+/// Each leaf is `SHA-256(item_bytes)` and each internal node is
+/// `SHA-256(left || right)`. When a level has an odd number of nodes the
+/// last node is duplicated, both while building and while verifying, so the
+/// two stay consistent.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<String>>,
+}
+
+impl MerkleTree {
+    /// Build a tree from the raw bytes of each item
+    pub fn new(items: &[impl AsRef<[u8]>]) -> Self {
+        let leaves: Vec<String> = items.iter().map(|item| hash_message(item.as_ref())).collect();
+        Self::from_leaf_hashes(leaves)
+    }
+
+    /// Build a tree from already-hashed (hex) leaves
+    pub fn from_leaf_hashes(leaves: Vec<String>) -> Self {
+        if leaves.is_empty() {
+            return Self {
+                levels: vec![vec![hash_message(b"")]],
+            };
+        }
+
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let current = levels.last().unwrap();
+            let mut next = Vec::with_capacity(current.len().div_ceil(2));
+
+            for chunk in current.chunks(2) {
+                let right = if chunk.len() > 1 { &chunk[1] } else { &chunk[0] };
+                next.push(hash_pair(&chunk[0], right));
+            }
+
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    /// The Merkle root as a hex string
+    pub fn root(&self) -> String {
+        self.levels.last().unwrap()[0].clone()
+    }
+
+    /// Number of leaves committed to by this tree
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Build an inclusion proof for the leaf at `index`, ordered from leaf to root
+    pub fn generate_proof(&self, index: usize) -> Option<Vec<MerkleProofStep>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut idx = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let is_right_node = idx % 2 == 1;
+            let sibling_idx = if is_right_node {
+                idx - 1
+            } else if idx + 1 < level.len() {
+                idx + 1
+            } else {
+                idx
+            };
+
+            proof.push(MerkleProofStep {
+                sibling: level[sibling_idx].clone(),
+                side: if is_right_node { MerkleSide::Left } else { MerkleSide::Right },
+            });
+
+            idx /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// Recompute the root by folding `leaf` with each proof step in order and
+    /// compare it to `root` in constant time
+    pub fn verify_proof(root: &str, leaf: &[u8], proof: &[MerkleProofStep]) -> bool {
+        Self::verify_proof_from_leaf_hash(root, &hash_message(leaf), proof)
+    }
+
+    /// Same as `verify_proof`, but for a tree built with `from_leaf_hashes`:
+    /// `leaf_hash` is already the hex leaf hash, so it is folded with the
+    /// proof steps directly instead of being hashed again.
+    pub fn verify_proof_from_leaf_hash(root: &str, leaf_hash: &str, proof: &[MerkleProofStep]) -> bool {
+        let mut current = leaf_hash.to_string();
+
+        for step in proof {
+            current = match step.side {
+                MerkleSide::Left => hash_pair(&step.sibling, &current),
+                MerkleSide::Right => hash_pair(&current, &step.sibling),
+            };
+        }
+
+        constant_time_eq(current.as_bytes(), root.as_bytes())
+    }
+}
+
+fn hash_pair(left: &str, right: &str) -> String {
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Levels in a `SparseMerkleTree`: one per bit of a SHA-256 key hash, so
+/// every key - present or absent - has a path of exactly this length from
+/// root to leaf.
+const SMT_DEPTH: usize = 256;
+
+/// A membership (`value: Some`) or non-membership (`value: None`) proof for
+/// one key against a `SparseMerkleTree` root, ordered leaf to root like
+/// `MerkleProofStep` - `siblings[0]` is the leaf's immediate sibling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SparseMerkleProof {
+    pub siblings: Vec<String>,
+    pub value: Option<Vec<u8>>,
+}
+
+/// Binary sparse Merkle tree over a key/value map, following the
+/// deposit/merkle-proof pattern used in Lighthouse's eth1 integration:
+/// fixed at `SMT_DEPTH` levels so every key has a proof of the same length,
+/// whether or not it's actually set. Keys are addressed by `SHA-256(key)`,
+/// read one bit at a time from the most significant bit; each leaf is
+/// `SHA-256(key || value)` and each internal node is `SHA-256(left ||
+/// right)`. Empty subtrees reuse a precomputed default hash per level
+/// instead of materializing the 2^256 - n absent leaves, so building or
+/// proving against this tree only costs time proportional to the entries
+/// that are actually set.
+pub struct SparseMerkleTree {
+    /// `default_hashes[d]` is the root of an empty subtree `d` levels tall
+    /// (`d = 0` is a single empty leaf, `d = SMT_DEPTH` is the whole empty tree)
+    default_hashes: Vec<String>,
+    /// Entries actually present, as `(key path, leaf hash)`, sorted by path
+    leaves: Vec<([u8; 32], String)>,
+    /// Raw values by key, so `prove` can return a membership proof's value
+    /// without being able to invert `leaf_hash`
+    values: std::collections::HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl SparseMerkleTree {
+    /// Build a tree over `entries` (raw, un-hashed keys and values)
+    pub fn new(entries: &[(Vec<u8>, Vec<u8>)]) -> Self {
+        let mut leaves: Vec<([u8; 32], String)> = entries
+            .iter()
+            .map(|(key, value)| (key_path(key), leaf_hash(key, value)))
+            .collect();
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let values = entries.iter().cloned().collect();
+
+        Self {
+            default_hashes: default_hashes(),
+            leaves,
+            values,
+        }
+    }
+
+    /// The sparse Merkle root as a hex string
+    pub fn root(&self) -> String {
+        subtree_root(&self.leaves, SMT_DEPTH, &self.default_hashes)
+    }
+
+    /// Build a membership or non-membership proof for `key`: a proof whose
+    /// `value` is `None` and that verifies against `root()` demonstrates
+    /// `key` is absent from the tree.
+    pub fn prove(&self, key: &[u8]) -> SparseMerkleProof {
+        let path = key_path(key);
+        let mut siblings = Vec::with_capacity(SMT_DEPTH);
+        collect_siblings(&self.leaves, 0, &path, &self.default_hashes, &mut siblings);
+        siblings.reverse();
+
+        SparseMerkleProof {
+            siblings,
+            value: self.values.get(key).cloned(),
+        }
+    }
+
+    /// Recompute the root implied by `key` and `proof` and compare it to
+    /// `root` in constant time. A `proof.value` of `None` checked against a
+    /// real `root` proves `key` is absent from the tree that produced it.
+    pub fn verify(root: &str, key: &[u8], proof: &SparseMerkleProof) -> bool {
+        if proof.siblings.len() != SMT_DEPTH {
+            return false;
+        }
+
+        let path = key_path(key);
+        let mut current = match &proof.value {
+            Some(value) => leaf_hash(key, value),
+            None => empty_leaf_hash(),
+        };
+
+        for (i, sibling) in proof.siblings.iter().enumerate() {
+            let depth = SMT_DEPTH - 1 - i;
+            current = if bit_at(&path, depth) {
+                hash_pair(sibling, &current)
+            } else {
+                hash_pair(&current, sibling)
+            };
+        }
+
+        constant_time_eq(current.as_bytes(), root.as_bytes())
+    }
+}
+
+/// `SHA-256(key)`, read as a fixed-length bit path from most to least significant bit
+fn key_path(key: &[u8]) -> [u8; 32] {
+    use sha2::{Sha256, Digest};
+    let mut hasher = Sha256::new();
+    hasher.update(key);
+    hasher.finalize().into()
+}
+
+fn leaf_hash(key: &[u8], value: &[u8]) -> String {
+    hash_message(&[key, value].concat())
+}
+
+/// Hash of an absent leaf - distinct from any real `leaf_hash`, since no
+/// real key/value pair hashes to `SHA-256` of this literal marker
+fn empty_leaf_hash() -> String {
+    hash_message(b"beacon:sparse-merkle:empty-leaf")
+}
+
+fn bit_at(path: &[u8; 32], depth: usize) -> bool {
+    let byte = path[depth / 8];
+    (byte >> (7 - depth % 8)) & 1 == 1
+}
+
+/// `default_hashes[d]` for `d` in `0..=SMT_DEPTH`
+fn default_hashes() -> Vec<String> {
+    let mut hashes = Vec::with_capacity(SMT_DEPTH + 1);
+    hashes.push(empty_leaf_hash());
+    for _ in 0..SMT_DEPTH {
+        let previous = hashes.last().unwrap();
+        hashes.push(hash_pair(previous, previous));
+    }
+    hashes
+}
+
+/// Root of the subtree containing `leaves` (sorted by path, all sharing the
+/// path prefix leading to this subtree), which is `remaining_depth` levels
+/// tall. Splits on the next bit and recurses; an empty side short-circuits
+/// to the precomputed default for its height instead of recursing further.
+fn subtree_root(leaves: &[([u8; 32], String)], remaining_depth: usize, default_hashes: &[String]) -> String {
+    if leaves.is_empty() {
+        return default_hashes[remaining_depth].clone();
+    }
+    if remaining_depth == 0 {
+        return leaves[0].1.clone();
+    }
+
+    let depth = SMT_DEPTH - remaining_depth;
+    let split = leaves.partition_point(|(path, _)| !bit_at(path, depth));
+    let (left, right) = leaves.split_at(split);
+
+    hash_pair(
+        &subtree_root(left, remaining_depth - 1, default_hashes),
+        &subtree_root(right, remaining_depth - 1, default_hashes),
+    )
+}
+
+/// Sibling hashes for `path`, in root-to-leaf order (reversed by `prove`
+/// into the leaf-to-root order `SparseMerkleProof` documents)
+fn collect_siblings(
+    leaves: &[([u8; 32], String)],
+    depth: usize,
+    path: &[u8; 32],
+    default_hashes: &[String],
+    out: &mut Vec<String>,
+) {
+    if depth == SMT_DEPTH {
+        return;
+    }
+
+    let split = leaves.partition_point(|(leaf_path, _)| !bit_at(leaf_path, depth));
+    let (left, right) = leaves.split_at(split);
+    let remaining_depth = SMT_DEPTH - depth - 1;
+
+    if bit_at(path, depth) {
+        out.push(subtree_root(left, remaining_depth, default_hashes));
+        collect_siblings(right, depth + 1, path, default_hashes, out);
+    } else {
+        out.push(subtree_root(right, remaining_depth, default_hashes));
+        collect_siblings(left, depth + 1, path, default_hashes, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_round_trip_for_every_leaf() {
+        let items: Vec<Vec<u8>> = (0..5).map(|i| format!("item-{}", i).into_bytes()).collect();
+        let tree = MerkleTree::new(&items);
+
+        for (index, item) in items.iter().enumerate() {
+            let proof = tree.generate_proof(index).unwrap();
+            assert!(MerkleTree::verify_proof(&tree.root(), item, &proof));
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_leaf() {
+        let items: Vec<Vec<u8>> = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+        let tree = MerkleTree::new(&items);
+
+        let proof = tree.generate_proof(0).unwrap();
+        assert!(!MerkleTree::verify_proof(&tree.root(), b"not-a", &proof));
+    }
+
+    #[test]
+    fn test_single_leaf_tree() {
+        let items: Vec<Vec<u8>> = vec![b"only".to_vec()];
+        let tree = MerkleTree::new(&items);
+
+        let proof = tree.generate_proof(0).unwrap();
+        assert!(proof.is_empty());
+        assert!(MerkleTree::verify_proof(&tree.root(), b"only", &proof));
+    }
+
+    #[test]
+    fn test_proof_round_trip_from_leaf_hashes() {
+        let leaves: Vec<String> = (0..5).map(|i| hash_message(format!("item-{}", i).as_bytes())).collect();
+        let tree = MerkleTree::from_leaf_hashes(leaves.clone());
+
+        for (index, leaf_hash) in leaves.iter().enumerate() {
+            let proof = tree.generate_proof(index).unwrap();
+            assert!(MerkleTree::verify_proof_from_leaf_hash(&tree.root(), leaf_hash, &proof));
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_index_has_no_proof() {
+        let items: Vec<Vec<u8>> = vec![b"a".to_vec(), b"b".to_vec()];
+        let tree = MerkleTree::new(&items);
+
+        assert!(tree.generate_proof(2).is_none());
+    }
+
+    fn sample_smt() -> SparseMerkleTree {
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = (0..5)
+            .map(|i| (format!("key-{}", i).into_bytes(), format!("value-{}", i).into_bytes()))
+            .collect();
+        SparseMerkleTree::new(&entries)
+    }
+
+    #[test]
+    fn test_smt_membership_proof_round_trip() {
+        let tree = sample_smt();
+
+        for i in 0..5 {
+            let key = format!("key-{}", i).into_bytes();
+            let proof = tree.prove(&key);
+            assert_eq!(proof.siblings.len(), SMT_DEPTH);
+            assert_eq!(proof.value.as_deref(), Some(format!("value-{}", i).as_bytes()));
+            assert!(SparseMerkleTree::verify(&tree.root(), &key, &proof));
+        }
+    }
+
+    #[test]
+    fn test_smt_non_membership_proof() {
+        let tree = sample_smt();
+
+        let proof = tree.prove(b"key-absent");
+        assert!(proof.value.is_none());
+        assert!(SparseMerkleTree::verify(&tree.root(), b"key-absent", &proof));
+    }
+
+    #[test]
+    fn test_smt_proof_rejects_wrong_root() {
+        let tree = sample_smt();
+
+        let key = b"key-0";
+        let proof = tree.prove(key);
+        assert!(!SparseMerkleTree::verify(&empty_leaf_hash(), key, &proof));
+    }
+
+    #[test]
+    fn test_smt_proof_rejects_tampered_value() {
+        let tree = sample_smt();
+
+        let key = b"key-0";
+        let mut proof = tree.prove(key);
+        proof.value = Some(b"not-value-0".to_vec());
+        assert!(!SparseMerkleTree::verify(&tree.root(), key, &proof));
+    }
+
+    #[test]
+    fn test_smt_empty_tree_root_is_default() {
+        let tree = SparseMerkleTree::new(&[]);
+        assert_eq!(tree.root(), default_hashes()[SMT_DEPTH]);
+    }
+}
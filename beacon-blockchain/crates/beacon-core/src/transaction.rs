@@ -1,6 +1,29 @@
 use serde::{Deserialize, Serialize};
 use ed25519_dalek::{Signer, Verifier};
-use crate::{TransactionId, Address, Timestamp, Hash};
+use crate::{TransactionId, Address, Timestamp, Hash, SignatureScheme};
+
+/// Domain-separation tag prepended to the hash preimage built by
+/// `Transaction::calculate_hash` - binds the encoding to this wire format
+/// and to "hash" specifically, so the same field bytes can never collide
+/// with the signing preimage (see `SIGNING_DOMAIN_TAG`).
+const HASH_DOMAIN_TAG: &[u8] = b"BEACON_TX_HASH_V1";
+
+/// Domain-separation tag prepended to the signing preimage built by
+/// `Transaction::get_signing_data` - see `HASH_DOMAIN_TAG`.
+const SIGNING_DOMAIN_TAG: &[u8] = b"BEACON_TX_SIG_V1";
+
+/// Append `field` to `buf` as a canonical, self-describing item: a
+/// big-endian `u32` byte length followed by the raw bytes. Building
+/// preimages this way - rather than concatenating `bincode::serialize`
+/// output, which is not guaranteed stable across versions, platforms, or
+/// even field order - means two fields can never be confused for each
+/// other (`write_field(b"ab"); write_field(b"c")` can't collide with
+/// `write_field(b"a"); write_field(b"bc")`), which an un-prefixed
+/// concatenation can.
+fn write_field(buf: &mut Vec<u8>, field: &[u8]) {
+    buf.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    buf.extend_from_slice(field);
+}
 
 /// Transaction type enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -13,6 +36,74 @@ pub enum TransactionType {
     Invoke,
     /// System configuration update
     Config,
+    /// A sealed `ConfidentialTransaction` payload - the chaincode invocation
+    /// it carries is opaque on-chain and only recovered by a node holding a
+    /// wrapped-key entry; see `ConfidentialTransaction::to_transaction_input`/
+    /// `decode_from`.
+    Confidential,
+}
+
+impl TransactionType {
+    /// Stable one-byte discriminant used by the canonical encoding - unlike
+    /// `bincode`'s derived discriminant, this is pinned explicitly so
+    /// reordering the enum's variants can never change existing hashes.
+    fn canonical_tag(&self) -> u8 {
+        match self {
+            TransactionType::Transfer => 0,
+            TransactionType::Deploy => 1,
+            TransactionType::Invoke => 2,
+            TransactionType::Config => 3,
+            TransactionType::Confidential => 4,
+        }
+    }
+}
+
+/// Admission policy for zero-gas-price "service transactions" - privileged
+/// system/Config transactions an operator wants to accept without payment,
+/// modeled on the ethcore gas-price whitelist backport. Enforced by
+/// `Transaction::validate_service_transaction` against
+/// `SecurityConfig::service_transaction_policy`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ServiceTransactionPolicy {
+    /// Reject every zero-gas-price transaction, regardless of sender.
+    RefuseAll,
+    /// Accept zero-gas-price transactions only from
+    /// `SecurityConfig::service_transaction_allowlist`.
+    #[default]
+    AllowlistOnly,
+    /// Accept every zero-gas-price transaction.
+    AllowAll,
+}
+
+/// A BIP68/112/113-style relative timelock, measured from the block that
+/// last wrote the transaction's `lock_key` rather than from a fixed height
+/// or time. Lets chaincode express escrow and cooldown patterns ("this
+/// asset can't be spent again until N blocks/seconds after its last
+/// change") without a custom timer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RelativeLock {
+    /// Must wait at least this many blocks since `lock_key` was last written
+    Blocks(u32),
+    /// Must wait at least this many seconds since `lock_key` was last written
+    Seconds(u32),
+}
+
+impl RelativeLock {
+    /// Canonically encode this lock into `buf` - a one-byte variant tag
+    /// followed by the big-endian count, so `Blocks(n)` and `Seconds(n)`
+    /// never hash the same.
+    fn canonical_encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            RelativeLock::Blocks(n) => {
+                buf.push(0);
+                buf.extend_from_slice(&n.to_be_bytes());
+            }
+            RelativeLock::Seconds(n) => {
+                buf.push(1);
+                buf.extend_from_slice(&n.to_be_bytes());
+            }
+        }
+    }
 }
 
 /// Transaction input data
@@ -28,6 +119,30 @@ pub struct TransactionInput {
     pub metadata: std::collections::HashMap<String, String>,
 }
 
+impl TransactionInput {
+    /// Canonically encode this input into `buf`. `metadata` is a
+    /// `HashMap`, whose iteration order is not deterministic, so entries
+    /// are sorted by key first - a hash/signature preimage must be the
+    /// same every time for the same logical transaction.
+    fn canonical_encode(&self, buf: &mut Vec<u8>) {
+        write_field(buf, self.chaincode_id.as_bytes());
+        write_field(buf, self.function.as_bytes());
+
+        buf.extend_from_slice(&(self.args.len() as u32).to_be_bytes());
+        for arg in &self.args {
+            write_field(buf, arg.as_bytes());
+        }
+
+        let mut keys: Vec<&String> = self.metadata.keys().collect();
+        keys.sort();
+        buf.extend_from_slice(&(keys.len() as u32).to_be_bytes());
+        for key in keys {
+            write_field(buf, key.as_bytes());
+            write_field(buf, self.metadata[key].as_bytes());
+        }
+    }
+}
+
 /// Transaction structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
@@ -51,8 +166,18 @@ pub struct Transaction {
     pub timestamp: Timestamp,
     /// Digital signature
     pub signature: String,
+    /// Signature scheme `signature` was produced under - see
+    /// `SignatureScheme`. Defaults to `Ed25519` for transactions
+    /// serialized before this field existed.
+    #[serde(default)]
+    pub scheme: SignatureScheme,
     /// Hash of the transaction
     pub hash: Hash,
+    /// State key `relative_lock` (if any) is measured against - the key
+    /// whose last-write block index/timestamp the lock compares to.
+    pub lock_key: Option<String>,
+    /// Optional relative timelock on this transaction; see `RelativeLock`.
+    pub relative_lock: Option<RelativeLock>,
 }
 
 impl Transaction {
@@ -78,34 +203,83 @@ impl Transaction {
             gas_price: 1,         // Default gas price
             timestamp,
             signature: String::new(),
+            scheme: SignatureScheme::default(),
             hash: String::new(),
+            lock_key: None,
+            relative_lock: None,
         };
-        
+
         // Calculate hash
         tx.hash = tx.calculate_hash();
         tx
     }
-    
+
+    /// Attach a relative timelock to this transaction, keyed against
+    /// `lock_key`'s last write, and recompute the hash to cover it.
+    pub fn with_relative_lock(mut self, lock_key: String, lock: RelativeLock) -> Self {
+        self.lock_key = Some(lock_key);
+        self.relative_lock = Some(lock);
+        self.hash = self.calculate_hash();
+        self
+    }
+
+    /// Canonically encode the fields that are consensus-relevant regardless
+    /// of transaction type - `scheme`, `tx_type`, `from`, `to`, `input`,
+    /// `nonce`, `gas_limit`, `gas_price` and `timestamp` - into `buf`.
+    /// Shared by `calculate_hash` and `get_signing_data` so the two
+    /// preimages can never drift apart on what they cover. Covering
+    /// `scheme` keeps a transaction from being re-authenticated under a
+    /// different signature scheme than the one it was signed with.
+    fn canonical_core_fields(&self, buf: &mut Vec<u8>) {
+        buf.push(self.scheme.canonical_tag());
+        buf.push(self.tx_type.canonical_tag());
+        write_field(buf, self.from.as_str().as_bytes());
+        match &self.to {
+            Some(to) => {
+                buf.push(1);
+                write_field(buf, to.as_str().as_bytes());
+            }
+            None => buf.push(0),
+        }
+        self.input.canonical_encode(buf);
+        buf.extend_from_slice(&self.nonce.to_be_bytes());
+        buf.extend_from_slice(&self.gas_limit.to_be_bytes());
+        buf.extend_from_slice(&self.gas_price.to_be_bytes());
+        buf.extend_from_slice(&self.timestamp.to_millis().to_be_bytes());
+    }
+
     /// Calculate the hash of the transaction
+    ///
+    /// Built from a canonical, length-prefixed encoding (see `write_field`)
+    /// rather than raw `bincode::serialize` output, so the preimage is
+    /// unambiguous and stable regardless of `bincode`'s internal format.
     pub fn calculate_hash(&self) -> Hash {
         use sha2::{Sha256, Digest};
-        
+
+        let mut buf = Vec::new();
+        write_field(&mut buf, HASH_DOMAIN_TAG);
+        write_field(&mut buf, self.id.as_str().as_bytes());
+        self.canonical_core_fields(&mut buf);
+        match &self.lock_key {
+            Some(key) => {
+                buf.push(1);
+                write_field(&mut buf, key.as_bytes());
+            }
+            None => buf.push(0),
+        }
+        match &self.relative_lock {
+            Some(lock) => {
+                buf.push(1);
+                lock.canonical_encode(&mut buf);
+            }
+            None => buf.push(0),
+        }
+
         let mut hasher = Sha256::new();
-        hasher.update(self.id.as_str().as_bytes());
-        hasher.update(&bincode::serialize(&self.tx_type).unwrap_or_default());
-        hasher.update(self.from.as_str().as_bytes());
-        if let Some(ref to) = self.to {
-            hasher.update(to.as_str().as_bytes());
-        }
-        hasher.update(&bincode::serialize(&self.input).unwrap_or_default());
-        hasher.update(&self.nonce.to_le_bytes());
-        hasher.update(&self.gas_limit.to_le_bytes());
-        hasher.update(&self.gas_price.to_le_bytes());
-        hasher.update(&self.timestamp.to_millis().to_le_bytes());
-        
+        hasher.update(&buf);
         hex::encode(hasher.finalize())
     }
-    
+
     /// Validate the transaction structure
     pub fn validate(&self) -> Result<(), crate::BeaconError> {
         // Check if transaction ID is valid
@@ -134,36 +308,141 @@ impl Transaction {
         if self.hash != self.calculate_hash() {
             return Err(crate::BeaconError::InvalidTransaction("Invalid transaction hash".to_string()));
         }
-        
+
         Ok(())
     }
-    
-    /// Sign the transaction with a private key
+
+    /// Reject this transaction if `scheme` isn't one of `enabled_schemes`.
+    /// Separate from `validate` because `beacon-core` sits below the
+    /// config layer - callers that have a `NodeConfig` in scope (e.g.
+    /// `BeaconNode::submit_transaction`) call this alongside `validate`
+    /// with `SecurityConfig::enabled_signature_schemes`.
+    pub fn validate_signature_scheme(&self, enabled_schemes: &[SignatureScheme]) -> Result<(), crate::BeaconError> {
+        if !enabled_schemes.contains(&self.scheme) {
+            return Err(crate::BeaconError::InvalidTransaction(format!(
+                "signature scheme {:?} is not enabled",
+                self.scheme
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject this transaction if it's a zero-gas-price "service
+    /// transaction" that `policy`/`allowlist` don't permit from `self.from`.
+    /// Non-zero-gas-price transactions always pass, since the policy only
+    /// exists to gate the free admission path - ordinary transactions still
+    /// pay for their own inclusion and need no allowlisting. Separate from
+    /// `validate` for the same reason as `validate_signature_scheme`: the
+    /// policy and allowlist live in `NodeConfig`, which `beacon-core` can't
+    /// see, so callers with one in scope (e.g. `BeaconNode::submit_transaction`)
+    /// call this alongside `validate`.
+    pub fn validate_service_transaction(
+        &self,
+        policy: &ServiceTransactionPolicy,
+        allowlist: &[Address],
+    ) -> Result<(), crate::BeaconError> {
+        if self.gas_price != 0 {
+            return Ok(());
+        }
+
+        match policy {
+            ServiceTransactionPolicy::AllowAll => Ok(()),
+            ServiceTransactionPolicy::RefuseAll => Err(crate::BeaconError::permission_denied(format!(
+                "zero-gas-price service transactions are refused by policy (sender {})",
+                self.from.as_str()
+            ))),
+            ServiceTransactionPolicy::AllowlistOnly => {
+                if allowlist.iter().any(|addr| addr == &self.from) {
+                    Ok(())
+                } else {
+                    Err(crate::BeaconError::permission_denied(format!(
+                        "sender {} is not on the service transaction allowlist",
+                        self.from.as_str()
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Sign the transaction with an ed25519 private key, setting `scheme`
+    /// to `SignatureScheme::Ed25519`
     pub fn sign(&mut self, private_key: &ed25519_dalek::SigningKey) -> Result<(), crate::BeaconError> {
+        self.scheme = SignatureScheme::Ed25519;
+        self.hash = self.calculate_hash();
         let message = self.get_signing_data();
         let signature = private_key.sign(&message);
         self.signature = hex::encode(signature.to_bytes());
         Ok(())
     }
-    
+
+    /// Sign the transaction with a secp256k1 recoverable signature, setting
+    /// `scheme` to `SignatureScheme::Secp256k1Recoverable` so
+    /// `verify_signature` recovers the signer from the signature itself
+    /// instead of expecting an ed25519 key - see
+    /// `crate::secp256k1_sign_recoverable`.
+    pub fn sign_secp256k1(&mut self, signing_key: &k256::ecdsa::SigningKey) -> Result<(), crate::BeaconError> {
+        self.scheme = SignatureScheme::Secp256k1Recoverable;
+        self.hash = self.calculate_hash();
+        let message = self.get_signing_data();
+        self.signature = crate::secp256k1_sign_recoverable(signing_key, &message);
+        Ok(())
+    }
+
     /// Get the data that should be signed
+    ///
+    /// Binds the signature to `self.hash` (which in turn commits to `id`,
+    /// `lock_key` and `relative_lock` - see `calculate_hash`) plus an
+    /// explicit canonical encoding of `tx_type`, `from`, `to`, `input`,
+    /// `nonce`, `gas_limit`, `gas_price` and `timestamp`. The explicit
+    /// fields used to be reachable only through `hash`, which meant
+    /// `gas_limit`/`gas_price` could be altered after signing without
+    /// invalidating the signature as long as `hash` was recomputed to
+    /// match - encoding them directly here closes that gap.
     fn get_signing_data(&self) -> Vec<u8> {
         let mut data = Vec::new();
-        data.extend_from_slice(self.hash.as_bytes());
-        data.extend_from_slice(&self.nonce.to_le_bytes());
-        data.extend_from_slice(&self.timestamp.to_millis().to_le_bytes());
+        write_field(&mut data, SIGNING_DOMAIN_TAG);
+        write_field(&mut data, self.hash.as_bytes());
+        self.canonical_core_fields(&mut data);
         data
     }
     
-    /// Verify the transaction signature
+    /// Verify the transaction signature, dispatching on `scheme`.
+    ///
+    /// For `SignatureScheme::Ed25519` transactions, verifies against the
+    /// supplied `public_key`, exactly as before this field existed. For
+    /// `SignatureScheme::Secp256k1Recoverable` transactions, `public_key`
+    /// is ignored entirely: the signer is instead recovered from the
+    /// signature and signing data and checked against `from` - see
+    /// `verify_secp256k1_self_authenticating`.
     pub fn verify_signature(&self, public_key: &ed25519_dalek::VerifyingKey) -> bool {
-        if let Ok(signature_bytes) = hex::decode(&self.signature) {
-            if let Ok(signature) = ed25519_dalek::Signature::try_from(signature_bytes.as_slice()) {
-                let message = self.get_signing_data();
-                return public_key.verify(&message, &signature).is_ok();
+        match self.scheme {
+            SignatureScheme::Ed25519 => {
+                if let Ok(signature_bytes) = hex::decode(&self.signature) {
+                    if let Ok(signature) = ed25519_dalek::Signature::try_from(signature_bytes.as_slice()) {
+                        let message = self.get_signing_data();
+                        return public_key.verify(&message, &signature).is_ok();
+                    }
+                }
+                false
             }
+            SignatureScheme::Secp256k1Recoverable => self.verify_secp256k1_self_authenticating(),
+        }
+    }
+
+    /// Authenticate a `Secp256k1Recoverable` transaction against its
+    /// declared `from` address with no separately supplied public key, by
+    /// recovering the signer from the signature and signing data - see
+    /// `crate::secp256k1_recover_address`. Returns `false` for a
+    /// transaction signed under any other scheme.
+    pub fn verify_secp256k1_self_authenticating(&self) -> bool {
+        if self.scheme != SignatureScheme::Secp256k1Recoverable {
+            return false;
+        }
+        let message = self.get_signing_data();
+        match crate::secp256k1_recover_address(&message, &self.signature) {
+            Ok(recovered) => recovered == self.from.as_str(),
+            Err(_) => false,
         }
-        false
     }
 }
 
@@ -103,6 +103,9 @@ pub struct ConsensusParams {
     pub block_size_limit: usize,   // bytes
     pub transaction_timeout: u64,  // seconds
     pub validator_rotation_period: u64, // seconds
+    /// How many blocks of history the proposer-equivocation slasher keeps
+    /// per validator before pruning - see `beacon_consensus::Slasher`.
+    pub slashing_evidence_retention_blocks: u64,
 }
 
 impl Default for ConsensusParams {
@@ -112,10 +115,91 @@ impl Default for ConsensusParams {
             block_size_limit: 1_048_576, // 1 MB
             transaction_timeout: 300,     // 5 minutes
             validator_rotation_period: 86400, // 24 hours
+            slashing_evidence_retention_blocks: 10_000,
         }
     }
 }
 
+/// A single scheduled protocol upgrade: at `activation_height`, `fork_name`'s
+/// rules become active and blocks from then on must carry a header
+/// `version` of at least `required_version`, so old blocks keep validating
+/// under the rules that were live when they were produced while new blocks
+/// are held to the new ones.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForkActivation {
+    pub fork_name: String,
+    pub activation_height: BlockIndex,
+    pub required_version: u32,
+}
+
+/// Identifies which fork's rules apply to a given height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ForkId {
+    /// No scheduled fork has activated yet.
+    Genesis,
+    Named(String),
+}
+
+/// Ordered schedule of protocol upgrades - the "handle forks with
+/// superstructs" approach (Helios' Capella activation), adapted to a single
+/// flat schedule rather than per-fork types: `Consensus::fork_at` and
+/// `required_version_at` walk this to decide which rule set applies to a
+/// given block height.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ForkSchedule {
+    /// Activations, in strictly increasing `activation_height` order -
+    /// enforced by `validate` (called from `NodeConfig::validate`).
+    pub activations: Vec<ForkActivation>,
+}
+
+impl ForkSchedule {
+    pub fn new(activations: Vec<ForkActivation>) -> Self {
+        Self { activations }
+    }
+
+    /// The fork active at `height`: the last activation whose
+    /// `activation_height` is `<= height`, or `ForkId::Genesis` if none
+    /// have activated yet.
+    pub fn fork_at(&self, height: BlockIndex) -> ForkId {
+        self.activations
+            .iter()
+            .filter(|activation| activation.activation_height <= height)
+            .last()
+            .map(|activation| ForkId::Named(activation.fork_name.clone()))
+            .unwrap_or(ForkId::Genesis)
+    }
+
+    /// The minimum block header `version` required at `height`, per the
+    /// most recent activation that applies to it (0 before any fork).
+    pub fn required_version_at(&self, height: BlockIndex) -> u32 {
+        self.activations
+            .iter()
+            .filter(|activation| activation.activation_height <= height)
+            .map(|activation| activation.required_version)
+            .last()
+            .unwrap_or(0)
+    }
+
+    /// The next scheduled activation after `height`, if any.
+    pub fn next_after(&self, height: BlockIndex) -> Option<&ForkActivation> {
+        self.activations.iter().find(|activation| activation.activation_height > height)
+    }
+
+    /// Activations must be sorted by strictly increasing `activation_height`
+    /// - called from `NodeConfig::validate`.
+    pub fn validate(&self) -> Result<(), String> {
+        for pair in self.activations.windows(2) {
+            if pair[1].activation_height <= pair[0].activation_height {
+                return Err(format!(
+                    "fork schedule activation heights must be strictly increasing: {} then {}",
+                    pair[0].activation_height, pair[1].activation_height
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// State key-value pair
 pub type StateKey = String;
 pub type StateValue = Vec<u8>;
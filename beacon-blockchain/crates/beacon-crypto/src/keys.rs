@@ -3,38 +3,70 @@ use beacon_core::crypto::KeyPair;
 use beacon_core::{BeaconResult, BeaconError};
 
 /// Key store for managing node keys
+///
+/// Keys are persisted using `KeyPair::to_encrypted_json` / `from_encrypted_json`
+/// so the signing key never touches disk in the clear. Legacy plaintext `.key`
+/// files are only loaded when `allow_plaintext_fallback` is explicitly enabled.
 pub struct KeyStore {
     key_dir: String,
+    allow_plaintext_fallback: bool,
 }
 
 impl KeyStore {
     pub fn new(key_dir: String) -> Self {
-        Self { key_dir }
+        Self {
+            key_dir,
+            allow_plaintext_fallback: false,
+        }
     }
-    
-    pub async fn load_or_generate_keypair(&self, name: &str) -> BeaconResult<KeyPair> {
-        let key_path = format!("{}/{}.key", self.key_dir, name);
-        
+
+    /// Construct a key store that may still load legacy plaintext key files,
+    /// for nodes migrating to the encrypted keystore format.
+    pub fn with_plaintext_fallback(key_dir: String, allow_plaintext_fallback: bool) -> Self {
+        Self {
+            key_dir,
+            allow_plaintext_fallback,
+        }
+    }
+
+    pub async fn load_or_generate_keypair(&self, name: &str, passphrase: &str) -> BeaconResult<KeyPair> {
+        let key_path = format!("{}/{}.key.json", self.key_dir, name);
+        let legacy_path = format!("{}/{}.key", self.key_dir, name);
+
         if std::path::Path::new(&key_path).exists() {
-            self.load_keypair(&key_path).await
+            self.load_encrypted_keypair(&key_path, passphrase).await
+        } else if std::path::Path::new(&legacy_path).exists() {
+            if !self.allow_plaintext_fallback {
+                return Err(BeaconError::crypto(format!(
+                    "refusing to load plaintext key file {} without explicit opt-in",
+                    legacy_path
+                )));
+            }
+            self.load_plaintext_keypair(&legacy_path).await
         } else {
             let keypair = KeyPair::generate();
-            self.save_keypair(&keypair, &key_path).await?;
+            self.save_encrypted_keypair(&keypair, &key_path, passphrase).await?;
             Ok(keypair)
         }
     }
-    
-    async fn load_keypair(&self, path: &str) -> BeaconResult<KeyPair> {
+
+    async fn load_encrypted_keypair(&self, path: &str, passphrase: &str) -> BeaconResult<KeyPair> {
+        let json = tokio::fs::read_to_string(path).await?;
+        KeyPair::from_encrypted_json(&json, passphrase)
+    }
+
+    async fn load_plaintext_keypair(&self, path: &str) -> BeaconResult<KeyPair> {
         let data = tokio::fs::read(path).await?;
         if data.len() != 32 {
             return Err(BeaconError::crypto("Invalid key file length"));
         }
         KeyPair::from_bytes(&data)
     }
-    
-    async fn save_keypair(&self, keypair: &KeyPair, path: &str) -> BeaconResult<()> {
+
+    async fn save_encrypted_keypair(&self, keypair: &KeyPair, path: &str, passphrase: &str) -> BeaconResult<()> {
         tokio::fs::create_dir_all(std::path::Path::new(path).parent().unwrap()).await?;
-        tokio::fs::write(path, keypair.signing_key_bytes()).await?;
+        let json = keypair.to_encrypted_json(passphrase)?;
+        tokio::fs::write(path, json).await?;
         Ok(())
     }
 }
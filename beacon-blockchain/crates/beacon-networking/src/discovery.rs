@@ -1,9 +1,19 @@
 use libp2p::{Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time;
 use tracing::{debug, info, warn};
-use beacon_core::{BeaconError, BeaconResult};
+use beacon_core::{BeaconError, BeaconResult, KeyPair, verify_signature};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use libp2p::multiaddr::Protocol;
+use crate::peer::{serde_multiaddrs, serde_peer_id};
+
+/// Default capacity of a `PeerDiscovery`'s candidate table; once full,
+/// `add_discovered_peer` evicts the lowest-scoring record to make room,
+/// CKB `SqlitePeerStore`-style.
+const DEFAULT_STORE_CAPACITY: usize = 1000;
 
 /// Peer discovery configuration
 #[derive(Debug, Clone)]
@@ -16,6 +26,22 @@ pub struct DiscoveryConfig {
     pub max_discovery_peers: usize,
     /// Timeout for connection attempts
     pub connection_timeout: Duration,
+    /// Maximum number of discovered-peer records to retain - see
+    /// `DEFAULT_STORE_CAPACITY`.
+    pub store_capacity: usize,
+    /// Starting reconnect backoff, vpncloud `ReconnectEntry`-style - see
+    /// `ReconnectState`.
+    pub min_reconnect_interval: Duration,
+    /// Cap on the doubling reconnect backoff (`MAX_RECONNECT_INTERVAL`).
+    pub max_reconnect_interval: Duration,
+    /// If a peer has been failing to connect for longer than this, it's
+    /// dropped from discovery entirely instead of kept around backing off
+    /// forever. `None` means never give up.
+    pub reconnect_giveup_after: Option<Duration>,
+    /// How often to re-resolve DNS-named bootstrap addresses (`/dns4`,
+    /// `/dns6`, `/dnsaddr`), so a long-running node picks up an operator's
+    /// IP rotation without a restart. See `PeerDiscovery::resolve_dns_addr`.
+    pub dns_resolve_interval: Duration,
 }
 
 impl Default for DiscoveryConfig {
@@ -25,47 +51,232 @@ impl Default for DiscoveryConfig {
             discovery_interval: Duration::from_secs(30),
             max_discovery_peers: 10,
             connection_timeout: Duration::from_secs(10),
+            store_capacity: DEFAULT_STORE_CAPACITY,
+            min_reconnect_interval: Duration::from_secs(1),
+            max_reconnect_interval: Duration::from_secs(3600),
+            reconnect_giveup_after: None,
+            dns_resolve_interval: Duration::from_secs(300),
         }
     }
 }
 
+/// Durable persistence for `PeerDiscovery`'s candidate peers, so discovery
+/// history and connection scoring survive a restart instead of starting from
+/// an empty table every time. Mirrors `PeerStore` in `peer.rs`:
+/// `beacon-networking` depends on this trait rather than directly on
+/// `beacon-storage`, and a concrete implementation (writing through
+/// `StorageBackend`) is supplied by whoever wires `PeerDiscovery` up to real
+/// storage.
+#[async_trait::async_trait]
+pub trait DiscoveryStore: Send + Sync {
+    /// Persist (insert or overwrite) a discovered-peer record.
+    async fn save_peer(&self, peer: &DiscoveredPeer) -> BeaconResult<()>;
+    /// Remove a discovered-peer record.
+    async fn remove_peer(&self, peer_id: &PeerId) -> BeaconResult<()>;
+    /// Persist (insert or overwrite) a ban's expiry timestamp.
+    async fn save_ban(&self, peer_id: &PeerId, ban_expiry: u64) -> BeaconResult<()>;
+    /// Remove a ban record.
+    async fn remove_ban(&self, peer_id: &PeerId) -> BeaconResult<()>;
+    /// Load every persisted discovered-peer and ban record, for
+    /// `PeerDiscovery::load` to rebuild its in-memory maps from on startup.
+    async fn load_all(&self) -> BeaconResult<(Vec<DiscoveredPeer>, HashMap<PeerId, u64>)>;
+}
+
 /// Peer discovery service
 pub struct PeerDiscovery {
     config: DiscoveryConfig,
     discovered_peers: HashMap<PeerId, DiscoveredPeer>,
+    /// Discovery-phase bans, keyed by `PeerId`, mapping to a Unix expiry
+    /// timestamp - separate from `PeerManager`'s own ban table in `peer.rs`,
+    /// since a peer can be deprioritized here before it's ever connected.
+    banned: HashMap<PeerId, u64>,
     last_discovery: Instant,
+    /// Durable backing store, if persistence is enabled - see `DiscoveryStore`.
+    store: Option<Arc<dyn DiscoveryStore>>,
+    /// Per-peer reconnect backoff schedule - see `ReconnectState`. Not
+    /// persisted: it's process-local retry timing, not something a restart
+    /// needs to remember.
+    backoff: HashMap<PeerId, ReconnectState>,
+    /// Last time `resolve_bootstrap_peers` ran a DNS lookup, for
+    /// `DiscoveryConfig::dns_resolve_interval` throttling.
+    last_dns_resolve: Option<Instant>,
+    /// Most recently resolved concrete addresses for `config.bootstrap_peers`,
+    /// returned as-is between resolves.
+    resolved_bootstrap_peers: Vec<Multiaddr>,
+    /// Lifetime count of `mark_connection_attempt` calls, for
+    /// `DiscoveryStats::connection_attempts` - see `metrics::DiscoveryReporter`.
+    connection_attempts: u64,
+    /// Lifetime count of failed `mark_connection_attempt` calls, for
+    /// `DiscoveryStats::connection_failures`.
+    connection_failures: u64,
 }
 
-/// Information about a discovered peer
+/// Per-peer reconnect backoff, modeled on vpncloud's `ReconnectEntry`:
+/// `timeout` starts at `DiscoveryConfig::min_reconnect_interval` and doubles
+/// on every failed attempt, capped at `max_reconnect_interval`. `next` is the
+/// earliest instant `discover_new_peers` will consider retrying this peer
+/// again, with a little random jitter mixed in to avoid every backed-off
+/// peer waking up in the same instant.
 #[derive(Debug, Clone)]
-struct DiscoveredPeer {
-    peer_id: PeerId,
-    addresses: Vec<Multiaddr>,
-    discovered_at: Instant,
-    connection_attempts: u32,
-    last_attempt: Option<Instant>,
+struct ReconnectState {
+    tries: u32,
+    timeout: Duration,
+    next: Instant,
+    /// When this peer's current run of failures started, for
+    /// `reconnect_giveup_after`. Cleared on a successful connection.
+    failing_since: Option<Instant>,
+}
+
+impl ReconnectState {
+    /// Fresh backoff state for a peer with no failure history: eligible to
+    /// connect immediately.
+    fn fresh(min_interval: Duration) -> Self {
+        Self {
+            tries: 0,
+            timeout: min_interval,
+            next: Instant::now(),
+            failing_since: None,
+        }
+    }
+
+    /// Record a failed attempt, doubling `timeout` (capped at `max_interval`)
+    /// and pushing `next` out with a little jitter.
+    fn on_failure(&mut self, max_interval: Duration) {
+        self.tries += 1;
+        self.timeout = (self.timeout * 2).min(max_interval);
+        self.failing_since.get_or_insert_with(Instant::now);
+
+        let jitter_ms = rand::random::<u64>() % 1000;
+        self.next = Instant::now() + self.timeout + Duration::from_millis(jitter_ms);
+    }
+}
+
+/// Persistent, scored record of a discovered-but-not-yet-connected peer.
+/// Timestamps are Unix seconds rather than `Instant` so the record can
+/// round-trip through a `DiscoveryStore`, matching `PeerInfo` in `peer.rs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredPeer {
+    #[serde(with = "serde_peer_id")]
+    pub peer_id: PeerId,
+    #[serde(with = "serde_multiaddrs")]
+    pub addresses: Vec<Multiaddr>,
+    pub discovered_at: u64,
+    pub connection_successes: u32,
+    pub connection_failures: u32,
+    pub avg_latency_ms: Option<u64>,
+    pub last_seen: u64,
+}
+
+impl DiscoveredPeer {
+    fn new(peer_id: PeerId, addresses: Vec<Multiaddr>) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Self {
+            peer_id,
+            addresses,
+            discovered_at: now,
+            connection_successes: 0,
+            connection_failures: 0,
+            avg_latency_ms: None,
+            last_seen: now,
+        }
+    }
+
+    /// Score this peer by connection history: untested peers score neutral
+    /// (0), successes add a little, and failures subtract more - so a peer
+    /// that mostly fails to connect sinks below fresh, untested candidates
+    /// rather than merely breaking even, CKB `SqlitePeerStore`-style.
+    pub fn score(&self) -> i64 {
+        self.connection_successes as i64 * 10 - self.connection_failures as i64 * 20
+    }
 }
 
 impl PeerDiscovery {
-    /// Create a new peer discovery service
+    /// Create a new peer discovery service with no persistence: discovered
+    /// peers start empty and nothing is written back on mutation. See
+    /// `PeerDiscovery::with_store` for a persistent one.
     pub fn new(config: DiscoveryConfig) -> Self {
         Self {
             config,
             discovered_peers: HashMap::new(),
+            banned: HashMap::new(),
+            last_discovery: Instant::now(),
+            store: None,
+            backoff: HashMap::new(),
+            last_dns_resolve: None,
+            resolved_bootstrap_peers: Vec::new(),
+            connection_attempts: 0,
+            connection_failures: 0,
+        }
+    }
+
+    /// Create a new peer discovery service backed by `store`, loading every
+    /// persisted record back into the in-memory maps.
+    pub async fn with_store(config: DiscoveryConfig, store: Arc<dyn DiscoveryStore>) -> BeaconResult<Self> {
+        let mut discovery = Self {
+            config,
+            discovered_peers: HashMap::new(),
+            banned: HashMap::new(),
             last_discovery: Instant::now(),
+            store: Some(store),
+            backoff: HashMap::new(),
+            last_dns_resolve: None,
+            resolved_bootstrap_peers: Vec::new(),
+            connection_attempts: 0,
+            connection_failures: 0,
+        };
+        discovery.load().await?;
+        Ok(discovery)
+    }
+
+    /// (Re)hydrate `discovered_peers`/`banned` from `store`, if persistence
+    /// is enabled. A no-op otherwise.
+    pub async fn load(&mut self) -> BeaconResult<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+
+        let (peers, bans) = store.load_all().await?;
+        self.discovered_peers = peers.into_iter().map(|peer| (peer.peer_id, peer)).collect();
+        self.banned = bans;
+        debug!(
+            "Loaded {} discovered peer(s) and {} ban(s) from store",
+            self.discovered_peers.len(),
+            self.banned.len()
+        );
+
+        Ok(())
+    }
+
+    /// Flush every in-memory discovered-peer and ban record to `store` in
+    /// one pass, if persistence is enabled. A no-op otherwise. Individual
+    /// mutations (`add_discovered_peer`, `mark_connection_attempt`, ...)
+    /// already persist incrementally; this is for a full resync, e.g. after
+    /// reconnecting to a store or before a graceful shutdown.
+    pub async fn persist(&self) -> BeaconResult<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+
+        for peer in self.discovered_peers.values() {
+            store.save_peer(peer).await?;
+        }
+        for (peer_id, expiry) in &self.banned {
+            store.save_ban(peer_id, *expiry).await?;
         }
+
+        Ok(())
     }
 
     /// Start peer discovery process
     pub async fn start_discovery(&mut self) -> BeaconResult<Vec<Multiaddr>> {
         let mut peers_to_connect = Vec::new();
 
-        // Connect to bootstrap peers first
+        // Connect to bootstrap peers first, resolving any DNS-named
+        // addresses (`/dns4`, `/dns6`, `/dnsaddr`) to concrete ones.
         if self.discovered_peers.is_empty() {
-            info!("Connecting to {} bootstrap peers", self.config.bootstrap_peers.len());
-            for addr in &self.config.bootstrap_peers {
-                peers_to_connect.push(addr.clone());
-            }
+            let bootstrap_addrs = self.resolve_bootstrap_peers().await;
+            info!("Connecting to {} bootstrap peer address(es)", bootstrap_addrs.len());
+            peers_to_connect.extend(bootstrap_addrs);
         }
 
         // Check if it's time for active discovery
@@ -77,7 +288,7 @@ impl PeerDiscovery {
         }
 
         // Clean up old discovery entries
-        self.cleanup_old_discoveries();
+        self.cleanup_old_discoveries().await;
 
         Ok(peers_to_connect)
     }
@@ -93,8 +304,16 @@ impl PeerDiscovery {
         // - Peer exchange with connected peers
         // - DNS seed nodes
 
-        // Example: Random walk through known peers
-        let known_peers: Vec<_> = self.discovered_peers.values().collect();
+        // Prefer high-score peers, CKB `SqlitePeerStore`-style, so a repeatedly
+        // unreachable candidate doesn't keep eating a discovery-round slot
+        // that a reliable one could use instead.
+        let mut known_peers: Vec<_> = self
+            .discovered_peers
+            .values()
+            .filter(|peer| !self.is_banned(&peer.peer_id))
+            .collect();
+        known_peers.sort_by_key(|peer| std::cmp::Reverse(peer.score()));
+
         for peer in known_peers.iter().take(self.config.max_discovery_peers) {
             // Check if we should retry connection
             if self.should_retry_connection(peer) {
@@ -105,58 +324,163 @@ impl PeerDiscovery {
         Ok(new_peers)
     }
 
-    /// Check if we should retry connecting to a peer
+    /// Check if we should retry connecting to a peer: eligible once its
+    /// backoff `next` instant has elapsed (or it has no backoff state yet,
+    /// i.e. it's never failed). See `ReconnectState`.
     fn should_retry_connection(&self, peer: &DiscoveredPeer) -> bool {
-        // Don't retry if we've attempted too many times
-        if peer.connection_attempts >= 3 {
-            return false;
+        match self.backoff.get(&peer.peer_id) {
+            Some(state) => Instant::now() >= state.next,
+            None => true,
         }
+    }
 
-        // Don't retry if we've attempted recently
-        if let Some(last_attempt) = peer.last_attempt {
-            if last_attempt.elapsed() < Duration::from_secs(60) {
-                return false;
+    /// Add a discovered peer, flushing it to `store` if persistence is
+    /// enabled. If the candidate table is at `store_capacity`, the
+    /// lowest-scoring entry is evicted to make room.
+    pub async fn add_discovered_peer(&mut self, peer_id: PeerId, addresses: Vec<Multiaddr>) {
+        if !self.discovered_peers.contains_key(&peer_id)
+            && self.discovered_peers.len() >= self.config.store_capacity
+        {
+            if let Some(evict_id) = self
+                .discovered_peers
+                .values()
+                .min_by_key(|peer| peer.score())
+                .map(|peer| peer.peer_id)
+            {
+                self.remove_discovered_peer(&evict_id).await;
             }
         }
 
-        true
-    }
+        let discovered_peer = DiscoveredPeer::new(peer_id, addresses);
 
-    /// Add a discovered peer
-    pub fn add_discovered_peer(&mut self, peer_id: PeerId, addresses: Vec<Multiaddr>) {
-        let discovered_peer = DiscoveredPeer {
-            peer_id,
-            addresses,
-            discovered_at: Instant::now(),
-            connection_attempts: 0,
-            last_attempt: None,
-        };
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_peer(&discovered_peer).await {
+                warn!("Failed to persist discovered peer {}: {}", peer_id, e);
+            }
+        }
 
         self.discovered_peers.insert(peer_id, discovered_peer);
+        self.backoff.insert(peer_id, ReconnectState::fresh(self.config.min_reconnect_interval));
         debug!("Added discovered peer: {}", peer_id);
     }
 
-    /// Mark a connection attempt for a peer
-    pub fn mark_connection_attempt(&mut self, peer_id: &PeerId) {
-        if let Some(peer) = self.discovered_peers.get_mut(peer_id) {
-            peer.connection_attempts += 1;
-            peer.last_attempt = Some(Instant::now());
+    /// Record the outcome of a connection attempt for a peer, updating its
+    /// score inputs, its reconnect backoff, and flushing the updated record
+    /// to `store` if persistence is enabled. On success the backoff resets
+    /// to the minimum; on failure `timeout` doubles (capped at
+    /// `max_reconnect_interval`) and, if the peer has been failing for
+    /// longer than `reconnect_giveup_after`, it's dropped from discovery
+    /// entirely.
+    pub async fn mark_connection_attempt(&mut self, peer_id: &PeerId, success: bool, latency_ms: Option<u64>) {
+        self.connection_attempts += 1;
+        if !success {
+            self.connection_failures += 1;
+        }
+
+        let Some(peer) = self.discovered_peers.get_mut(peer_id) else {
+            return;
+        };
+
+        if success {
+            peer.connection_successes += 1;
+            if let Some(latency) = latency_ms {
+                peer.avg_latency_ms = Some(match peer.avg_latency_ms {
+                    Some(avg) => (avg + latency) / 2,
+                    None => latency,
+                });
+            }
+        } else {
+            peer.connection_failures += 1;
+        }
+        peer.last_seen = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_peer(peer).await {
+                warn!("Failed to persist discovered peer {}: {}", peer_id, e);
+            }
+        }
+
+        if success {
+            self.backoff.insert(*peer_id, ReconnectState::fresh(self.config.min_reconnect_interval));
+            return;
+        }
+
+        let state = self
+            .backoff
+            .entry(*peer_id)
+            .or_insert_with(|| ReconnectState::fresh(self.config.min_reconnect_interval));
+        state.on_failure(self.config.max_reconnect_interval);
+        let should_give_up = self
+            .config
+            .reconnect_giveup_after
+            .zip(state.failing_since)
+            .is_some_and(|(giveup_after, since)| since.elapsed() >= giveup_after);
+
+        if should_give_up {
+            debug!("Giving up on peer {} after repeated failed reconnect attempts", peer_id);
+            self.remove_discovered_peer(peer_id).await;
         }
     }
 
-    /// Remove a peer from discovery (e.g., when successfully connected)
-    pub fn remove_discovered_peer(&mut self, peer_id: &PeerId) {
+    /// Remove a peer from discovery (e.g., when successfully connected),
+    /// flushing the removal to `store` if persistence is enabled.
+    pub async fn remove_discovered_peer(&mut self, peer_id: &PeerId) {
         self.discovered_peers.remove(peer_id);
+        self.backoff.remove(peer_id);
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.remove_peer(peer_id).await {
+                warn!("Failed to remove persisted discovered peer {}: {}", peer_id, e);
+            }
+        }
     }
 
-    /// Clean up old discovery entries
-    fn cleanup_old_discoveries(&mut self) {
-        let retention_time = Duration::from_secs(24 * 60 * 60); // 24 hours
-        let now = Instant::now();
+    /// Ban a peer for `ban_duration`, flushing the ban to `store` if
+    /// persistence is enabled.
+    pub async fn ban_peer(&mut self, peer_id: PeerId, ban_duration: Duration) {
+        let ban_expiry = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+            + ban_duration.as_secs();
+        self.banned.insert(peer_id, ban_expiry);
 
-        self.discovered_peers.retain(|_, peer| {
-            now.duration_since(peer.discovered_at) < retention_time
-        });
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_ban(&peer_id, ban_expiry).await {
+                warn!("Failed to persist ban for peer {}: {}", peer_id, e);
+            }
+        }
+    }
+
+    /// Check whether a peer is currently banned from discovery
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        let Some(&expiry) = self.banned.get(peer_id) else {
+            return false;
+        };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        now < expiry
+    }
+
+    /// Clean up old discovery entries and expired bans
+    async fn cleanup_old_discoveries(&mut self) {
+        let retention_secs = 24 * 60 * 60; // 24 hours
+        let now_unix = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        self.discovered_peers
+            .retain(|_, peer| now_unix.saturating_sub(peer.discovered_at) < retention_secs);
+
+        let expired: Vec<PeerId> = self
+            .banned
+            .iter()
+            .filter(|(_, &expiry)| now_unix >= expiry)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        self.banned.retain(|_, &mut expiry| now_unix < expiry);
+
+        if let Some(store) = &self.store {
+            for peer_id in &expired {
+                if let Err(e) = store.remove_ban(peer_id).await {
+                    warn!("Failed to remove expired discovery ban for peer {}: {}", peer_id, e);
+                }
+            }
+        }
     }
 
     /// Get statistics about peer discovery
@@ -165,7 +489,90 @@ impl PeerDiscovery {
             discovered_peers: self.discovered_peers.len(),
             bootstrap_peers: self.config.bootstrap_peers.len(),
             last_discovery_duration: self.last_discovery.elapsed(),
+            connection_attempts: self.connection_attempts,
+            connection_failures: self.connection_failures,
+        }
+    }
+
+    /// (Re)resolve any DNS-named addresses in `config.bootstrap_peers` into
+    /// concrete, validated addresses, throttled by `dns_resolve_interval` so
+    /// a long-lived node doesn't re-query DNS on every discovery round.
+    async fn resolve_bootstrap_peers(&mut self) -> Vec<Multiaddr> {
+        let should_resolve = match self.last_dns_resolve {
+            Some(last) => last.elapsed() >= self.config.dns_resolve_interval,
+            None => true,
+        };
+        if !should_resolve {
+            return self.resolved_bootstrap_peers.clone();
         }
+
+        let mut resolved = Vec::new();
+        for addr in &self.config.bootstrap_peers {
+            for candidate in Self::resolve_dns_addr(addr).await {
+                if is_valid_address(&candidate) {
+                    resolved.push(candidate);
+                }
+            }
+        }
+
+        self.resolved_bootstrap_peers = resolved.clone();
+        self.last_dns_resolve = Some(Instant::now());
+        resolved
+    }
+
+    /// Expand a `/dns4`, `/dns6`, or `/dnsaddr` component in `addr` into
+    /// concrete `/ip4`/`/ip6` addresses via a DNS lookup, aleph-node
+    /// boot-node-style (`/dns4/Node0/tcp/30333/p2p/...`), keeping every
+    /// other component (port, `/p2p/...`, ...) in place. Returns `addr`
+    /// unchanged if it has no DNS component, or an empty list if the lookup
+    /// fails.
+    async fn resolve_dns_addr(addr: &Multiaddr) -> Vec<Multiaddr> {
+        let components: Vec<Protocol> = addr.iter().collect();
+        let has_dns_component = components
+            .iter()
+            .any(|c| matches!(c, Protocol::Dns4(_) | Protocol::Dns6(_) | Protocol::Dnsaddr(_)));
+        if !has_dns_component {
+            return vec![addr.clone()];
+        }
+
+        let Some(hostname) = components.iter().find_map(|c| match c {
+            Protocol::Dns4(host) | Protocol::Dns6(host) | Protocol::Dnsaddr(host) => Some(host.to_string()),
+            _ => None,
+        }) else {
+            return vec![addr.clone()];
+        };
+        let port = components
+            .iter()
+            .find_map(|c| match c {
+                Protocol::Tcp(port) => Some(*port),
+                _ => None,
+            })
+            .unwrap_or(30303);
+
+        let socket_addrs: Vec<std::net::SocketAddr> = match tokio::net::lookup_host((hostname.as_str(), port)).await {
+            Ok(addrs) => addrs.collect(),
+            Err(e) => {
+                warn!("Failed to resolve DNS bootstrap address {}: {}", addr, e);
+                return Vec::new();
+            }
+        };
+
+        socket_addrs
+            .into_iter()
+            .map(|socket_addr| {
+                let mut resolved = Multiaddr::empty();
+                for component in &components {
+                    match component {
+                        Protocol::Dns4(_) | Protocol::Dns6(_) | Protocol::Dnsaddr(_) => match socket_addr.ip() {
+                            std::net::IpAddr::V4(ip) => resolved.push(Protocol::Ip4(ip)),
+                            std::net::IpAddr::V6(ip) => resolved.push(Protocol::Ip6(ip)),
+                        },
+                        other => resolved.push(other.clone()),
+                    }
+                }
+                resolved
+            })
+            .collect()
     }
 }
 
@@ -175,6 +582,11 @@ pub struct DiscoveryStats {
     pub discovered_peers: usize,
     pub bootstrap_peers: usize,
     pub last_discovery_duration: Duration,
+    /// Lifetime count of connection attempts `mark_connection_attempt` has
+    /// recorded, for `metrics::DiscoveryReporter`'s per-interval rate.
+    pub connection_attempts: u64,
+    /// Lifetime count of failed connection attempts.
+    pub connection_failures: u64,
 }
 
 /// Bootstrap node information
@@ -241,11 +653,119 @@ impl BootstrapConfig {
     }
 }
 
+/// Check if an address is valid for connection. DNS-named components
+/// (`/dns4`, `/dns6`, `/dnsaddr`) are accepted as-is. For
+/// `config.bootstrap_peers`, `PeerDiscovery::resolve_bootstrap_peers`
+/// resolves them to concrete `/ip4`/`/ip6` addresses (via `resolve_dns_addr`)
+/// and re-validates the results, aleph-node boot-node-style; addresses
+/// accepted through `PeerExchange::validate_peer_addresses` are not resolved.
+fn is_valid_address(addr: &Multiaddr) -> bool {
+    for component in addr.iter() {
+        match component {
+            Protocol::Ip4(ip) => {
+                if ip.is_loopback() || ip.is_multicast() || ip.is_broadcast() {
+                    return false;
+                }
+            }
+            Protocol::Ip6(ip) => {
+                if ip.is_loopback() || ip.is_multicast() {
+                    return false;
+                }
+            }
+            Protocol::Tcp(port) => {
+                if port == 0 {
+                    return false;
+                }
+            }
+            Protocol::Dns4(_) | Protocol::Dns6(_) | Protocol::Dnsaddr(_) => {}
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Derive an Ed25519 public key from a node's configured private key seed,
+/// vpncloud `public_key_from_private_key`-style (there built on
+/// `Ed25519KeyPair::from_seed_unchecked`), so a node can build and
+/// self-verify its own `SignedPeerRecord` without constructing a full
+/// `KeyPair`.
+pub fn public_key_from_seed(seed: &[u8; 32]) -> [u8; 32] {
+    SigningKey::from_bytes(seed).verifying_key().to_bytes()
+}
+
+/// A peer's self-advertised address record, Ed25519-signed by its own
+/// identity key. `PeerExchange::validate_signed_record` verifies the
+/// signature against the record's own embedded public key and rejects
+/// stale ones, so a malicious connected peer can't flood us with forged or
+/// replayed addresses through peer exchange (an eclipse/poisoning attack).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedPeerRecord {
+    #[serde(with = "serde_peer_id")]
+    pub peer_id: PeerId,
+    #[serde(with = "serde_multiaddrs")]
+    pub addresses: Vec<Multiaddr>,
+    /// Unix seconds the record was signed at - `verify`'s freshness check.
+    pub timestamp: u64,
+    /// Ed25519 public key bytes of the advertising peer, matching
+    /// `KeyPair::verifying_key_bytes`/`public_key_from_seed`.
+    pub public_key: [u8; 32],
+    /// Hex-encoded Ed25519 signature over `signing_payload`, matching
+    /// `KeyPair::sign`.
+    pub signature: String,
+}
+
+impl SignedPeerRecord {
+    /// Build and sign a fresh record for `peer_id`/`addresses` with
+    /// `key_pair`, stamping the current Unix time as the freshness marker.
+    pub fn new(peer_id: PeerId, addresses: Vec<Multiaddr>, key_pair: &KeyPair) -> Self {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let mut record = Self {
+            peer_id,
+            addresses,
+            timestamp,
+            public_key: key_pair.verifying_key_bytes(),
+            signature: String::new(),
+        };
+        record.signature = key_pair.sign(&record.signing_payload());
+        record
+    }
+
+    /// Canonical bytes this record's signature covers: peer ID, every
+    /// address, and the timestamp, in a fixed order so signer and verifier
+    /// always hash the same bytes.
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = self.peer_id.to_bytes();
+        for addr in &self.addresses {
+            payload.extend_from_slice(&addr.to_vec());
+        }
+        payload.extend_from_slice(&self.timestamp.to_be_bytes());
+        payload
+    }
+
+    /// Verify this record's signature against its own embedded public key,
+    /// and that it's no older than `max_age` - rejects a stale record a
+    /// malicious peer might replay.
+    pub fn verify(&self, max_age: Duration) -> bool {
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&self.public_key) else {
+            return false;
+        };
+        if !verify_signature(&verifying_key, &self.signing_payload(), &self.signature) {
+            return false;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        now.saturating_sub(self.timestamp) <= max_age.as_secs()
+    }
+}
+
 /// Peer exchange protocol for discovering peers from connected nodes
 pub struct PeerExchange {
     max_peers_per_request: usize,
     exchange_interval: Duration,
     last_exchange: HashMap<PeerId, Instant>,
+    /// Maximum age a `SignedPeerRecord` can be and still be accepted by
+    /// `validate_signed_record` - see `SignedPeerRecord::verify`.
+    max_record_age: Duration,
 }
 
 impl PeerExchange {
@@ -255,6 +775,7 @@ impl PeerExchange {
             max_peers_per_request: 20,
             exchange_interval: Duration::from_secs(300), // 5 minutes
             last_exchange: HashMap::new(),
+            max_record_age: Duration::from_secs(300),
         }
     }
 
@@ -275,35 +796,22 @@ impl PeerExchange {
     pub fn validate_peer_addresses(&self, addresses: Vec<Multiaddr>) -> Vec<Multiaddr> {
         addresses
             .into_iter()
-            .filter(|addr| self.is_valid_address(addr))
+            .filter(|addr| is_valid_address(addr))
             .take(self.max_peers_per_request)
             .collect()
     }
 
-    /// Check if an address is valid for connection
-    fn is_valid_address(&self, addr: &Multiaddr) -> bool {
-        // Basic validation - reject obviously invalid addresses
-        for component in addr.iter() {
-            match component {
-                libp2p::multiaddr::Protocol::Ip4(ip) => {
-                    if ip.is_loopback() || ip.is_multicast() || ip.is_broadcast() {
-                        return false;
-                    }
-                }
-                libp2p::multiaddr::Protocol::Ip6(ip) => {
-                    if ip.is_loopback() || ip.is_multicast() {
-                        return false;
-                    }
-                }
-                libp2p::multiaddr::Protocol::Tcp(port) => {
-                    if port == 0 || port > 65535 {
-                        return false;
-                    }
-                }
-                _ => {}
-            }
+    /// Authenticated counterpart to `validate_peer_addresses`, for an
+    /// address list received over the wire as a `SignedPeerRecord` rather
+    /// than a locally-constructed one: the record's Ed25519 signature and
+    /// freshness are checked first, and only a record that passes both has
+    /// its addresses run through the usual structural validation. Returns
+    /// an empty list for an unsigned, forged, or stale record.
+    pub fn validate_signed_record(&self, record: &SignedPeerRecord) -> Vec<Multiaddr> {
+        if !record.verify(self.max_record_age) {
+            return Vec::new();
         }
-        true
+        self.validate_peer_addresses(record.addresses.clone())
     }
 
     /// Clean up old exchange records
@@ -320,3 +828,115 @@ impl Default for PeerExchange {
         Self::new()
     }
 }
+
+/// HTTP bootstrap client, Lighthouse `Bootstrapper`-style: given a running
+/// seed node's HTTP API base URL, fetches its `/api/v1/bootstrap` bundle
+/// (known-good peers plus the latest finalized block) and feeds the peers
+/// into a `PeerDiscovery`, so a fresh node can join without hand-configured
+/// multiaddrs. Falls back to a fixed `BootstrapConfig`-style node list if the
+/// HTTP fetch fails.
+pub struct Bootstrapper {
+    client: reqwest::Client,
+}
+
+impl Bootstrapper {
+    /// Create a new bootstrap client.
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetch `seed_url`'s bootstrap bundle and add its peers to `discovery`,
+    /// validating addresses through `exchange.validate_peer_addresses`
+    /// first. If the fetch fails for any reason, falls back to adding
+    /// `fallback_nodes` (e.g. `BootstrapConfig::mainnet_nodes()`) instead.
+    /// Returns the number of peers added.
+    pub async fn bootstrap(
+        &self,
+        discovery: &mut PeerDiscovery,
+        seed_url: &reqwest::Url,
+        exchange: &PeerExchange,
+        fallback_nodes: Vec<BootstrapNode>,
+    ) -> usize {
+        match self.fetch_peers(seed_url, exchange).await {
+            Ok(addresses) => {
+                let added = addresses.len();
+                for (peer_id, addr) in addresses {
+                    discovery.add_discovered_peer(peer_id, vec![addr]).await;
+                }
+                info!("Bootstrapped {} peer(s) from {}", added, seed_url);
+                added
+            }
+            Err(e) => {
+                warn!(
+                    "Bootstrap from {} failed, falling back to {} configured node(s): {}",
+                    seed_url,
+                    fallback_nodes.len(),
+                    e
+                );
+                let mut added = 0;
+                for node in fallback_nodes {
+                    match node.peer_id {
+                        Some(peer_id) => {
+                            discovery.add_discovered_peer(peer_id, vec![node.multiaddr]).await;
+                            added += 1;
+                        }
+                        None => warn!(
+                            "Skipping fallback bootstrap node without a known peer ID: {}",
+                            node.multiaddr
+                        ),
+                    }
+                }
+                added
+            }
+        }
+    }
+
+    /// Fetch and validate the peer list from `seed_url`'s `/api/v1/bootstrap`
+    /// endpoint, pairing each address with the `PeerId` embedded in its
+    /// trailing `/p2p/...` component.
+    async fn fetch_peers(
+        &self,
+        seed_url: &reqwest::Url,
+        exchange: &PeerExchange,
+    ) -> BeaconResult<Vec<(PeerId, Multiaddr)>> {
+        let url = seed_url
+            .join("api/v1/bootstrap")
+            .map_err(|e| BeaconError::network(format!("Invalid bootstrap URL: {}", e)))?;
+
+        let response: serde_json::Value = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| BeaconError::network(format!("Failed to fetch bootstrap info from {}: {}", seed_url, e)))?
+            .json()
+            .await
+            .map_err(|e| BeaconError::network(format!("Invalid bootstrap response from {}: {}", seed_url, e)))?;
+
+        let addresses: Vec<Multiaddr> = response["peers"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        Ok(exchange
+            .validate_peer_addresses(addresses)
+            .into_iter()
+            .filter_map(|addr| match addr.iter().last() {
+                Some(Protocol::P2p(peer_id)) => Some((peer_id, addr)),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+impl Default for Bootstrapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,279 @@
+use beacon_core::{BeaconError, BeaconResult, Transaction};
+use std::collections::{HashSet, VecDeque};
+
+/// How many recently-relayed block hashes / transaction ids a
+/// `ConnectionFilter` remembers to suppress duplicate relays, unless
+/// overridden by `ConnectionFilterConfig::recent_cache_size`.
+const DEFAULT_RECENT_CACHE_SIZE: usize = 64;
+
+/// Bounds on a peer-installed `ConnectionFilter`, so a misbehaving or
+/// careless peer can't force us to hash every relayed item against an
+/// oversized bitset. Mirrors BIP37's `MAX_BLOOM_FILTER_SIZE`/`MAX_HASH_FUNCS`.
+#[derive(Debug, Clone)]
+pub struct ConnectionFilterConfig {
+    /// Largest bloom filter a peer may install, in bytes.
+    pub max_filter_size_bytes: usize,
+    /// Largest number of hash functions a peer may request.
+    pub max_hash_functions: u32,
+    /// How many recently relayed block hashes / transaction ids to remember
+    /// per peer for duplicate suppression.
+    pub recent_cache_size: usize,
+}
+
+impl Default for ConnectionFilterConfig {
+    fn default() -> Self {
+        Self {
+            max_filter_size_bytes: 36_000,
+            max_hash_functions: 50,
+            recent_cache_size: DEFAULT_RECENT_CACHE_SIZE,
+        }
+    }
+}
+
+/// Whether a `ConnectionFilter` auto-adds an item to itself once it matches a
+/// relayed transaction - BIP37's `nFlags`, minus the P2PUBKEY_ONLY mode this
+/// codebase's account-based transactions have no equivalent of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterUpdateFlag {
+    /// Never add anything to the filter automatically.
+    None,
+    /// Add every matching transaction's id back into the filter.
+    All,
+}
+
+/// A BIP37-style bloom filter: `k` hash functions over a fixed-size bitset.
+/// Each hash is a 32-bit murmur3 seeded per function so one base seed
+/// ("tweak") produces `k` independent-looking hashes without `k` separate
+/// hash algorithms.
+#[derive(Debug, Clone)]
+struct BloomFilter {
+    bits: Vec<u8>,
+    num_hash_functions: u32,
+    tweak: u32,
+}
+
+impl BloomFilter {
+    fn new(size_bytes: usize, num_hash_functions: u32, tweak: u32) -> Self {
+        Self {
+            bits: vec![0u8; size_bytes.max(1)],
+            num_hash_functions,
+            tweak,
+        }
+    }
+
+    /// Seed for hash function `i`, derived from the filter's tweak.
+    fn seed(&self, i: u32) -> u32 {
+        i.wrapping_mul(0xFBA4C795).wrapping_add(self.tweak)
+    }
+
+    fn bit_index(&self, data: &[u8], i: u32) -> usize {
+        let hash = murmur3_32(data, self.seed(i));
+        (hash as usize) % (self.bits.len() * 8)
+    }
+
+    fn insert(&mut self, data: &[u8]) {
+        for i in 0..self.num_hash_functions {
+            let idx = self.bit_index(data, i);
+            self.bits[idx / 8] |= 1 << (idx % 8);
+        }
+    }
+
+    fn contains(&self, data: &[u8]) -> bool {
+        (0..self.num_hash_functions).all(|i| {
+            let idx = self.bit_index(data, i);
+            self.bits[idx / 8] & (1 << (idx % 8)) != 0
+        })
+    }
+}
+
+/// 32-bit murmur3 (x86 variant) over `data`, seeded with `seed`. Implemented
+/// directly since bloom filter membership is the only thing that needs it.
+fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e2d51;
+    const C2: u32 = 0x1b873593;
+
+    let mut hash = seed;
+    let chunks = data.chunks_exact(4);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1);
+        k = k.rotate_left(15);
+        k = k.wrapping_mul(C2);
+
+        hash ^= k;
+        hash = hash.rotate_left(13);
+        hash = hash.wrapping_mul(5).wrapping_add(0xe6546b64);
+    }
+
+    if !remainder.is_empty() {
+        let mut k = 0u32;
+        for (i, &byte) in remainder.iter().enumerate() {
+            k |= (byte as u32) << (8 * i);
+        }
+        k = k.wrapping_mul(C1);
+        k = k.rotate_left(15);
+        k = k.wrapping_mul(C2);
+        hash ^= k;
+    }
+
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85ebca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2ae35);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+/// Bounded FIFO set of recently relayed item ids: inserting past `capacity`
+/// evicts the oldest-inserted id. Recording an id that is already present
+/// does not move it, so eviction follows insertion order, not recency of use.
+#[derive(Debug, Clone)]
+struct RecentItems {
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+    capacity: usize,
+}
+
+impl RecentItems {
+    fn new(capacity: usize) -> Self {
+        Self {
+            order: VecDeque::new(),
+            seen: HashSet::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Record `id` as relayed. Returns `true` the first time (the caller
+    /// should relay it), `false` if it was already recorded.
+    fn record(&mut self, id: &str) -> bool {
+        if self.seen.contains(id) {
+            return false;
+        }
+
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        self.order.push_back(id.to_string());
+        self.seen.insert(id.to_string());
+        true
+    }
+}
+
+/// Per-peer BIP37-style relay filter. A peer installs an optional bloom
+/// filter over the addresses/transaction ids it cares about via
+/// `load_filter`/`add_to_filter`, and `should_relay_transaction` consults it
+/// (plus duplicate suppression) before a transaction is forwarded to that
+/// peer - see `MessagingService::process_outgoing_message`.
+#[derive(Debug, Clone)]
+pub struct ConnectionFilter {
+    bloom: Option<BloomFilter>,
+    update_flag: FilterUpdateFlag,
+    recent_blocks: RecentItems,
+    recent_transactions: RecentItems,
+    config: ConnectionFilterConfig,
+}
+
+impl ConnectionFilter {
+    pub fn new(config: ConnectionFilterConfig) -> Self {
+        Self {
+            bloom: None,
+            update_flag: FilterUpdateFlag::None,
+            recent_blocks: RecentItems::new(config.recent_cache_size),
+            recent_transactions: RecentItems::new(config.recent_cache_size),
+            config,
+        }
+    }
+
+    /// Install a fresh bloom filter, replacing any previous one - BIP37's
+    /// `filterload`. Rejects a filter larger than `max_filter_size_bytes` or
+    /// requesting more than `max_hash_functions`, rather than honoring an
+    /// abusive request.
+    pub fn load_filter(
+        &mut self,
+        size_bytes: usize,
+        num_hash_functions: u32,
+        tweak: u32,
+        update_flag: FilterUpdateFlag,
+    ) -> BeaconResult<()> {
+        if size_bytes > self.config.max_filter_size_bytes {
+            return Err(BeaconError::network(format!(
+                "bloom filter of {} bytes exceeds the {}-byte cap",
+                size_bytes, self.config.max_filter_size_bytes
+            )));
+        }
+        if num_hash_functions > self.config.max_hash_functions {
+            return Err(BeaconError::network(format!(
+                "bloom filter requesting {} hash functions exceeds the cap of {}",
+                num_hash_functions, self.config.max_hash_functions
+            )));
+        }
+
+        self.bloom = Some(BloomFilter::new(size_bytes, num_hash_functions, tweak));
+        self.update_flag = update_flag;
+        Ok(())
+    }
+
+    /// Add one more item (an address or transaction id the peer is
+    /// interested in) to the installed filter - BIP37's `filteradd`. A no-op
+    /// if no filter is loaded.
+    pub fn add_to_filter(&mut self, data: &[u8]) {
+        if let Some(bloom) = &mut self.bloom {
+            bloom.insert(data);
+        }
+    }
+
+    /// Remove the installed filter - BIP37's `filterclear`. With no filter
+    /// loaded, everything matches (unfiltered relay).
+    pub fn clear_filter(&mut self) {
+        self.bloom = None;
+    }
+
+    /// Whether `data` is present in the installed filter. With no filter
+    /// loaded, everything matches.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match &self.bloom {
+            Some(bloom) => bloom.contains(data),
+            None => true,
+        }
+    }
+
+    /// Whether a transaction should be relayed to this peer: unlike BIP37's
+    /// UTXO outpoints, this codebase's transactions are account-based, so a
+    /// match is tested against the transaction id and both the sender and
+    /// (if present) receiver addresses - any one matching counts as interest.
+    /// A transaction that matches but was already relayed is suppressed via
+    /// `recent_transactions`. A match with `FilterUpdateFlag::All` in effect
+    /// auto-adds the transaction id to the filter, so a peer that only
+    /// expressed interest in an address starts tracking that transaction's
+    /// id too.
+    pub fn should_relay_transaction(&mut self, tx: &Transaction) -> bool {
+        let matched = self.matches(tx.id.as_str().as_bytes())
+            || self.matches(tx.from.as_str().as_bytes())
+            || tx.to.as_ref().is_some_and(|to| self.matches(to.as_str().as_bytes()));
+
+        if !matched {
+            return false;
+        }
+        if !self.recent_transactions.record(tx.id.as_str()) {
+            return false;
+        }
+        if self.update_flag == FilterUpdateFlag::All {
+            self.add_to_filter(tx.id.as_str().as_bytes());
+        }
+        true
+    }
+
+    /// Whether a block should be relayed to this peer. BIP37 filters only
+    /// apply to transactions, so this is pure duplicate suppression against
+    /// `recent_blocks`.
+    pub fn should_relay_block(&mut self, block_hash: &str) -> bool {
+        self.recent_blocks.record(block_hash)
+    }
+}
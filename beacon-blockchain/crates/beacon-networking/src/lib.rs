@@ -3,9 +3,12 @@ pub mod peer;
 pub mod protocol;
 pub mod discovery;
 pub mod messaging;
+pub mod filter;
+pub mod metrics;
 
 pub use network::*;
 pub use peer::*;
 pub use protocol::*;
 pub use discovery::*;
 pub use messaging::*;
+pub use filter::*;
@@ -1,11 +1,13 @@
 use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
-use futures::FutureExt;
+use futures::StreamExt;
 use tokio::sync::{broadcast, mpsc};
+use tokio_util::time::{delay_queue, DelayQueue};
 use libp2p::PeerId;
 use uuid::Uuid;
 use tracing::{debug, info, warn, error};
 use beacon_core::{BeaconError, BeaconResult, Block, Transaction};
+use crate::{ConnectionFilter, ConnectionFilterConfig, FilterUpdateFlag, PeerManager, PeerStatus};
 
 /// Message types that can be sent through the network
 #[derive(Debug, Clone)]
@@ -22,6 +24,25 @@ pub enum OutgoingMessage {
     RequestTransaction(String), // transaction_id
 }
 
+impl OutgoingMessage {
+    /// The priority this message is sent at unless the caller overrides it:
+    /// block/tx requests are `High` so a busy node keeps answering them
+    /// promptly, broadcasts are `Normal`, and low-value chatter like pings is
+    /// `Low`. Callers responding to a consensus-critical request (e.g. a
+    /// block request that's blocking another node's sync) should pass
+    /// `MessagePriority::Critical` explicitly rather than rely on this default.
+    pub fn default_priority(&self) -> MessagePriority {
+        match self {
+            OutgoingMessage::RequestBlocks(_, _) => MessagePriority::High,
+            OutgoingMessage::RequestTransaction(_) => MessagePriority::High,
+            OutgoingMessage::BroadcastBlock(_) => MessagePriority::Normal,
+            OutgoingMessage::BroadcastTransaction(_) => MessagePriority::Normal,
+            OutgoingMessage::DirectMessage(_, DirectMessageType::Ping) => MessagePriority::Low,
+            OutgoingMessage::DirectMessage(_, _) => MessagePriority::Normal,
+        }
+    }
+}
+
 /// Direct message types for peer-to-peer communication
 #[derive(Debug, Clone)]
 pub enum DirectMessageType {
@@ -44,10 +65,16 @@ pub enum IncomingMessage {
     PingReceived(PeerId),
     /// Pong received from a peer
     PongReceived(PeerId),
-    /// Block response received
+    /// Block response received as a single message (small ranges)
     BlockResponseReceived(Vec<Block>, String, PeerId), // blocks, request_id, peer
     /// Transaction response received
     TransactionResponseReceived(Option<Transaction>, String, PeerId), // transaction, request_id, peer
+    /// One chunk of a streamed block response, for ranges too large to
+    /// deliver as a single `BlockResponseReceived`
+    BlockChunkReceived(Block, String, PeerId, u32), // block, request_id, peer, seq
+    /// Clean end of a streamed block response; `total` is the number of
+    /// chunks the sender says it sent, used to detect a truncated stream
+    BlockStreamTerminated(String, PeerId, u32), // request_id, peer, total
     /// Peer info received
     PeerInfoReceived(PeerInfoData, PeerId),
     /// Peer list received
@@ -65,10 +92,19 @@ pub struct PeerInfoData {
 
 /// Message routing and delivery service
 pub struct MessagingService {
-    /// Outgoing message queue
-    outgoing_queue: VecDeque<(OutgoingMessage, Instant)>,
+    /// Outgoing messages, queued per priority level
+    outgoing_queue: PriorityQueues,
     /// Pending requests waiting for responses
     pending_requests: HashMap<String, PendingRequest>,
+    /// One timer entry per pending request, keyed by request_id, so an
+    /// expiry fires in O(1) instead of a periodic full scan of `pending_requests`
+    request_expiry: DelayQueue<String>,
+    /// What we know about each peer, used to pick a real target for outgoing
+    /// requests instead of `PeerId::random()`
+    peer_registry: HashMap<PeerId, PeerEntry>,
+    /// Block/transaction ids already forwarded to subscribers, so a message
+    /// looping back through the gossip mesh is dropped instead of re-broadcast
+    seen_cache: SeenCache,
     /// Message delivery statistics
     delivery_stats: DeliveryStats,
     /// Configuration
@@ -77,8 +113,65 @@ pub struct MessagingService {
     incoming_sender: broadcast::Sender<IncomingMessage>,
     /// Command receiver for outgoing messages
     command_receiver: mpsc::Receiver<OutgoingMessage>,
+    /// Keep-alive ping/pong tracking per connected peer - see `run_keepalive`.
+    keepalive: HashMap<PeerId, KeepAliveState>,
+}
+
+/// Per-peer keep-alive ping/pong tracking driving `run_keepalive`: when the
+/// last ping was sent (for pacing and round-trip timing) and how many in a
+/// row have gone unanswered.
+#[derive(Debug, Clone, Default)]
+struct KeepAliveState {
+    /// When the most recently sent ping is still awaiting a pong, used both
+    /// to measure round-trip time once it arrives and to detect a timeout.
+    awaiting_pong_since: Option<Instant>,
+    /// When the last ping was sent at all (answered or not), pacing
+    /// `PeerManagerConfig::keepalive_interval`.
+    last_ping_sent: Option<Instant>,
+    /// Consecutive pings that timed out without a pong.
+    consecutive_missed: u32,
 }
 
+/// What the messaging layer has learned about a peer, kept just accurate
+/// enough to drive `select_peer_for`
+#[derive(Debug, Clone)]
+struct PeerEntry {
+    /// Advertised chain state, if we've received a `PeerInfoReceived` for
+    /// this peer. `None` until then, so a peer we've only heard about
+    /// indirectly (e.g. via a peer list) can't be selected for a request it
+    /// may not be able to serve.
+    info: Option<PeerInfoData>,
+    /// Last time this peer was seen doing anything (pong, peer info, peer
+    /// list), used as a liveness signal
+    last_seen: Instant,
+    /// Requests to this peer that ultimately timed out without a response,
+    /// decayed back to zero on any sign of life. A peer with recent
+    /// failures is excluded from selection until it proves itself again.
+    recent_failures: u32,
+    /// This peer's installed BIP37-style relay filter, if any - see
+    /// `ConnectionFilter` and `MessagingService::load_filter`.
+    filter: Option<ConnectionFilter>,
+}
+
+impl PeerEntry {
+    fn new() -> Self {
+        Self {
+            info: None,
+            last_seen: Instant::now(),
+            recent_failures: 0,
+            filter: None,
+        }
+    }
+}
+
+/// A peer with this many or more recent request failures is excluded from
+/// selection until it responds to something again.
+const MAX_RECENT_FAILURES: u32 = 3;
+
+/// A peer not heard from (pong, peer info, peer list) within this long is
+/// treated as no longer live and excluded from selection.
+const PEER_LIVENESS_WINDOW: Duration = Duration::from_secs(120);
+
 /// Configuration for messaging service
 #[derive(Debug, Clone)]
 pub struct MessagingConfig {
@@ -92,6 +185,15 @@ pub struct MessagingConfig {
     pub max_retry_attempts: u32,
     /// Delay between retry attempts
     pub retry_delay: Duration,
+    /// Our own network id, used to filter out peers on a different network
+    /// when selecting one for a request
+    pub network_id: String,
+    /// Maximum number of block/transaction ids remembered by the gossip
+    /// duplicate-suppression cache at once
+    pub seen_cache_capacity: usize,
+    /// How long a block/transaction id is remembered before it's forgotten
+    /// and would be forwarded again if re-received
+    pub seen_cache_ttl: Duration,
 }
 
 impl Default for MessagingConfig {
@@ -102,6 +204,71 @@ impl Default for MessagingConfig {
             max_pending_requests: 1000,
             max_retry_attempts: 3,
             retry_delay: Duration::from_secs(5),
+            network_id: "beacon_devnet".to_string(),
+            seen_cache_capacity: 10000,
+            seen_cache_ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Bounded, time-expiring cache of block/transaction ids this node has
+/// already forwarded, used to suppress re-broadcasting the same gossip
+/// message. Entries expire after `ttl`; if the cache fills up before that,
+/// the oldest-inserted entry is evicted to make room. Inserting an id that
+/// is already present does not refresh it, so eviction follows insertion
+/// order, not recency of use.
+struct SeenCache {
+    /// Timer per remembered id, so expiry fires in O(1) like `request_expiry`
+    /// rather than a periodic sweep
+    expiry: DelayQueue<String>,
+    keys: HashMap<String, delay_queue::Key>,
+    /// Insertion order, used to find the oldest entry when evicting for
+    /// capacity; `expire` removes an id from here too, so it holds exactly
+    /// the ids in `keys`
+    order: VecDeque<String>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl SeenCache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            expiry: DelayQueue::new(),
+            keys: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            ttl,
+        }
+    }
+
+    /// Record `id` as seen if it isn't already. Returns `true` if this is
+    /// the first time it's been seen (the caller should forward it), `false`
+    /// if it's a duplicate (the caller should drop it).
+    fn insert(&mut self, id: String) -> bool {
+        if self.keys.contains_key(&id) {
+            return false;
+        }
+
+        while self.keys.len() >= self.capacity {
+            let Some(oldest) = self.order.pop_front() else { break };
+            if let Some(key) = self.keys.remove(&oldest) {
+                self.expiry.remove(&key);
+                break;
+            }
+        }
+
+        let key = self.expiry.insert(id.clone(), self.ttl);
+        self.order.push_back(id.clone());
+        self.keys.insert(id, key);
+        true
+    }
+
+    /// An id's timer fired; forget it so it's treated as unseen again.
+    fn expire(&mut self, id: &str) {
+        if self.keys.remove(id).is_some() {
+            if let Some(pos) = self.order.iter().position(|o| o == id) {
+                self.order.remove(pos);
+            }
         }
     }
 }
@@ -114,6 +281,22 @@ struct PendingRequest {
     peer_id: PeerId,
     created_at: Instant,
     retry_count: u32,
+    /// Key into `MessagingService::request_expiry`, so completing the
+    /// request can cancel its timer instead of letting it fire uselessly
+    expiry_key: delay_queue::Key,
+    /// Accumulation state for a streamed block response, present only while
+    /// a `BlockRequest` is (or may be) arriving as `BlockChunkReceived`
+    /// messages rather than a single `BlockResponseReceived`
+    block_stream: Option<BlockStreamProgress>,
+}
+
+/// In-progress accumulation of a streamed block response
+#[derive(Debug, Default)]
+struct BlockStreamProgress {
+    /// Chunks received so far, in arrival order
+    received: Vec<Block>,
+    /// The sequence number the next chunk must carry to be in-order
+    next_seq: u32,
 }
 
 /// Types of requests that can be pending
@@ -134,6 +317,87 @@ pub struct DeliveryStats {
     pub requests_completed: u64,
     pub requests_timed_out: u64,
     pub delivery_failures: u64,
+    /// Current outgoing queue depth for each priority level, indexed the same
+    /// way as `MessagePriority` (Low, Normal, High, Critical)
+    pub queue_depth_by_priority: [usize; 4],
+    /// Requests that timed out and were retried rather than dropped
+    pub requests_retried: u64,
+    /// Streamed block responses that ended early: the stream timed out, or
+    /// the terminator's chunk count didn't match what actually arrived
+    pub partial_stream_failures: u64,
+    /// Block responses served as a stream rather than a single message
+    pub streamed_block_responses: u64,
+    /// Blocks/transactions dropped as already-seen instead of being
+    /// forwarded to subscribers again
+    pub duplicates_suppressed: u64,
+}
+
+/// Maximum number of consecutive sends a priority level may "skip" a
+/// non-empty lower level before that lower level is forced through anyway.
+/// Keeps a steady stream of e.g. `Critical` traffic from starving `Low` out
+/// entirely.
+const MAX_CONSECUTIVE_SKIPS: u32 = 8;
+
+/// Per-priority sub-queues for outgoing messages. Higher levels are drained
+/// first, but each level tracks how many times in a row it has been skipped
+/// in favor of a higher one; once that count hits `MAX_CONSECUTIVE_SKIPS` the
+/// starved level is served next regardless of what else is queued above it.
+#[derive(Default)]
+struct PriorityQueues {
+    /// Indexed by `MessagePriority as usize` (Low=0, Normal=1, High=2, Critical=3)
+    queues: [VecDeque<PriorityMessage>; 4],
+    skip_counts: [u32; 4],
+}
+
+impl PriorityQueues {
+    fn push(&mut self, priority: MessagePriority, message: OutgoingMessage) {
+        self.queues[priority as usize].push_back(PriorityMessage::new(message, priority));
+    }
+
+    fn len(&self) -> usize {
+        self.queues.iter().map(VecDeque::len).sum()
+    }
+
+    fn depths(&self) -> [usize; 4] {
+        [
+            self.queues[0].len(),
+            self.queues[1].len(),
+            self.queues[2].len(),
+            self.queues[3].len(),
+        ]
+    }
+
+    /// Drop the oldest item in the lowest non-empty priority level, for
+    /// backpressure when the combined queue is full.
+    fn drop_oldest(&mut self) {
+        for level in 0..self.queues.len() {
+            if self.queues[level].pop_front().is_some() {
+                return;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<PriorityMessage> {
+        // A level that's been starved past the threshold is served next,
+        // regardless of what's queued above it.
+        for level in 0..self.queues.len() - 1 {
+            if self.skip_counts[level] >= MAX_CONSECUTIVE_SKIPS && !self.queues[level].is_empty() {
+                self.skip_counts[level] = 0;
+                return self.queues[level].pop_front();
+            }
+        }
+
+        for level in (0..self.queues.len()).rev() {
+            if let Some(item) = self.queues[level].pop_front() {
+                for lower in self.skip_counts.iter_mut().take(level) {
+                    *lower += 1;
+                }
+                return Some(item);
+            }
+        }
+
+        None
+    }
 }
 
 impl MessagingService {
@@ -144,78 +408,91 @@ impl MessagingService {
         let (incoming_sender, incoming_receiver) = broadcast::channel(10000);
         let (command_sender, command_receiver) = mpsc::channel(1000);
 
+        let seen_cache = SeenCache::new(config.seen_cache_capacity, config.seen_cache_ttl);
+
         let service = Self {
-            outgoing_queue: VecDeque::new(),
+            outgoing_queue: PriorityQueues::default(),
             pending_requests: HashMap::new(),
+            request_expiry: DelayQueue::new(),
+            peer_registry: HashMap::new(),
+            seen_cache,
             delivery_stats: DeliveryStats::default(),
             config,
             incoming_sender,
             command_receiver,
+            keepalive: HashMap::new(),
         };
 
         (service, incoming_receiver, command_sender)
     }
 
-    /// Process the messaging service
+    /// Process the messaging service. Fully event-driven: the command
+    /// channel and the request-expiry timers are both awaited via `select!`
+    /// rather than polled on a fixed interval, so there's no latency floor on
+    /// command handling and a timeout fires in O(1) the moment it's due.
     pub async fn run(mut self) -> BeaconResult<()> {
-        let mut cleanup_interval = tokio::time::interval(Duration::from_secs(60));
-
         loop {
             // Check for outgoing messages to process
             self.process_outgoing_queue().await;
 
-            // Check for incoming commands (non-blocking)
-            match self.command_receiver.try_recv() {
-                Ok(msg) => {
-                    if let Err(e) = self.enqueue_outgoing_message(msg).await {
-                        error!("Failed to enqueue message: {}", e);
+            tokio::select! {
+                maybe_msg = self.command_receiver.recv() => {
+                    match maybe_msg {
+                        Some(msg) => {
+                            let priority = msg.default_priority();
+                            if let Err(e) = self.enqueue_outgoing_message(msg, priority).await {
+                                error!("Failed to enqueue message: {}", e);
+                            }
+                        }
+                        None => {
+                            warn!("Command channel closed, shutting down messaging service");
+                            break;
+                        }
+                    }
+                }
+                Some(expired) = self.request_expiry.next() => {
+                    match expired {
+                        Ok(expired) => self.handle_request_timeout(expired.into_inner()).await,
+                        Err(e) => error!("Request expiry timer failed: {}", e),
+                    }
+                }
+                Some(expired) = self.seen_cache.expiry.next() => {
+                    match expired {
+                        Ok(expired) => self.seen_cache.expire(expired.into_inner().as_str()),
+                        Err(e) => error!("Seen-cache expiry timer failed: {}", e),
                     }
-                },
-                Err(mpsc::error::TryRecvError::Empty) => {
-                    // No messages available, continue
-                },
-                Err(mpsc::error::TryRecvError::Disconnected) => {
-                    warn!("Command channel closed, shutting down messaging service");
-                    break;
                 }
             }
-
-            // Check if it's time for cleanup
-            if cleanup_interval.tick().now_or_never().is_some() {
-                self.cleanup_expired_requests();
-            }
-
-            // Small delay to prevent busy waiting
-            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
         }
 
         Ok(())
     }
 
-    /// Enqueue an outgoing message
-    async fn enqueue_outgoing_message(&mut self, message: OutgoingMessage) -> BeaconResult<()> {
+    /// Enqueue an outgoing message at the given priority
+    async fn enqueue_outgoing_message(&mut self, message: OutgoingMessage, priority: MessagePriority) -> BeaconResult<()> {
         if self.outgoing_queue.len() >= self.config.max_queue_size {
             warn!("Outgoing message queue is full, dropping oldest message");
-            self.outgoing_queue.pop_front();
+            self.outgoing_queue.drop_oldest();
             self.delivery_stats.delivery_failures += 1;
         }
 
-        self.outgoing_queue.push_back((message, Instant::now()));
-        debug!("Enqueued outgoing message, queue size: {}", self.outgoing_queue.len());
+        self.outgoing_queue.push(priority, message);
+        debug!("Enqueued outgoing message at {:?} priority, queue size: {}", priority, self.outgoing_queue.len());
         Ok(())
     }
 
-    /// Process the outgoing message queue
+    /// Process the outgoing message queue, highest priority first, subject to
+    /// the anti-starvation cap in `PriorityQueues::pop`
     async fn process_outgoing_queue(&mut self) {
-        while let Some((message, queued_at)) = self.outgoing_queue.pop_front() {
+        while let Some(queued) = self.outgoing_queue.pop() {
             // Check if message has been in queue too long
-            if queued_at.elapsed() > self.config.request_timeout {
+            if queued.created_at.elapsed() > self.config.request_timeout {
                 warn!("Message expired in queue, dropping");
                 self.delivery_stats.delivery_failures += 1;
                 continue;
             }
 
-            if let Err(e) = self.process_outgoing_message(message).await {
+            if let Err(e) = self.process_outgoing_message(queued.message).await {
                 error!("Failed to process outgoing message: {}", e);
                 self.delivery_stats.delivery_failures += 1;
             } else {
@@ -225,6 +502,8 @@ impl MessagingService {
             // Yield control to allow other tasks to run
             tokio::task::yield_now().await;
         }
+
+        self.delivery_stats.queue_depth_by_priority = self.outgoing_queue.depths();
     }
 
     /// Process a single outgoing message
@@ -238,7 +517,23 @@ impl MessagingService {
             }
             OutgoingMessage::BroadcastTransaction(transaction) => {
                 debug!("Broadcasting transaction {}", transaction.id.as_str());
-                info!("Would broadcast transaction {} to all peers", transaction.id.as_str());
+                let recipients: Vec<PeerId> = self
+                    .peer_registry
+                    .iter_mut()
+                    .filter_map(|(peer_id, entry)| {
+                        let wants_it = match &mut entry.filter {
+                            Some(filter) => filter.should_relay_transaction(&transaction),
+                            None => true,
+                        };
+                        wants_it.then_some(*peer_id)
+                    })
+                    .collect();
+                info!(
+                    "Would relay transaction {} to {} interested peer(s) (of {} known)",
+                    transaction.id.as_str(),
+                    recipients.len(),
+                    self.peer_registry.len()
+                );
             }
             OutgoingMessage::DirectMessage(peer_id, msg_type) => {
                 debug!("Sending direct message to peer {}: {:?}", peer_id, msg_type);
@@ -276,14 +571,11 @@ impl MessagingService {
 
     /// Send a block request
     async fn send_block_request(&mut self, start_index: u64, count: u32) -> BeaconResult<()> {
-        // For now, just pick the first available peer
-        // In a real implementation, we'd select the best peer for this request
-        let peer_id = PeerId::random(); // Placeholder
+        let request_type = RequestType::BlockRequest { start_index, count };
+        let peer_id = self.select_peer_for(&request_type)
+            .ok_or_else(|| BeaconError::network("No suitable peer available for block request"))?;
 
-        let request_id = self.create_request(
-            peer_id,
-            RequestType::BlockRequest { start_index, count }
-        ).await?;
+        let request_id = self.create_request(peer_id, request_type).await?;
 
         info!("Would send block request to {} (request_id: {})", peer_id, request_id);
         Ok(())
@@ -291,17 +583,53 @@ impl MessagingService {
 
     /// Send a transaction request
     async fn send_transaction_request(&mut self, tx_id: String) -> BeaconResult<()> {
-        let peer_id = PeerId::random(); // Placeholder
+        let request_type = RequestType::TransactionRequest { tx_id: tx_id.clone() };
+        let peer_id = self.select_peer_for(&request_type)
+            .ok_or_else(|| BeaconError::network("No suitable peer available for transaction request"))?;
 
-        let request_id = self.create_request(
-            peer_id,
-            RequestType::TransactionRequest { tx_id: tx_id.clone() }
-        ).await?;
+        let request_id = self.create_request(peer_id, request_type).await?;
 
         info!("Would send transaction request for {} to {} (request_id: {})", tx_id, peer_id, request_id);
         Ok(())
     }
 
+    /// Pick the best-suited known peer for a request, in place of the old
+    /// `PeerId::random()` placeholder. For a `BlockRequest`, candidates are
+    /// restricted to peers on our network whose advertised `best_block_index`
+    /// actually covers the requested range; among those we prefer whichever
+    /// peer currently has the fewest outstanding requests, spreading load the
+    /// way a range-sync scheduler would. Other request types fall back to any
+    /// live, non-failing peer we know about.
+    fn select_peer_for(&self, request_type: &RequestType) -> Option<PeerId> {
+        let outstanding = |peer_id: &PeerId| {
+            self.pending_requests.values().filter(|r| &r.peer_id == peer_id).count()
+        };
+
+        let candidates = self.peer_registry.iter().filter(|(_, entry)| {
+            entry.recent_failures < MAX_RECENT_FAILURES && entry.last_seen.elapsed() < PEER_LIVENESS_WINDOW
+        });
+
+        match request_type {
+            RequestType::BlockRequest { start_index, count } => {
+                let last_needed = start_index.saturating_add(*count as u64).saturating_sub(1);
+                candidates
+                    .filter_map(|(peer_id, entry)| {
+                        let info = entry.info.as_ref()?;
+                        if info.network_id == self.config.network_id && info.best_block_index >= last_needed {
+                            Some(*peer_id)
+                        } else {
+                            None
+                        }
+                    })
+                    .min_by_key(outstanding)
+            }
+            _ => candidates
+                .filter(|(_, entry)| entry.info.as_ref().map_or(true, |i| i.network_id == self.config.network_id))
+                .map(|(peer_id, _)| *peer_id)
+                .min_by_key(outstanding),
+        }
+    }
+
     /// Create a new request and track it
     async fn create_request(&mut self, peer_id: PeerId, request_type: RequestType) -> BeaconResult<String> {
         if self.pending_requests.len() >= self.config.max_pending_requests {
@@ -309,12 +637,15 @@ impl MessagingService {
         }
 
         let request_id = Uuid::new_v4().to_string();
+        let expiry_key = self.request_expiry.insert(request_id.clone(), self.config.request_timeout);
         let pending_request = PendingRequest {
             request_id: request_id.clone(),
             request_type,
             peer_id,
             created_at: Instant::now(),
             retry_count: 0,
+            expiry_key,
+            block_stream: None,
         };
 
         self.pending_requests.insert(request_id.clone(), pending_request);
@@ -323,23 +654,79 @@ impl MessagingService {
         Ok(request_id)
     }
 
-    /// Handle an incoming message
-    pub async fn handle_incoming_message(&mut self, message: IncomingMessage) -> BeaconResult<()> {
+    /// Handle an incoming message. Takes the `PeerManager` so a `PongReceived`
+    /// can feed its measured round-trip time into `PeerInfo::update_latency`
+    /// - see `run_keepalive`.
+    pub async fn handle_incoming_message(
+        &mut self,
+        message: IncomingMessage,
+        peer_manager: &mut PeerManager,
+    ) -> BeaconResult<()> {
         self.delivery_stats.messages_received += 1;
 
         match &message {
+            IncomingMessage::BlockReceived(block, _) => {
+                if !self.seen_cache.insert(block.hash.clone()) {
+                    self.delivery_stats.duplicates_suppressed += 1;
+                    debug!("Suppressing already-seen block {}", block.hash);
+                    return Ok(());
+                }
+            }
+            IncomingMessage::TransactionReceived(transaction, _) => {
+                if !self.seen_cache.insert(transaction.id.as_str().to_string()) {
+                    self.delivery_stats.duplicates_suppressed += 1;
+                    debug!("Suppressing already-seen transaction {}", transaction.id.as_str());
+                    return Ok(());
+                }
+            }
             IncomingMessage::BlockResponseReceived(_, request_id, _) => {
-                if let Some(_) = self.pending_requests.remove(request_id) {
+                if let Some(request) = self.pending_requests.remove(request_id) {
+                    self.request_expiry.remove(&request.expiry_key);
                     self.delivery_stats.requests_completed += 1;
                     debug!("Completed block request {}", request_id);
                 }
             }
             IncomingMessage::TransactionResponseReceived(_, request_id, _) => {
-                if let Some(_) = self.pending_requests.remove(request_id) {
+                if let Some(request) = self.pending_requests.remove(request_id) {
+                    self.request_expiry.remove(&request.expiry_key);
                     self.delivery_stats.requests_completed += 1;
                     debug!("Completed transaction request {}", request_id);
                 }
             }
+            IncomingMessage::BlockChunkReceived(block, request_id, _, seq) => {
+                self.handle_block_chunk(request_id, block.clone(), *seq);
+            }
+            IncomingMessage::BlockStreamTerminated(request_id, _, total) => {
+                self.handle_block_stream_terminated(request_id, *total);
+            }
+            IncomingMessage::PeerInfoReceived(info, peer_id) => {
+                let entry = self.peer_registry.entry(*peer_id).or_insert_with(PeerEntry::new);
+                entry.info = Some(info.clone());
+                entry.last_seen = Instant::now();
+                entry.recent_failures = 0;
+            }
+            IncomingMessage::PeerListReceived(_, peer_id) => {
+                // We only learn multiaddrs here, not chain state, but hearing
+                // from this peer at all is still a liveness signal.
+                let entry = self.peer_registry.entry(*peer_id).or_insert_with(PeerEntry::new);
+                entry.last_seen = Instant::now();
+                entry.recent_failures = 0;
+            }
+            IncomingMessage::PongReceived(peer_id) => {
+                let entry = self.peer_registry.entry(*peer_id).or_insert_with(PeerEntry::new);
+                entry.last_seen = Instant::now();
+                entry.recent_failures = entry.recent_failures.saturating_sub(1);
+
+                if let Some(state) = self.keepalive.get_mut(peer_id) {
+                    if let Some(sent_at) = state.awaiting_pong_since.take() {
+                        state.consecutive_missed = 0;
+                        if let Some(peer) = peer_manager.get_peer_mut(peer_id) {
+                            peer.update_latency(sent_at.elapsed().as_millis() as u64);
+                            peer.update_last_seen();
+                        }
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -351,21 +738,250 @@ impl MessagingService {
         Ok(())
     }
 
-    /// Clean up expired requests
-    fn cleanup_expired_requests(&mut self) {
-        let now = Instant::now();
-        let mut expired_requests = Vec::new();
+    /// One chunk of a streamed block response arrived. Starts the stream's
+    /// accumulation buffer on the first chunk, appends in-order chunks, and
+    /// resets the request's expiry timer so a peer that's steadily streaming
+    /// isn't reaped as timed out between chunks.
+    fn handle_block_chunk(&mut self, request_id: &str, block: Block, seq: u32) {
+        let Some(request) = self.pending_requests.get_mut(request_id) else {
+            debug!("Block chunk for unknown request {}, ignoring", request_id);
+            return;
+        };
+
+        let stream = request.block_stream.get_or_insert_with(BlockStreamProgress::default);
+        if seq == stream.next_seq {
+            stream.received.push(block);
+            stream.next_seq += 1;
+        } else {
+            warn!("Out-of-order block chunk {} for request {} (expected {}), dropping", seq, request_id, stream.next_seq);
+        }
+
+        self.request_expiry.reset(&request.expiry_key, self.config.request_timeout);
+    }
+
+    /// The sender signaled the end of a streamed block response. The request
+    /// only completes cleanly if every chunk up to `total` arrived in order;
+    /// anything else (missing chunks, a terminator with no prior chunks when
+    /// `total > 0`) is a partial-stream failure rather than a completion.
+    fn handle_block_stream_terminated(&mut self, request_id: &str, total: u32) {
+        let Some(request) = self.pending_requests.remove(request_id) else {
+            debug!("Block stream terminator for unknown request {}, ignoring", request_id);
+            return;
+        };
+        self.request_expiry.remove(&request.expiry_key);
+
+        let received = request.block_stream.map(|s| s.received.len() as u32).unwrap_or(0);
+        if received == total {
+            self.delivery_stats.requests_completed += 1;
+            self.delivery_stats.streamed_block_responses += 1;
+            debug!("Completed streamed block request {} ({} chunks)", request_id, total);
+        } else {
+            self.delivery_stats.partial_stream_failures += 1;
+            warn!("Block stream {} terminated early: got {} of {} chunks", request_id, received, total);
+        }
+    }
+
+    /// A request's timer fired with no response received. Retry it with
+    /// exponential backoff (`retry_delay * 2^retry_count`) until
+    /// `max_retry_attempts` is exhausted, then drop it for good.
+    async fn handle_request_timeout(&mut self, request_id: String) {
+        let Some(request) = self.pending_requests.remove(&request_id) else {
+            return;
+        };
 
-        for (request_id, request) in &self.pending_requests {
-            if now.duration_since(request.created_at) > self.config.request_timeout {
-                expired_requests.push(request_id.clone());
+        // A stream that had already started is mid-transfer, not merely slow
+        // to start; retrying would re-request the whole range and orphan the
+        // chunks already received, so count it as a partial failure and drop
+        // it rather than feeding it through the normal retry path.
+        if let Some(stream) = &request.block_stream {
+            if !stream.received.is_empty() {
+                self.peer_registry.entry(request.peer_id).or_insert_with(PeerEntry::new).recent_failures += 1;
+                self.delivery_stats.partial_stream_failures += 1;
+                warn!("Block stream {} timed out mid-transfer after {} chunks", request_id, stream.received.len());
+                return;
             }
         }
 
-        for request_id in expired_requests {
-            self.pending_requests.remove(&request_id);
+        // A timeout is the strongest failure signal we have for this peer;
+        // count it against them so `select_peer_for` steers future requests
+        // elsewhere until they prove themselves responsive again.
+        self.peer_registry.entry(request.peer_id).or_insert_with(PeerEntry::new).recent_failures += 1;
+
+        if request.retry_count >= self.config.max_retry_attempts {
             self.delivery_stats.requests_timed_out += 1;
-            warn!("Request {} timed out", request_id);
+            warn!("Request {} timed out after {} retries", request_id, request.retry_count);
+            return;
+        }
+
+        let backoff = self.config.retry_delay * 2u32.saturating_pow(request.retry_count);
+        let retry_count = request.retry_count + 1;
+
+        // Re-select a peer for block/transaction requests, now that the one
+        // we tried has a fresh failure against it; requests already addressed
+        // to a specific peer (ping, peer info/list) retry against that same
+        // peer. Fall back to the failed peer itself if no better candidate is
+        // known, rather than giving up the retry entirely.
+        let peer_id = match &request.request_type {
+            RequestType::BlockRequest { .. } | RequestType::TransactionRequest { .. } => {
+                self.select_peer_for(&request.request_type).unwrap_or(request.peer_id)
+            }
+            RequestType::PeerInfoRequest | RequestType::PeerListRequest => request.peer_id,
+        };
+
+        let expiry_key = self.request_expiry.insert(request_id.clone(), backoff);
+        self.pending_requests.insert(request_id.clone(), PendingRequest {
+            request_id: request_id.clone(),
+            request_type: request.request_type,
+            peer_id,
+            created_at: Instant::now(),
+            retry_count,
+            expiry_key,
+            block_stream: None,
+        });
+
+        self.delivery_stats.requests_retried += 1;
+        info!("Retrying request {} (attempt {}/{}) after {:?} backoff", request_id, retry_count, self.config.max_retry_attempts, backoff);
+    }
+
+    /// Ping every connected peer due for one (per
+    /// `PeerManagerConfig::keepalive_interval`), and resolve outstanding
+    /// pings that have exceeded `keepalive_timeout` without a pong: each
+    /// miss counts against `PeerManagerConfig::keepalive_max_missed`, and a
+    /// peer that exhausts that budget is transitioned to `Disconnected` and
+    /// docked reputation via `adjust_peer_reputation` - repeated failures
+    /// naturally drive it below the ban threshold. Intended to be called
+    /// periodically by whatever owns both this service and `peer_manager`.
+    pub async fn run_keepalive(&mut self, peer_manager: &mut PeerManager) {
+        let interval = peer_manager.keepalive_interval();
+        let timeout = peer_manager.keepalive_timeout();
+        let max_missed = peer_manager.keepalive_max_missed();
+
+        let connected: Vec<PeerId> = peer_manager
+            .get_connected_peers()
+            .iter()
+            .map(|peer| peer.peer_id)
+            .collect();
+
+        for peer_id in &connected {
+            let state = self.keepalive.entry(*peer_id).or_default();
+
+            if let Some(since) = state.awaiting_pong_since {
+                if since.elapsed() >= timeout {
+                    state.awaiting_pong_since = None;
+                    state.consecutive_missed += 1;
+                    warn!(
+                        "Peer {} missed a keep-alive ping ({} consecutive)",
+                        peer_id, state.consecutive_missed
+                    );
+
+                    if state.consecutive_missed >= max_missed {
+                        warn!(
+                            "Peer {} missed {} consecutive keep-alive pings, disconnecting",
+                            peer_id, state.consecutive_missed
+                        );
+                        if let Some(peer) = peer_manager.get_peer_mut(peer_id) {
+                            peer.set_status(PeerStatus::Disconnected);
+                        }
+                        peer_manager
+                            .adjust_peer_reputation(peer_id, -15, "missed keep-alive pings")
+                            .await;
+                        state.consecutive_missed = 0;
+                        continue;
+                    }
+                }
+            }
+
+            let due = state.last_ping_sent.map_or(true, |sent| sent.elapsed() >= interval);
+            if due && state.awaiting_pong_since.is_none() {
+                let now = Instant::now();
+                state.last_ping_sent = Some(now);
+                state.awaiting_pong_since = Some(now);
+                if let Err(e) = self
+                    .enqueue_outgoing_message(
+                        OutgoingMessage::DirectMessage(*peer_id, DirectMessageType::Ping),
+                        MessagePriority::Low,
+                    )
+                    .await
+                {
+                    error!("Failed to enqueue keep-alive ping for {}: {}", peer_id, e);
+                }
+            }
+        }
+
+        let still_connected: std::collections::HashSet<PeerId> = connected.into_iter().collect();
+        self.keepalive.retain(|peer_id, _| still_connected.contains(peer_id));
+    }
+
+    /// When `peer_manager.connected_peer_count()` exceeds
+    /// `PeerManagerConfig::soft_peer_target`, disconnect the
+    /// highest-latency / lowest-reputation peers first until back at the
+    /// target, rather than waiting for the hard `max_peers` cap to force an
+    /// eviction. Reserved peers are never dropped this way.
+    pub fn consolidate_peers(&self, peer_manager: &mut PeerManager) {
+        let target = peer_manager.soft_peer_target();
+        let connected = peer_manager.connected_peer_count();
+        if connected <= target {
+            return;
+        }
+
+        let mut ranked: Vec<(PeerId, Option<u64>, u8)> = peer_manager
+            .get_connected_peers()
+            .iter()
+            .filter(|peer| !peer_manager.is_reserved(&peer.peer_id))
+            .map(|peer| (peer.peer_id, peer.latency, peer.reputation))
+            .collect();
+
+        // Highest latency first (unknown latency treated as worst), then
+        // lowest reputation, so consolidation sheds the least useful peers.
+        ranked.sort_by(|a, b| {
+            b.1.unwrap_or(u64::MAX)
+                .cmp(&a.1.unwrap_or(u64::MAX))
+                .then(a.2.cmp(&b.2))
+        });
+
+        let to_drop = connected - target;
+        for (peer_id, _, _) in ranked.into_iter().take(to_drop) {
+            info!(
+                "Consolidating peers: disconnecting {} ({} connected, soft target {})",
+                peer_id, connected, target
+            );
+            if let Some(peer) = peer_manager.get_peer_mut(&peer_id) {
+                peer.set_status(PeerStatus::Disconnected);
+            }
+        }
+    }
+
+    /// Install a fresh relay filter for `peer_id` - BIP37's `filterload`.
+    /// Returns an error (without installing anything) if the requested
+    /// filter exceeds `ConnectionFilterConfig`'s caps.
+    pub fn load_filter(
+        &mut self,
+        peer_id: PeerId,
+        size_bytes: usize,
+        num_hash_functions: u32,
+        tweak: u32,
+        update_flag: FilterUpdateFlag,
+    ) -> BeaconResult<()> {
+        let mut filter = ConnectionFilter::new(ConnectionFilterConfig::default());
+        filter.load_filter(size_bytes, num_hash_functions, tweak, update_flag)?;
+        self.peer_registry.entry(peer_id).or_insert_with(PeerEntry::new).filter = Some(filter);
+        Ok(())
+    }
+
+    /// Add one more item of interest to `peer_id`'s installed filter -
+    /// BIP37's `filteradd`. A no-op if that peer has no filter loaded.
+    pub fn add_to_filter(&mut self, peer_id: PeerId, data: &[u8]) {
+        if let Some(entry) = self.peer_registry.get_mut(&peer_id) {
+            if let Some(filter) = &mut entry.filter {
+                filter.add_to_filter(data);
+            }
+        }
+    }
+
+    /// Remove `peer_id`'s installed filter - BIP37's `filterclear`.
+    pub fn clear_filter(&mut self, peer_id: PeerId) {
+        if let Some(entry) = self.peer_registry.get_mut(&peer_id) {
+            entry.filter = None;
         }
     }
 
@@ -0,0 +1,138 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    IntGauge, IntGaugeVec, Registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry,
+};
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::discovery::{DiscoveryStats, PeerDiscovery};
+
+/// How often `spawn_discovery_reporter` samples and emits stats, vpncloud
+/// `STATS_INTERVAL`-style.
+pub const STATS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Registry for peer-discovery gauges, kept separate from `beacon-api`'s own
+/// `REGISTRY` so this crate doesn't need to depend on it; `gather` exposes
+/// the same `MetricFamily`s for a caller (the `/metrics` handler) to merge
+/// into its own scrape.
+pub static DISCOVERY_REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+static DISCOVERED_PEERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge_with_registry!(
+        "beacon_discovery_peers",
+        "Currently known (discovered, not necessarily connected) peer count",
+        DISCOVERY_REGISTRY
+    )
+    .unwrap()
+});
+
+static BOOTSTRAP_PEERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge_with_registry!(
+        "beacon_discovery_bootstrap_peers",
+        "Configured bootstrap peer count",
+        DISCOVERY_REGISTRY
+    )
+    .unwrap()
+});
+
+static LAST_DISCOVERY_AGE_SECONDS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge_with_registry!(
+        "beacon_discovery_last_discovery_age_seconds",
+        "Seconds since the last active discovery round completed",
+        DISCOVERY_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Lifetime connection-attempt counters, by outcome (`attempt`/`failure`) -
+/// a gauge rather than a counter since the underlying value already comes
+/// from `PeerDiscovery`'s own lifetime tally, matching `beacon-api`'s
+/// `DB_READ_CACHE` pattern for pre-aggregated counts.
+static CONNECTION_OUTCOMES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec_with_registry!(
+        "beacon_discovery_connection_attempts_total",
+        "Lifetime peer connection attempts, by outcome",
+        &["outcome"],
+        DISCOVERY_REGISTRY
+    )
+    .unwrap()
+});
+
+/// Render `stats` as statsd gauge lines (`beacon.discovery.<name>:<value>|g`),
+/// vpncloud `StatsdMsg`-style.
+fn statsd_lines(stats: &DiscoveryStats) -> Vec<String> {
+    vec![
+        format!("beacon.discovery.peers:{}|g", stats.discovered_peers),
+        format!("beacon.discovery.bootstrap_peers:{}|g", stats.bootstrap_peers),
+        format!(
+            "beacon.discovery.last_discovery_age_seconds:{}|g",
+            stats.last_discovery_duration.as_secs()
+        ),
+        format!("beacon.discovery.connection_attempts:{}|g", stats.connection_attempts),
+        format!("beacon.discovery.connection_failures:{}|g", stats.connection_failures),
+    ]
+}
+
+/// Refresh the gauges in `DISCOVERY_REGISTRY` from `stats`, and push the
+/// same numbers to `statsd_addr` over UDP if configured. A statsd send
+/// failure is logged and otherwise ignored - an unreachable metrics sink
+/// shouldn't affect discovery itself.
+fn report(stats: &DiscoveryStats, statsd_addr: Option<SocketAddr>) {
+    DISCOVERED_PEERS.set(stats.discovered_peers as i64);
+    BOOTSTRAP_PEERS.set(stats.bootstrap_peers as i64);
+    LAST_DISCOVERY_AGE_SECONDS.set(stats.last_discovery_duration.as_secs() as i64);
+    CONNECTION_OUTCOMES
+        .with_label_values(&["attempt"])
+        .set(stats.connection_attempts as i64);
+    CONNECTION_OUTCOMES
+        .with_label_values(&["failure"])
+        .set(stats.connection_failures as i64);
+
+    let Some(addr) = statsd_addr else {
+        return;
+    };
+
+    let socket = match UdpSocket::bind("0.0.0.0:0") {
+        Ok(socket) => socket,
+        Err(e) => {
+            warn!("Failed to bind statsd UDP socket: {}", e);
+            return;
+        }
+    };
+    for line in statsd_lines(stats) {
+        if let Err(e) = socket.send_to(line.as_bytes(), addr) {
+            warn!("Failed to send statsd metric to {}: {}", addr, e);
+        }
+    }
+}
+
+/// Periodically samples `discovery`'s stats onto the Prometheus gauges above
+/// and, if `statsd_addr` is set, emits the same numbers to a statsd
+/// endpoint every `STATS_INTERVAL` - vpncloud's `StatsdMsg` reporter task,
+/// adapted to `PeerDiscovery`. Runs until the returned handle is aborted;
+/// dropping the handle detaches the task without stopping it.
+pub fn spawn_discovery_reporter(
+    discovery: Arc<RwLock<PeerDiscovery>>,
+    statsd_addr: Option<SocketAddr>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STATS_INTERVAL);
+        loop {
+            interval.tick().await;
+            let stats = discovery.read().await.get_discovery_stats();
+            debug!("Discovery stats: {:?}", stats);
+            report(&stats, statsd_addr);
+        }
+    })
+}
+
+/// Snapshot of `DISCOVERY_REGISTRY`'s metric families, for a caller (e.g.
+/// `beacon-api`'s `/metrics` handler) to append to its own scrape before
+/// encoding it in Prometheus text-exposition format.
+pub fn gather() -> Vec<prometheus::proto::MetricFamily> {
+    DISCOVERY_REGISTRY.gather()
+}
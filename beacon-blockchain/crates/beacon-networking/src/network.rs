@@ -1,14 +1,30 @@
 use libp2p::{
-    gossipsub, identify, kad, mdns, noise, ping, swarm::SwarmEvent, tcp, yamux, Multiaddr, PeerId,
-    Swarm, SwarmBuilder,
+    gossipsub, identify, kad, mdns, multiaddr::Protocol, noise, ping, request_response,
+    swarm::SwarmEvent, tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
 };
 use futures::StreamExt;
-use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 use beacon_core::{BeaconError, BeaconResult, Block, Transaction};
 
-use crate::{NetworkMessage, PeerInfo, ProtocolHandler};
+use crate::{
+    BlockSyncCodec, Capability, NetworkMessage, PeerInfo, PeerManager, PeerManagerConfig, PeerStore,
+    ProtocolHandler, BLOCK_SYNC_PROTOCOL, PROTOCOL_VERSION, SUPPORTED_CAPABILITIES,
+};
+
+/// Read access to local block storage the networking layer needs to answer
+/// block-sync requests, without depending directly on beacon-storage.
+#[async_trait::async_trait]
+pub trait ChainReader: Send + Sync {
+    /// Fetch up to `count` blocks starting at `start_index`, in ascending order.
+    async fn get_blocks_range(&self, start_index: u64, count: u32) -> BeaconResult<Vec<Block>>;
+
+    /// The locally known chain tip, or `None` before genesis has been stored.
+    /// Used to validate incoming blocks' `index`/`previous_hash` before they
+    /// are accepted into the gossipsub mesh.
+    async fn best_block(&self) -> BeaconResult<Option<Block>>;
+}
 
 /// Network configuration
 #[derive(Debug, Clone)]
@@ -17,6 +33,11 @@ pub struct NetworkConfig {
     pub bootstrap_peers: Vec<Multiaddr>,
     pub max_connections: usize,
     pub network_id: String,
+    /// Base URL of a running peer's HTTP API to bootstrap from, as an
+    /// alternative (or supplement) to a preconfigured `bootstrap_peers` list:
+    /// the node fetches `/api/v1/blockchain/genesis` and `/api/v1/network/peers`
+    /// from it, validates the network matches, and dials the peers returned.
+    pub bootstrap_http: Option<reqwest::Url>,
 }
 
 impl Default for NetworkConfig {
@@ -26,6 +47,7 @@ impl Default for NetworkConfig {
             bootstrap_peers: Vec::new(),
             max_connections: 50,
             network_id: "beacon_devnet".to_string(),
+            bootstrap_http: None,
         }
     }
 }
@@ -43,17 +65,29 @@ pub enum NetworkEvent {
     TransactionReceived(Transaction, PeerId),
     /// Peer discovery update
     PeerDiscovered(PeerId, Vec<Multiaddr>),
+    /// A peer requested a range of blocks over the block-sync protocol
+    SyncRequest { peer: PeerId, start_index: u64, count: u32 },
+    /// A peer reported its chain tip via the `PeerInfo` handshake
+    PeerStatus { peer: PeerId, best_block_index: u64 },
     /// Network error occurred
     Error(String),
 }
 
+/// How many blocks to request at once when a peer's handshake reveals it is
+/// ahead of our local tip, so one very-ahead peer can't trigger a single
+/// unbounded sync request.
+const SYNC_CATCH_UP_BATCH: u32 = 128;
+
 /// Main networking component
 pub struct NetworkManager {
     swarm: Swarm<BeaconBehaviour>,
-    peers: HashMap<PeerId, PeerInfo>,
+    peer_manager: PeerManager,
     event_sender: broadcast::Sender<NetworkEvent>,
     command_receiver: mpsc::Receiver<NetworkCommand>,
     protocol_handler: ProtocolHandler,
+    chain_reader: Arc<dyn ChainReader>,
+    network_id: String,
+    bootstrap_http: Option<reqwest::Url>,
 }
 
 /// Commands that can be sent to the network manager
@@ -69,13 +103,27 @@ pub enum NetworkCommand {
     DisconnectPeer(PeerId),
     /// Get list of connected peers
     GetPeers(tokio::sync::oneshot::Sender<Vec<PeerInfo>>),
+    /// Request a range of blocks directly from a specific peer via the
+    /// block-sync protocol, instead of broadcasting over gossip
+    RequestBlocks { peer: PeerId, start_index: u64, count: u32 },
+    /// Pin a peer so it's never evicted. It still counts toward the
+    /// connection caps, which reserved peers widen by a small fixed margin
+    /// (see `PeerManager::is_over_capacity`)
+    AddReservedPeer(PeerId),
+    /// Unpin a previously reserved peer
+    RemoveReservedPeer(PeerId),
 }
 
 impl NetworkManager {
-    /// Create a new network manager
+    /// Create a new network manager. `chain_reader` lets the block-sync
+    /// protocol answer `BlockRequest`s from the local chain store.
+    /// `peer_store`, if given, persists `PeerManager`'s peer/ban records
+    /// across restarts - see `PeerStore`.
     pub async fn new(
         config: NetworkConfig,
         keypair: libp2p::identity::Keypair,
+        chain_reader: Arc<dyn ChainReader>,
+        peer_store: Option<Arc<dyn PeerStore>>,
     ) -> BeaconResult<(Self, broadcast::Receiver<NetworkEvent>, mpsc::Sender<NetworkCommand>)> {
         let local_peer_id = PeerId::from(keypair.public());
         info!("Local peer id: {}", local_peer_id);
@@ -101,12 +149,25 @@ impl NetworkManager {
         let (event_sender, event_receiver) = broadcast::channel(1000);
         let (command_sender, command_receiver) = mpsc::channel(100);
 
+        let peer_manager_config = PeerManagerConfig {
+            max_peers: config.max_connections,
+            ..Default::default()
+        };
+
+        let peer_manager = match peer_store {
+            Some(store) => PeerManager::with_store(peer_manager_config, store).await?,
+            None => PeerManager::new(peer_manager_config),
+        };
+
         let manager = Self {
             swarm,
-            peers: HashMap::new(),
+            peer_manager,
             event_sender,
             command_receiver,
             protocol_handler: ProtocolHandler::new(),
+            chain_reader,
+            network_id: config.network_id,
+            bootstrap_http: config.bootstrap_http,
         };
 
         Ok((manager, event_receiver, command_sender))
@@ -119,6 +180,12 @@ impl NetworkManager {
             .listen_on("/ip4/0.0.0.0/tcp/30303".parse().unwrap())
             .map_err(|e| BeaconError::network(format!("Failed to listen: {}", e)))?;
 
+        if let Err(e) = self.bootstrap_from_http().await {
+            warn!("HTTP bootstrap failed: {}", e);
+        }
+
+        self.reconnect_reliable_peers();
+
         info!("Network manager started");
 
         loop {
@@ -161,14 +228,40 @@ impl NetworkManager {
             SwarmEvent::ConnectionEstablished {
                 peer_id, endpoint, ..
             } => {
+                if self.peer_manager.is_peer_banned(&peer_id) {
+                    info!("Rejecting connection from banned peer {}", peer_id);
+                    self.swarm.disconnect_peer_id(peer_id).ok();
+                    return Ok(());
+                }
+
+                let inbound = !endpoint.is_dialer();
+                if self.peer_manager.is_over_capacity(inbound) {
+                    match self.peer_manager.lowest_scored_peer() {
+                        Some(evicted) if evicted != peer_id => {
+                            info!("At capacity; evicting lowest-reputation peer {} for {}", evicted, peer_id);
+                            self.swarm.disconnect_peer_id(evicted).ok();
+                            self.peer_manager.remove_peer(&evicted);
+                            let _ = self.event_sender.send(NetworkEvent::PeerDisconnected(evicted));
+                        }
+                        _ => {
+                            info!("At capacity with no evictable peer; rejecting connection from {}", peer_id);
+                            self.swarm.disconnect_peer_id(peer_id).ok();
+                            return Ok(());
+                        }
+                    }
+                }
+
                 info!("Connected to peer: {} at {}", peer_id, endpoint.get_remote_address());
                 let peer_info = PeerInfo::new(peer_id, vec![endpoint.get_remote_address().clone()]);
-                self.peers.insert(peer_id, peer_info.clone());
+                self.peer_manager.add_peer(peer_info.clone()).await;
+                self.peer_manager.record_connection_direction(peer_id, inbound);
                 let _ = self.event_sender.send(NetworkEvent::PeerConnected(peer_id, peer_info));
+
+                self.send_status_handshake(peer_id).await?;
             }
             SwarmEvent::ConnectionClosed { peer_id, cause, .. } => {
                 info!("Disconnected from peer: {} (cause: {:?})", peer_id, cause);
-                self.peers.remove(&peer_id);
+                self.peer_manager.remove_peer(&peer_id);
                 let _ = self.event_sender.send(NetworkEvent::PeerDisconnected(peer_id));
             }
             _ => {}
@@ -181,10 +274,10 @@ impl NetworkManager {
         match event {
             BeaconBehaviourEvent::Gossipsub(gossipsub::Event::Message {
                 propagation_source,
+                message_id,
                 message,
-                ..
             }) => {
-                self.handle_gossip_message(propagation_source, message).await?;
+                self.handle_gossip_message(propagation_source, message_id, message).await?;
             }
             BeaconBehaviourEvent::Mdns(mdns::Event::Discovered(list)) => {
                 for (peer_id, multiaddr) in list {
@@ -197,29 +290,447 @@ impl NetworkManager {
             BeaconBehaviourEvent::Identify(identify::Event::Received { peer_id, info }) => {
                 debug!("Received identify info from {}: {:?}", peer_id, info);
             }
+            BeaconBehaviourEvent::BlockSync(event) => {
+                self.handle_block_sync_event(event).await?;
+            }
             _ => {}
         }
         Ok(())
     }
 
-    /// Handle gossip messages
+    /// Redial every peer `PeerManager::get_reliable_peers` reports, so long-
+    /// lasting connections that survived a restart via `PeerStore` get
+    /// reconnected automatically instead of waiting to be rediscovered.
+    /// Gated by `PeerManagerConfig::auto_reconnect_reliable_peers`.
+    fn reconnect_reliable_peers(&mut self) {
+        if !self.peer_manager.auto_reconnect_reliable_peers() {
+            return;
+        }
+
+        let dial_targets: Vec<(PeerId, Vec<Multiaddr>)> = self
+            .peer_manager
+            .get_reliable_peers()
+            .into_iter()
+            .map(|peer| (peer.peer_id, peer.addresses.clone()))
+            .collect();
+
+        for (peer_id, addresses) in dial_targets {
+            for addr in addresses {
+                info!("Reconnecting to reliable peer {} at {}", peer_id, addr);
+                if let Err(e) = self.swarm.dial(addr.clone()) {
+                    warn!("Failed to redial reliable peer {} at {}: {}", peer_id, addr, e);
+                }
+            }
+        }
+    }
+
+    /// Join the network by querying a running peer's HTTP API instead of (or
+    /// alongside) a preconfigured `bootstrap_peers` list: fetch its genesis
+    /// block to make sure we'd be joining the same chain, then fetch and
+    /// dial its peer list. A no-op if `bootstrap_http` wasn't configured.
+    async fn bootstrap_from_http(&mut self) -> BeaconResult<()> {
+        let Some(base_url) = self.bootstrap_http.clone() else {
+            return Ok(());
+        };
+
+        let client = reqwest::Client::new();
+
+        let genesis: serde_json::Value = client
+            .get(base_url.join("api/v1/blockchain/genesis").map_err(|e| {
+                BeaconError::network(format!("Invalid bootstrap URL: {}", e))
+            })?)
+            .send()
+            .await
+            .map_err(|e| BeaconError::network(format!("Failed to fetch bootstrap genesis: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| BeaconError::network(format!("Invalid bootstrap genesis response: {}", e)))?;
+
+        let remote_network_id = genesis["network_id"].as_str().ok_or_else(|| {
+            BeaconError::network("Bootstrap genesis response missing network_id")
+        })?;
+        if remote_network_id != self.network_id {
+            return Err(BeaconError::network(format!(
+                "Refusing to bootstrap from {}: network_id mismatch (theirs: {}, ours: {})",
+                base_url, remote_network_id, self.network_id
+            )));
+        }
+
+        let remote_genesis_hash = genesis["genesis_hash"].as_str().ok_or_else(|| {
+            BeaconError::network("Bootstrap genesis response missing genesis_hash")
+        })?;
+        if let Some(local_genesis) = self.chain_reader.get_blocks_range(0, 1).await?.first() {
+            if local_genesis.hash != remote_genesis_hash {
+                return Err(BeaconError::network(format!(
+                    "Refusing to bootstrap from {}: genesis hash mismatch (theirs: {}, ours: {})",
+                    base_url, remote_genesis_hash, local_genesis.hash
+                )));
+            }
+        }
+
+        let peers: serde_json::Value = client
+            .get(base_url.join("api/v1/network/peers").map_err(|e| {
+                BeaconError::network(format!("Invalid bootstrap URL: {}", e))
+            })?)
+            .send()
+            .await
+            .map_err(|e| BeaconError::network(format!("Failed to fetch bootstrap peers: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| BeaconError::network(format!("Invalid bootstrap peers response: {}", e)))?;
+
+        let addresses = peers["peers"].as_array().cloned().unwrap_or_default();
+        info!("Bootstrapping from {}: dialing {} peer(s)", base_url, addresses.len());
+        for address in addresses {
+            let Some(address) = address.as_str() else { continue };
+            let Ok(addr): Result<Multiaddr, _> = address.parse() else {
+                warn!("Skipping malformed bootstrap peer address: {}", address);
+                continue;
+            };
+
+            if let Some(Protocol::P2p(peer_id)) = addr.iter().last() {
+                self.swarm.behaviour_mut().kademlia.add_address(&peer_id, addr.clone());
+            }
+            if let Err(e) = self.swarm.dial(addr.clone()) {
+                warn!("Failed to dial bootstrap peer at {}: {}", addr, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send our `PeerInfo` as a directed handshake over the block-sync
+    /// protocol to a freshly connected peer, so both sides learn whether the
+    /// other is ahead without waiting for a gossip announcement.
+    async fn send_status_handshake(&mut self, peer: PeerId) -> BeaconResult<()> {
+        let best_block_index = self.chain_reader.best_block().await?.map(|b| b.header.index).unwrap_or(0);
+        let status = NetworkMessage::PeerInfo {
+            version: PROTOCOL_VERSION.to_string(),
+            network_id: self.network_id.clone(),
+            best_block_index,
+            peer_count: self.peer_count() as u32,
+            capabilities: SUPPORTED_CAPABILITIES.iter().map(|s| s.to_string()).collect(),
+        };
+        self.swarm.behaviour_mut().block_sync.send_request(&peer, status);
+        Ok(())
+    }
+
+    /// Process a peer's `PeerInfo` handshake, however it arrived (directed
+    /// block-sync request/response or gossip): reject a mismatched network
+    /// or one missing a mandatory capability, record the peer's reported tip
+    /// and negotiated capabilities, and queue catch-up block requests if
+    /// it's ahead of us.
+    async fn handle_peer_status(
+        &mut self,
+        peer: PeerId,
+        network_id: String,
+        best_block_index: u64,
+        capabilities: Vec<String>,
+    ) -> BeaconResult<()> {
+        if network_id != self.network_id {
+            warn!(
+                "Disconnecting peer {}: network_id mismatch (theirs: {}, ours: {})",
+                peer, network_id, self.network_id
+            );
+            self.swarm.disconnect_peer_id(peer).ok();
+            self.peer_manager.remove_peer(&peer);
+            return Ok(());
+        }
+
+        let parsed_capabilities: Vec<Capability> =
+            capabilities.iter().map(|c| Capability::parse(c)).collect();
+        let missing = self.peer_manager.missing_required_capabilities(&parsed_capabilities);
+        if !missing.is_empty() {
+            warn!(
+                "Disconnecting peer {}: missing required capabilities {:?}",
+                peer, missing
+            );
+            self.peer_manager
+                .adjust_peer_reputation(&peer, -20, "missing required capability")
+                .await;
+            self.swarm.disconnect_peer_id(peer).ok();
+            return Ok(());
+        }
+
+        self.protocol_handler.record_peer_capabilities(peer, &capabilities);
+        if let Some(peer_info) = self.peer_manager.get_peer_mut(&peer) {
+            peer_info.best_block_index = Some(best_block_index);
+            peer_info.capabilities = parsed_capabilities;
+        }
+        let _ = self.event_sender.send(NetworkEvent::PeerStatus { peer, best_block_index });
+
+        let our_tip = self.chain_reader.best_block().await?.map(|b| b.header.index);
+        let behind = match our_tip {
+            Some(tip) => best_block_index > tip,
+            None => true,
+        };
+        if behind {
+            let start_index = our_tip.map(|tip| tip + 1).unwrap_or(0);
+            let count = ((best_block_index - start_index + 1) as u32).min(SYNC_CATCH_UP_BATCH);
+            debug!(
+                "Peer {} is ahead (their tip {}, ours {:?}); requesting {} block(s) from {}",
+                peer, best_block_index, our_tip, count, start_index
+            );
+            self.swarm
+                .behaviour_mut()
+                .block_sync
+                .send_request(&peer, NetworkMessage::BlockRequest { start_index, count });
+        }
+
+        Ok(())
+    }
+
+    /// Handle an event from the block-sync request/response behaviour
+    async fn handle_block_sync_event(
+        &mut self,
+        event: request_response::Event<NetworkMessage, NetworkMessage>,
+    ) -> BeaconResult<()> {
+        match event {
+            request_response::Event::Message { peer, message, .. } => match message {
+                request_response::Message::Request { request, channel, .. } => {
+                    self.handle_block_sync_request(peer, request, channel).await?;
+                }
+                request_response::Message::Response { response, .. } => {
+                    self.handle_block_sync_response(peer, response).await?;
+                }
+            },
+            request_response::Event::OutboundFailure { peer, error, .. } => {
+                warn!("Block-sync request to {} failed: {}", peer, error);
+            }
+            request_response::Event::InboundFailure { peer, error, .. } => {
+                warn!("Block-sync request from {} failed: {}", peer, error);
+            }
+            request_response::Event::ResponseSent { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Pull the requested block range from the local chain store and send it
+    /// back on `channel`, instead of re-broadcasting over gossip. Also
+    /// answers the directed `PeerInfo` handshake sent on connection, replying
+    /// with our own status so the handshake is two-way.
+    async fn handle_block_sync_request(
+        &mut self,
+        peer: PeerId,
+        request: NetworkMessage,
+        channel: request_response::ResponseChannel<NetworkMessage>,
+    ) -> BeaconResult<()> {
+        let cost = request.request_cost(self.peer_manager.flow_control_config());
+        if let Err(e) = self.peer_manager.try_debit(peer, cost) {
+            warn!("Refusing block-sync request from {}: {}", peer, e);
+            return Ok(());
+        }
+
+        match request {
+            NetworkMessage::BlockRequest { start_index, count } => {
+                debug!("Peer {} requested {} block(s) starting at {}", peer, count, start_index);
+                let _ = self
+                    .event_sender
+                    .send(NetworkEvent::SyncRequest { peer, start_index, count });
+
+                let blocks = self.chain_reader.get_blocks_range(start_index, count).await?;
+                let response = NetworkMessage::BlockResponse {
+                    blocks,
+                    request_id: uuid::Uuid::new_v4().to_string(),
+                };
+
+                if self.swarm.behaviour_mut().block_sync.send_response(channel, response).is_err() {
+                    warn!("Failed to send block-sync response to {}: channel already closed", peer);
+                }
+            }
+            NetworkMessage::PeerInfo { network_id, best_block_index, capabilities, .. } => {
+                let best_block_index_response =
+                    self.chain_reader.best_block().await?.map(|b| b.header.index).unwrap_or(0);
+                let response = NetworkMessage::PeerInfo {
+                    version: PROTOCOL_VERSION.to_string(),
+                    network_id: self.network_id.clone(),
+                    best_block_index: best_block_index_response,
+                    peer_count: self.peer_count() as u32,
+                    capabilities: SUPPORTED_CAPABILITIES.iter().map(|s| s.to_string()).collect(),
+                };
+                if self.swarm.behaviour_mut().block_sync.send_response(channel, response).is_err() {
+                    warn!("Failed to send status handshake reply to {}: channel already closed", peer);
+                }
+                self.handle_peer_status(peer, network_id, best_block_index, capabilities).await?;
+            }
+            _ => {
+                warn!("Received unexpected message on the block-sync protocol from {}", peer);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Surface blocks received in reply to our own `RequestBlocks` command,
+    /// or a peer's status in reply to our handshake.
+    async fn handle_block_sync_response(&mut self, peer: PeerId, response: NetworkMessage) -> BeaconResult<()> {
+        match response {
+            NetworkMessage::BlockResponse { blocks, request_id } => {
+                debug!("Received {} block(s) from {} for request {}", blocks.len(), peer, request_id);
+                for block in blocks {
+                    let _ = self.event_sender.send(NetworkEvent::BlockReceived(block, peer));
+                }
+            }
+            NetworkMessage::PeerInfo { network_id, best_block_index, capabilities, .. } => {
+                self.handle_peer_status(peer, network_id, best_block_index, capabilities).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handle gossip messages. After decoding, each `Block`/`Transaction` is
+    /// verified and the verdict reported back to gossipsub via
+    /// `report_message_validation_result` so mesh maintenance and peer
+    /// scoring (`ValidationMode::Strict` + `validate_messages`) can act on
+    /// it: `Reject` for invalid payloads, `Ignore` for messages we don't
+    /// want to penalize but also don't want to re-propagate, `Accept`
+    /// otherwise.
     async fn handle_gossip_message(
         &mut self,
         source: PeerId,
+        message_id: gossipsub::MessageId,
         message: gossipsub::Message,
     ) -> BeaconResult<()> {
-        match self.protocol_handler.decode_message(&message.data) {
-            Ok(NetworkMessage::Block(block)) => {
-                debug!("Received block {} from peer {}", block.header.index, source);
-                let _ = self.event_sender.send(NetworkEvent::BlockReceived(block, source));
-            }
+        let acceptance = match self.protocol_handler.decode_message(&message.data, Some(&source)) {
+            Ok(NetworkMessage::Block(block)) => self.validate_gossip_block(&source, block).await,
             Ok(NetworkMessage::Transaction(transaction)) => {
-                debug!("Received transaction {} from peer {}", transaction.id.as_str(), source);
-                let _ = self
-                    .event_sender
-                    .send(NetworkEvent::TransactionReceived(transaction, source));
+                self.validate_gossip_transaction(&source, transaction).await
+            }
+            Ok(other) => {
+                self.handle_other_gossip_message(source, other).await?;
+                gossipsub::MessageAcceptance::Accept
+            }
+            Err(e) => {
+                warn!("Failed to decode message from peer {}: {}", source, e);
+                gossipsub::MessageAcceptance::Reject
+            }
+        };
+
+        self.swarm
+            .behaviour_mut()
+            .gossipsub
+            .report_message_validation_result(&message_id, &source, acceptance);
+
+        Ok(())
+    }
+
+    /// Verify a gossiped block's structure, signature and position relative
+    /// to our local tip, emitting `BlockReceived` only for blocks we accept.
+    async fn validate_gossip_block(&mut self, source: &PeerId, block: Block) -> gossipsub::MessageAcceptance {
+        // Index the block once: the header hash and merkle root computed here
+        // are reused by `verify` below instead of being recomputed, since the
+        // same block is about to be checked, then forwarded for storage.
+        let indexed = beacon_core::IndexedBlock::new(block);
+        let block = indexed.block();
+
+        if let Err(e) = indexed.verify() {
+            warn!("Rejected block {} from peer {}: {}", block.header.index, source, e);
+            return gossipsub::MessageAcceptance::Reject;
+        }
+
+        let signature_valid = beacon_core::verifying_key_from_hex(&block.header.validator)
+            .map(|key| block.verify_signature(&key))
+            .unwrap_or(false);
+        if !signature_valid {
+            warn!(
+                "Rejected block {} from peer {}: signature verification failed",
+                block.header.index, source
+            );
+            return gossipsub::MessageAcceptance::Reject;
+        }
+
+        match self.chain_reader.best_block().await {
+            Ok(Some(tip)) if block.header.index <= tip.header.index => {
+                debug!(
+                    "Ignoring block {} from peer {}: already at or behind our tip ({})",
+                    block.header.index, source, tip.header.index
+                );
+                return gossipsub::MessageAcceptance::Ignore;
             }
-            Ok(NetworkMessage::Ping) => {
+            Ok(Some(tip))
+                if block.header.index == tip.header.index + 1
+                    && block.header.previous_hash != tip.hash =>
+            {
+                // This doesn't chain from our tip, but it's not necessarily
+                // invalid - it's the one block of a competing fork we'd see
+                // first: a validator building on a different parent than we
+                // did at this height. Forward it rather than rejecting it
+                // outright, so the peer-sync/import path can evaluate it -
+                // and, if its ancestry back to a common ancestor turns out
+                // to already be known locally, reorganize onto it via
+                // `BlockImportPipeline::import_foreign_block`. Rejecting it
+                // here would make same-height fork resolution unreachable,
+                // since this is the only place such a block is seen.
+                debug!(
+                    "Block {} from peer {} doesn't chain from our tip; forwarding as a possible competing fork",
+                    block.header.index, source
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("Failed to read local chain tip while validating block: {}", e);
+            }
+        }
+
+        let block = indexed.into_block();
+        debug!("Received block {} from peer {}", block.header.index, source);
+        let _ = self.event_sender.send(NetworkEvent::BlockReceived(block, *source));
+        gossipsub::MessageAcceptance::Accept
+    }
+
+    /// Verify a gossiped transaction's structure and signature.
+    async fn validate_gossip_transaction(
+        &mut self,
+        source: &PeerId,
+        transaction: Transaction,
+    ) -> gossipsub::MessageAcceptance {
+        if let Err(e) = transaction.validate() {
+            warn!(
+                "Rejected transaction {} from peer {}: {}",
+                transaction.id.as_str(), source, e
+            );
+            return gossipsub::MessageAcceptance::Reject;
+        }
+
+        let signature_valid = match transaction.scheme {
+            beacon_core::SignatureScheme::Ed25519 => beacon_core::verifying_key_from_hex(transaction.from.as_str())
+                .map(|key| transaction.verify_signature(&key))
+                .unwrap_or(false),
+            beacon_core::SignatureScheme::Secp256k1Recoverable => {
+                transaction.verify_secp256k1_self_authenticating()
+            }
+        };
+        if !signature_valid {
+            warn!(
+                "Rejected transaction {} from peer {}: signature verification failed",
+                transaction.id.as_str(), source
+            );
+            return gossipsub::MessageAcceptance::Reject;
+        }
+
+        debug!("Received transaction {} from peer {}", transaction.id.as_str(), source);
+        let _ = self
+            .event_sender
+            .send(NetworkEvent::TransactionReceived(transaction, *source));
+        gossipsub::MessageAcceptance::Accept
+    }
+
+    /// Handle the gossip message kinds that aren't subject to validation
+    /// scoring (control/handshake messages rather than chain data).
+    async fn handle_other_gossip_message(
+        &mut self,
+        source: PeerId,
+        message: NetworkMessage,
+    ) -> BeaconResult<()> {
+        let cost = message.request_cost(self.peer_manager.flow_control_config());
+        if let Err(e) = self.peer_manager.try_debit(source, cost) {
+            warn!("Dropping gossip request from {}: {}", source, e);
+            return Ok(());
+        }
+
+        match message {
+            NetworkMessage::Ping => {
                 debug!("Received ping from peer {}", source);
                 // Respond with pong
                 if let Ok(pong_data) = self.protocol_handler.encode_message(&NetworkMessage::Pong) {
@@ -233,41 +744,45 @@ impl NetworkManager {
                     }
                 }
             }
-            Ok(NetworkMessage::Pong) => {
+            NetworkMessage::Pong => {
                 debug!("Received pong from peer {}", source);
             }
-            Ok(NetworkMessage::BlockRequest { start_index, count }) => {
-                debug!("Received block request from peer {}: start={}, count={}", source, start_index, count);
-                // TODO: Handle block request
+            NetworkMessage::BlockRequest { start_index, count } => {
+                warn!(
+                    "Ignoring block request from peer {} received over gossip (start={}, count={}); use the block-sync protocol instead",
+                    source, start_index, count
+                );
             }
-            Ok(NetworkMessage::BlockResponse { blocks, request_id }) => {
-                debug!("Received block response from peer {}: {} blocks, request_id={}", source, blocks.len(), request_id);
-                // TODO: Handle block response
+            NetworkMessage::BlockResponse { blocks, request_id } => {
+                warn!(
+                    "Ignoring block response from peer {} received over gossip ({} blocks, request_id={})",
+                    source, blocks.len(), request_id
+                );
             }
-            Ok(NetworkMessage::TransactionRequest { tx_id }) => {
+            NetworkMessage::TransactionRequest { tx_id } => {
                 debug!("Received transaction request from peer {}: tx_id={}", source, tx_id);
                 // TODO: Handle transaction request
             }
-            Ok(NetworkMessage::TransactionResponse { transaction, request_id }) => {
+            NetworkMessage::TransactionResponse { transaction, request_id } => {
                 debug!("Received transaction response from peer {}: request_id={}", source, request_id);
                 // TODO: Handle transaction response
             }
-            Ok(NetworkMessage::PeerInfo { version, network_id, best_block_index, peer_count }) => {
-                debug!("Received peer info from peer {}: version={}, network_id={}, best_block_index={}, peer_count={}", 
-                       source, version, network_id, best_block_index, peer_count);
-                // TODO: Handle peer info
+            NetworkMessage::PeerInfo { version, network_id, best_block_index, peer_count, capabilities } => {
+                debug!("Received peer info from peer {}: version={}, network_id={}, best_block_index={}, peer_count={}, capabilities={:?}",
+                       source, version, network_id, best_block_index, peer_count, capabilities);
+                self.handle_peer_status(source, network_id, best_block_index, capabilities).await?;
             }
-            Ok(NetworkMessage::PeerListRequest) => {
+            NetworkMessage::PeerListRequest => {
                 debug!("Received peer list request from peer {}", source);
                 // TODO: Handle peer list request
             }
-            Ok(NetworkMessage::PeerListResponse { peers }) => {
+            NetworkMessage::PeerListResponse { peers } => {
                 debug!("Received peer list response from peer {}: {} peers", source, peers.len());
                 // TODO: Handle peer list response
             }
-            Err(e) => {
-                warn!("Failed to decode message from peer {}: {}", source, e);
-            }
+            NetworkMessage::Block(_) | NetworkMessage::Transaction(_) => unreachable!(
+                "Block/Transaction messages are validated in handle_gossip_message before reaching here"
+            ),
         }
         Ok(())
     }
@@ -316,24 +831,44 @@ impl NetworkManager {
             NetworkCommand::DisconnectPeer(peer_id) => {
                 debug!("Disconnecting from peer {}", peer_id);
                 self.swarm.disconnect_peer_id(peer_id).ok();
-                self.peers.remove(&peer_id);
+                self.peer_manager.remove_peer(&peer_id);
             }
             NetworkCommand::GetPeers(sender) => {
-                let peers: Vec<PeerInfo> = self.peers.values().cloned().collect();
+                let peers: Vec<PeerInfo> = self
+                    .peer_manager
+                    .get_connected_peers()
+                    .into_iter()
+                    .cloned()
+                    .collect();
                 let _ = sender.send(peers);
             }
+            NetworkCommand::RequestBlocks { peer, start_index, count } => {
+                debug!("Requesting {} block(s) starting at {} from peer {}", count, start_index, peer);
+                self.swarm
+                    .behaviour_mut()
+                    .block_sync
+                    .send_request(&peer, NetworkMessage::BlockRequest { start_index, count });
+            }
+            NetworkCommand::AddReservedPeer(peer_id) => {
+                debug!("Reserving peer {}", peer_id);
+                self.peer_manager.add_reserved_peer(peer_id);
+            }
+            NetworkCommand::RemoveReservedPeer(peer_id) => {
+                debug!("Unreserving peer {}", peer_id);
+                self.peer_manager.remove_reserved_peer(&peer_id);
+            }
         }
         Ok(())
     }
 
     /// Get the number of connected peers
     pub fn peer_count(&self) -> usize {
-        self.peers.len()
+        self.peer_manager.connected_peer_count()
     }
 
     /// Check if a peer is connected
     pub fn is_peer_connected(&self, peer_id: &PeerId) -> bool {
-        self.peers.contains_key(peer_id)
+        self.peer_manager.get_peer(peer_id).is_some()
     }
 }
 
@@ -345,6 +880,7 @@ pub struct BeaconBehaviour {
     pub identify: identify::Behaviour,
     pub ping: ping::Behaviour,
     pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
+    pub block_sync: request_response::Behaviour<BlockSyncCodec>,
 }
 
 impl BeaconBehaviour {
@@ -355,10 +891,15 @@ impl BeaconBehaviour {
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let local_peer_id = PeerId::from(keypair.public());
 
-        // Configure Gossipsub
+        // Configure Gossipsub. `validate_messages` puts gossipsub into manual
+        // validation mode so it waits for our `report_message_validation_result`
+        // verdict (see `NetworkManager::handle_gossip_message`) before
+        // forwarding or penalizing a message, which is what makes
+        // `ValidationMode::Strict` and peer scoring below actually bite.
         let gossipsub_config = gossipsub::ConfigBuilder::default()
             .heartbeat_interval(std::time::Duration::from_secs(10))
             .validation_mode(gossipsub::ValidationMode::Strict)
+            .validate_messages()
             .build()
             .expect("Valid config");
 
@@ -367,6 +908,16 @@ impl BeaconBehaviour {
             gossipsub_config,
         )?;
 
+        // Misbehaving peers (those we `Reject` messages from) are throttled
+        // by score and eventually pruned from the mesh instead of only being
+        // dropped from this one exchange.
+        gossipsub
+            .with_peer_score(
+                gossipsub::PeerScoreParams::default(),
+                gossipsub::PeerScoreThresholds::default(),
+            )
+            .map_err(|e| format!("Failed to configure gossipsub peer scoring: {}", e))?;
+
         // Subscribe to topics
         gossipsub.subscribe(&gossipsub::IdentTopic::new("beacon-blocks"))?;
         gossipsub.subscribe(&gossipsub::IdentTopic::new("beacon-transactions"))?;
@@ -389,12 +940,21 @@ impl BeaconBehaviour {
         let store = kad::store::MemoryStore::new(local_peer_id);
         let kademlia = kad::Behaviour::new(local_peer_id, store);
 
+        // Configure the block-sync request/response protocol: directed,
+        // reliable fetches for ranges of blocks, separate from gossipsub's
+        // best-effort broadcast announcements.
+        let block_sync = request_response::Behaviour::new(
+            [(StreamProtocol::new(BLOCK_SYNC_PROTOCOL), request_response::ProtocolSupport::Full)],
+            request_response::Config::default(),
+        );
+
         Ok(Self {
             gossipsub,
             mdns,
             identify,
             ping,
             kademlia,
+            block_sync,
         })
     }
 }
@@ -1,13 +1,87 @@
 use libp2p::{Multiaddr, PeerId};
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use beacon_core::{BeaconError, BeaconResult};
+use crate::{Capability, ConnectionFilter, ConnectionFilterConfig, FilterUpdateFlag};
+
+/// Serializes a `PeerId` via its byte form (`to_bytes`/`from_bytes`), since
+/// `PeerId` itself has no `serde` impl. `pub(crate)` so `discovery.rs` can
+/// reuse it for `DiscoveredPeer` instead of duplicating it.
+pub(crate) mod serde_peer_id {
+    use libp2p::PeerId;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(peer_id: &PeerId, serializer: S) -> Result<S::Ok, S::Error> {
+        peer_id.to_bytes().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PeerId, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        PeerId::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes a `Vec<Multiaddr>` via each address's byte form (`to_vec`/
+/// `try_from`), since `Multiaddr` itself has no `serde` impl. `pub(crate)` so
+/// `discovery.rs` can reuse it for `DiscoveredPeer` instead of duplicating it.
+pub(crate) mod serde_multiaddrs {
+    use libp2p::Multiaddr;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(addrs: &[Multiaddr], serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes: Vec<Vec<u8>> = addrs.iter().map(|addr| addr.to_vec()).collect();
+        bytes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Multiaddr>, D::Error> {
+        let bytes = Vec::<Vec<u8>>::deserialize(deserializer)?;
+        bytes
+            .into_iter()
+            .map(|b| Multiaddr::try_from(b).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+/// Durable peer persistence, backing `PeerManager`'s in-memory maps so the
+/// network doesn't have to be rediscovered from scratch on every restart.
+/// Mirrors `ChainReader`'s role: `beacon-networking` depends on this trait
+/// instead of directly on `beacon-storage`, and a concrete implementation
+/// (writing through `Database`/`Keys` into a dedicated column family) is
+/// supplied by whoever wires `PeerManager` up to real storage.
+#[async_trait::async_trait]
+pub trait PeerStore: Send + Sync {
+    /// Persist (insert or overwrite) a peer record.
+    async fn save_peer(&self, peer: &PeerInfo) -> BeaconResult<()>;
+    /// Remove a peer record.
+    async fn remove_peer(&self, peer_id: &PeerId) -> BeaconResult<()>;
+    /// Persist (insert or overwrite) a ban's expiry timestamp.
+    async fn save_ban(&self, peer_id: &PeerId, ban_expiry: u64) -> BeaconResult<()>;
+    /// Remove a ban record.
+    async fn remove_ban(&self, peer_id: &PeerId) -> BeaconResult<()>;
+    /// Load every persisted peer and ban record, for `PeerManager::with_store` to
+    /// rebuild its in-memory maps from on startup.
+    async fn load_all(&self) -> BeaconResult<(Vec<PeerInfo>, HashMap<PeerId, u64>)>;
+}
+
+/// Outbound connections may exceed `PeerManagerConfig.max_peers` by this many
+/// extra slots: we chose to dial these ourselves (sync, discovery), so
+/// they're worth a little extra budget over unsolicited inbound connections.
+const OUTBOUND_OVERFLOW: usize = 8;
+
+/// Extra connection headroom reserved peers get on top of the regular
+/// inbound/outbound caps, so pinning peers doesn't itself force evictions.
+const RESERVED_MARGIN: usize = 4;
 
 /// Information about a network peer
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerInfo {
     /// Peer identifier
+    #[serde(with = "serde_peer_id")]
     pub peer_id: PeerId,
     /// Known addresses for this peer
+    #[serde(with = "serde_multiaddrs")]
     pub addresses: Vec<Multiaddr>,
     /// Last time we connected to this peer
     pub last_seen: u64,
@@ -21,6 +95,35 @@ pub struct PeerInfo {
     pub latency: Option<u64>,
     /// Reputation score (0-100)
     pub reputation: u8,
+    /// The peer's chain tip index as of its last `PeerInfo` handshake
+    pub best_block_index: Option<u64>,
+    /// Capabilities the peer has reported directly, in its last `PeerInfo`
+    /// handshake - authoritative over `gossiped_capabilities` when both are
+    /// known, since it comes straight from the peer rather than a third
+    /// party's say-so.
+    pub capabilities: Vec<Capability>,
+    /// Capabilities reported about this peer by a third party (e.g. during
+    /// peer exchange) before we've received a direct handshake from it
+    /// ourselves. Superseded by `capabilities` once one arrives - see
+    /// `effective_capabilities`.
+    #[serde(default)]
+    pub gossiped_capabilities: Vec<Capability>,
+    /// Cumulative seconds this peer has spent in `PeerStatus::Connected`
+    /// across its whole history, tracked by `set_status` - see
+    /// `PeerManager::get_reliable_peers`.
+    pub connected_time_secs: u64,
+    /// Unix timestamp this peer most recently transitioned into
+    /// `PeerStatus::Connected`, used by `set_status` to add the elapsed
+    /// session to `connected_time_secs` when it disconnects. `None` while
+    /// disconnected.
+    #[serde(default)]
+    pub connected_since: Option<u64>,
+    /// This peer's installed BIP37-style relay filter, if it has requested
+    /// one via `load_filter`. Not persisted - a reconnecting peer is
+    /// expected to reinstall its filter rather than have it survive a
+    /// restart, same as real bloom-filter peers do.
+    #[serde(skip)]
+    pub filter: Option<ConnectionFilter>,
 }
 
 /// Peer connection status
@@ -39,21 +142,74 @@ pub enum PeerStatus {
 impl PeerInfo {
     /// Create new peer info
     pub fn new(peer_id: PeerId, addresses: Vec<Multiaddr>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
         Self {
             peer_id,
             addresses,
-            last_seen: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            last_seen: now,
             status: PeerStatus::Connected,
             protocol_version: None,
             user_agent: None,
             latency: None,
             reputation: 50, // Start with neutral reputation
+            best_block_index: None,
+            capabilities: Vec::new(),
+            gossiped_capabilities: Vec::new(),
+            connected_time_secs: 0,
+            connected_since: Some(now),
+            filter: None,
+        }
+    }
+
+    /// Install a fresh relay filter for this peer - BIP37's `filterload`.
+    pub fn load_filter(
+        &mut self,
+        size_bytes: usize,
+        num_hash_functions: u32,
+        tweak: u32,
+        update_flag: FilterUpdateFlag,
+        config: ConnectionFilterConfig,
+    ) -> BeaconResult<()> {
+        let mut filter = ConnectionFilter::new(config);
+        filter.load_filter(size_bytes, num_hash_functions, tweak, update_flag)?;
+        self.filter = Some(filter);
+        Ok(())
+    }
+
+    /// Add one more item of interest to this peer's installed filter -
+    /// BIP37's `filteradd`. A no-op if no filter is loaded.
+    pub fn add_to_filter(&mut self, data: &[u8]) {
+        if let Some(filter) = &mut self.filter {
+            filter.add_to_filter(data);
         }
     }
 
+    /// Remove this peer's installed filter - BIP37's `filterclear`.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    /// The capability set to trust for this peer: `capabilities` (reported
+    /// directly) if we have any, else `gossiped_capabilities` as a fallback
+    /// for a peer we've only heard about indirectly so far.
+    pub fn effective_capabilities(&self) -> &[Capability] {
+        if !self.capabilities.is_empty() {
+            &self.capabilities
+        } else {
+            &self.gossiped_capabilities
+        }
+    }
+
+    /// Whether this peer supports `capability`, preferring what it reported
+    /// directly over anything only learned via gossip - see
+    /// `effective_capabilities`.
+    pub fn supports(&self, capability: &Capability) -> bool {
+        self.effective_capabilities().contains(capability)
+    }
+
     /// Update the last seen timestamp
     pub fn update_last_seen(&mut self) {
         self.last_seen = SystemTime::now()
@@ -62,12 +218,25 @@ impl PeerInfo {
             .as_secs();
     }
 
-    /// Set the connection status
+    /// Set the connection status. Transitioning into `Connected` starts
+    /// timing a new session; transitioning out of it folds the elapsed
+    /// session into `connected_time_secs` - see `PeerManager::get_reliable_peers`.
     pub fn set_status(&mut self, status: PeerStatus) {
-        self.status = status;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
         if status == PeerStatus::Connected {
+            if self.status != PeerStatus::Connected {
+                self.connected_since = Some(now);
+            }
             self.update_last_seen();
+        } else if let Some(connected_since) = self.connected_since.take() {
+            self.connected_time_secs += now.saturating_sub(connected_since);
         }
+
+        self.status = status;
     }
 
     /// Add a new address if not already known
@@ -133,6 +302,34 @@ pub struct PeerManagerConfig {
     pub min_reputation: u8,
     /// Ban duration for misbehaving peers
     pub ban_duration: Duration,
+    /// Per-peer request-credit flow control
+    pub flow_control: FlowControlConfig,
+    /// Minimum cumulative connected time (`PeerInfo::connected_time_secs`) a
+    /// good-reputation peer needs to count as "reliable" - see
+    /// `PeerManager::get_reliable_peers`.
+    pub reliable_min_connected_secs: u64,
+    /// Whether to automatically redial `get_reliable_peers()` on startup,
+    /// once `PeerManager::with_store` has loaded them back from a `PeerStore`. On
+    /// by default, since a reliable peer was worth reconnecting to before
+    /// the restart and almost always still is.
+    pub auto_reconnect_reliable_peers: bool,
+    /// Capabilities a peer's `PeerInfo` handshake must report every one of
+    /// to stay connected; a peer missing any of these is disconnected with
+    /// a reputation penalty - see `PeerManager::missing_required_capabilities`.
+    /// Empty by default, so no capability is mandatory unless configured.
+    pub min_required_capabilities: Vec<Capability>,
+    /// How often `MessagingService::run_keepalive` pings each connected peer.
+    pub keepalive_interval: Duration,
+    /// How long to wait for a pong before counting a ping as missed.
+    pub keepalive_timeout: Duration,
+    /// Consecutive missed pings before a peer is disconnected and docked
+    /// reputation - see `MessagingService::run_keepalive`.
+    pub keepalive_max_missed: u32,
+    /// Soft cap on connected peers; once `connected_peer_count()` exceeds
+    /// this, `MessagingService::consolidate_peers` drops the
+    /// highest-latency / lowest-reputation peers first, ahead of `max_peers`
+    /// forcing an eviction.
+    pub soft_peer_target: usize,
 }
 
 impl Default for PeerManagerConfig {
@@ -143,6 +340,68 @@ impl Default for PeerManagerConfig {
             peer_retention_time: Duration::from_secs(24 * 60 * 60), // 24 hours
             min_reputation: 30,
             ban_duration: Duration::from_secs(60 * 60), // 1 hour
+            flow_control: FlowControlConfig::default(),
+            reliable_min_connected_secs: 60 * 60, // 1 hour
+            auto_reconnect_reliable_peers: true,
+            min_required_capabilities: Vec::new(),
+            keepalive_interval: Duration::from_secs(30),
+            keepalive_timeout: Duration::from_secs(10),
+            keepalive_max_missed: 3,
+            soft_peer_target: 40,
+        }
+    }
+}
+
+/// Configuration for the per-peer request-credit flow control scheme: every
+/// peer gets a replenishing credit balance, requests are charged against it
+/// (scaled by how expensive they are to serve), and a peer that has burned
+/// through its balance gets its requests refused until credits recharge.
+#[derive(Debug, Clone)]
+pub struct FlowControlConfig {
+    /// Credits charged for a cheap request (ping, peer info, peer list)
+    pub base_cost: u64,
+    /// Additional credits charged per block for a `RequestBlocks(start, count)`
+    pub per_block_cost: u64,
+    /// Credits replenished per second, up to `max_credits`
+    pub recharge_rate: u64,
+    /// Credit balance cap
+    pub max_credits: u64,
+}
+
+impl Default for FlowControlConfig {
+    fn default() -> Self {
+        Self {
+            base_cost: 1,
+            per_block_cost: 2,
+            recharge_rate: 10,
+            max_credits: 200,
+        }
+    }
+}
+
+/// A peer's replenishing request-credit balance
+#[derive(Debug)]
+struct Credits {
+    current: u64,
+    last_recharge: Instant,
+}
+
+impl Credits {
+    fn new(starting_balance: u64) -> Self {
+        Self {
+            current: starting_balance,
+            last_recharge: Instant::now(),
+        }
+    }
+
+    /// Top up the balance for time elapsed since the last recharge, capped at
+    /// `max_credits`.
+    fn recharge(&mut self, config: &FlowControlConfig) {
+        let elapsed = self.last_recharge.elapsed().as_secs_f64();
+        let replenished = (elapsed * config.recharge_rate as f64) as u64;
+        if replenished > 0 {
+            self.current = (self.current + replenished).min(config.max_credits);
+            self.last_recharge = Instant::now();
         }
     }
 }
@@ -151,28 +410,226 @@ impl Default for PeerManagerConfig {
 pub struct PeerManager {
     peers: std::collections::HashMap<PeerId, PeerInfo>,
     banned_peers: std::collections::HashMap<PeerId, u64>, // PeerId -> ban expiry timestamp
+    /// Peers that are never evicted, e.g. trusted relays or operator-pinned
+    /// nodes. They still count toward the connection caps, which they widen
+    /// by up to `RESERVED_MARGIN` - see `is_over_capacity`.
+    reserved_peers: HashSet<PeerId>,
+    /// Currently connected peers we did not dial ourselves.
+    inbound_peers: HashSet<PeerId>,
+    /// Currently connected peers we dialed ourselves.
+    outbound_peers: HashSet<PeerId>,
+    /// Per-peer request-credit balance for flow control
+    credits: std::collections::HashMap<PeerId, Credits>,
+    /// Requests refused because the peer was over its credit limit
+    requests_rejected_over_limit: u64,
     config: PeerManagerConfig,
+    /// Durable backing store, if persistence is enabled - see `PeerStore`.
+    store: Option<Arc<dyn PeerStore>>,
 }
 
 impl PeerManager {
-    /// Create a new peer manager
+    /// Create a new peer manager with no persistence: `peers`/`banned_peers`
+    /// start empty and nothing is written back on mutation. See
+    /// `PeerManager::with_store` for a persistent one.
     pub fn new(config: PeerManagerConfig) -> Self {
         Self {
             peers: std::collections::HashMap::new(),
             banned_peers: std::collections::HashMap::new(),
+            reserved_peers: HashSet::new(),
+            inbound_peers: HashSet::new(),
+            outbound_peers: HashSet::new(),
+            credits: std::collections::HashMap::new(),
+            requests_rejected_over_limit: 0,
+            config,
+            store: None,
+        }
+    }
+
+    /// Create a new peer manager backed by `store`: loads every persisted
+    /// peer and ban record back into the in-memory maps, pruning expired
+    /// bans and stale peers with the same retention/ban-expiry logic
+    /// `cleanup_old_peers` applies at runtime, then flushes the prune back
+    /// to `store` so it doesn't keep reloading what it just discarded.
+    pub async fn with_store(config: PeerManagerConfig, store: Arc<dyn PeerStore>) -> BeaconResult<Self> {
+        let (loaded_peers, loaded_bans) = store.load_all().await?;
+
+        let mut manager = Self {
+            peers: loaded_peers.into_iter().map(|peer| (peer.peer_id, peer)).collect(),
+            banned_peers: loaded_bans,
+            reserved_peers: HashSet::new(),
+            inbound_peers: HashSet::new(),
+            outbound_peers: HashSet::new(),
+            credits: std::collections::HashMap::new(),
+            requests_rejected_over_limit: 0,
             config,
+            store: Some(store),
+        };
+
+        manager.cleanup_old_peers().await;
+        Ok(manager)
+    }
+
+    /// Peers worth automatically redialing on startup: good-reputation
+    /// peers with at least `PeerManagerConfig::reliable_min_connected_secs`
+    /// of cumulative connected time. Intended for
+    /// `PeerManagerConfig::auto_reconnect_reliable_peers`.
+    pub fn get_reliable_peers(&self) -> Vec<&PeerInfo> {
+        self.peers
+            .values()
+            .filter(|peer| {
+                peer.has_good_reputation()
+                    && peer.connected_time_secs >= self.config.reliable_min_connected_secs
+            })
+            .collect()
+    }
+
+    /// The flow-control tunables in effect, for computing a message's
+    /// `request_cost` before calling `try_debit`.
+    pub fn flow_control_config(&self) -> &FlowControlConfig {
+        &self.config.flow_control
+    }
+
+    /// Whether `NetworkManager::run` should redial `get_reliable_peers()` on
+    /// startup - see `PeerManagerConfig::auto_reconnect_reliable_peers`.
+    pub fn auto_reconnect_reliable_peers(&self) -> bool {
+        self.config.auto_reconnect_reliable_peers
+    }
+
+    /// How often `MessagingService::run_keepalive` should ping each
+    /// connected peer.
+    pub fn keepalive_interval(&self) -> Duration {
+        self.config.keepalive_interval
+    }
+
+    /// How long `MessagingService::run_keepalive` should wait for a pong
+    /// before counting a ping as missed.
+    pub fn keepalive_timeout(&self) -> Duration {
+        self.config.keepalive_timeout
+    }
+
+    /// Consecutive missed pings before `MessagingService::run_keepalive`
+    /// disconnects a peer.
+    pub fn keepalive_max_missed(&self) -> u32 {
+        self.config.keepalive_max_missed
+    }
+
+    /// Soft cap on connected peers for `MessagingService::consolidate_peers`.
+    pub fn soft_peer_target(&self) -> usize {
+        self.config.soft_peer_target
+    }
+
+    /// Charge `cost` credits against `peer_id`'s balance, recharging it for
+    /// elapsed time first. A `cost` of zero is always free and never touches
+    /// the peer's balance. Returns an error without charging anything if the
+    /// peer doesn't have enough credits; the caller can use that to refuse or
+    /// defer the request.
+    pub fn try_debit(&mut self, peer_id: PeerId, cost: u64) -> BeaconResult<()> {
+        if cost == 0 {
+            return Ok(());
+        }
+
+        let config = self.config.flow_control.clone();
+        let credits = self
+            .credits
+            .entry(peer_id)
+            .or_insert_with(|| Credits::new(config.max_credits));
+        credits.recharge(&config);
+
+        if credits.current < cost {
+            self.requests_rejected_over_limit += 1;
+            return Err(BeaconError::network(format!(
+                "peer {} is over its request-credit limit ({} available, {} required)",
+                peer_id, credits.current, cost
+            )));
         }
+
+        credits.current -= cost;
+        Ok(())
+    }
+
+    /// Number of requests refused so far for exceeding a peer's credit limit
+    pub fn requests_rejected_over_limit(&self) -> u64 {
+        self.requests_rejected_over_limit
+    }
+
+    /// Extra connection headroom granted by reserved peers, capped at
+    /// `RESERVED_MARGIN` so a long reserved list can't unbound the caps.
+    fn reserved_headroom(&self) -> usize {
+        self.reserved_peers.len().min(RESERVED_MARGIN)
+    }
+
+    /// Pin a peer so it's never evicted. It still counts toward the
+    /// connection caps, which reserved peers widen by up to `RESERVED_MARGIN`.
+    pub fn add_reserved_peer(&mut self, peer_id: PeerId) {
+        self.reserved_peers.insert(peer_id);
+    }
+
+    /// Unpin a previously reserved peer.
+    pub fn remove_reserved_peer(&mut self, peer_id: &PeerId) {
+        self.reserved_peers.remove(peer_id);
     }
 
-    /// Add or update a peer
-    pub fn add_peer(&mut self, mut peer_info: PeerInfo) {
+    /// Check whether a peer is reserved.
+    pub fn is_reserved(&self, peer_id: &PeerId) -> bool {
+        self.reserved_peers.contains(peer_id)
+    }
+
+    /// Record the direction of a newly established connection, so capacity
+    /// checks can apply the right cap.
+    pub fn record_connection_direction(&mut self, peer_id: PeerId, inbound: bool) {
+        if inbound {
+            self.outbound_peers.remove(&peer_id);
+            self.inbound_peers.insert(peer_id);
+        } else {
+            self.inbound_peers.remove(&peer_id);
+            self.outbound_peers.insert(peer_id);
+        }
+    }
+
+    /// Forget the recorded direction of a connection once it closes.
+    pub fn clear_connection_direction(&mut self, peer_id: &PeerId) {
+        self.inbound_peers.remove(peer_id);
+        self.outbound_peers.remove(peer_id);
+    }
+
+    /// Whether accepting one more connection of this direction would exceed
+    /// `PeerManagerConfig.max_peers`. Outbound connections get `OUTBOUND_OVERFLOW`
+    /// extra slots since we chose to make them ourselves; reserved peers
+    /// widen both caps by up to `RESERVED_MARGIN`.
+    pub fn is_over_capacity(&self, inbound: bool) -> bool {
+        let headroom = self.reserved_headroom();
+        if inbound {
+            self.inbound_peers.len() >= self.config.max_peers + headroom
+        } else {
+            self.outbound_peers.len() >= self.config.max_peers + OUTBOUND_OVERFLOW + headroom
+        }
+    }
+
+    /// The connected, non-reserved peer with the lowest reputation, i.e. the
+    /// best candidate to evict when a new connection needs room.
+    pub fn lowest_scored_peer(&self) -> Option<PeerId> {
+        self.peers
+            .values()
+            .filter(|peer| peer.status == PeerStatus::Connected && !self.is_reserved(&peer.peer_id))
+            .min_by_key(|peer| peer.reputation)
+            .map(|peer| peer.peer_id)
+    }
+
+    /// Add or update a peer, flushing it to `store` if persistence is enabled.
+    pub async fn add_peer(&mut self, mut peer_info: PeerInfo) {
         // Check if peer is banned
         if self.is_peer_banned(&peer_info.peer_id) {
             peer_info.set_status(PeerStatus::Banned);
         }
 
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_peer(&peer_info).await {
+                tracing::warn!("Failed to persist peer {}: {}", peer_info.peer_id, e);
+            }
+        }
+
         self.peers.insert(peer_info.peer_id, peer_info);
-        self.cleanup_old_peers();
+        self.cleanup_old_peers().await;
     }
 
     /// Get peer information
@@ -188,6 +645,7 @@ impl PeerManager {
     /// Remove a peer
     pub fn remove_peer(&mut self, peer_id: &PeerId) {
         self.peers.remove(peer_id);
+        self.clear_connection_direction(peer_id);
     }
 
     /// Get all connected peers
@@ -206,21 +664,68 @@ impl PeerManager {
             .collect()
     }
 
-    /// Ban a peer
-    pub fn ban_peer(&mut self, peer_id: &PeerId, reason: &str) {
+    /// Connected peers that support `capability` (see `PeerInfo::supports`).
+    /// Use to pick eligible peers before sending a feature-specific message,
+    /// e.g. only peers that understand an optional wire extension.
+    pub fn peers_with_capability(&self, capability: &Capability) -> Vec<&PeerInfo> {
+        self.peers
+            .values()
+            .filter(|peer| peer.status == PeerStatus::Connected && peer.supports(capability))
+            .collect()
+    }
+
+    /// The capabilities `PeerManagerConfig::min_required_capabilities` names
+    /// that aren't present in `capabilities`, i.e. ones worth dropping the
+    /// peer over. Empty means `capabilities` satisfies every mandatory
+    /// capability - including the common case of nothing being mandatory.
+    pub fn missing_required_capabilities(&self, capabilities: &[Capability]) -> Vec<Capability> {
+        self.config
+            .min_required_capabilities
+            .iter()
+            .filter(|required| !capabilities.contains(required))
+            .cloned()
+            .collect()
+    }
+
+    /// Record capabilities reported about `peer_id` by a third party (e.g.
+    /// during peer exchange) rather than learned from the peer directly.
+    /// A no-op if we don't know about `peer_id` yet. There's no wire message
+    /// carrying third-party capability reports today - `PeerListResponse`
+    /// only exchanges addresses - so this is a plumbing point for callers
+    /// that learn capabilities some other way, ahead of a direct handshake.
+    pub fn record_gossiped_capabilities(&mut self, peer_id: &PeerId, capabilities: Vec<Capability>) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.gossiped_capabilities = capabilities;
+        }
+    }
+
+    /// Ban a peer, flushing the ban (and updated peer record, if known) to
+    /// `store` if persistence is enabled.
+    pub async fn ban_peer(&mut self, peer_id: &PeerId, reason: &str) {
         let ban_expiry = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs()
             + self.config.ban_duration.as_secs();
-        
+
         self.banned_peers.insert(*peer_id, ban_expiry);
-        
+
         if let Some(peer) = self.peers.get_mut(peer_id) {
             peer.set_status(PeerStatus::Banned);
             peer.reputation = 0;
         }
-        
+
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_ban(peer_id, ban_expiry).await {
+                tracing::warn!("Failed to persist ban for peer {}: {}", peer_id, e);
+            }
+            if let Some(peer) = self.peers.get(peer_id) {
+                if let Err(e) = store.save_peer(peer).await {
+                    tracing::warn!("Failed to persist peer {}: {}", peer_id, e);
+                }
+            }
+        }
+
         tracing::info!("Banned peer {} for reason: {}", peer_id, reason);
     }
 
@@ -242,12 +747,13 @@ impl PeerManager {
         false
     }
 
-    /// Adjust peer reputation
-    pub fn adjust_peer_reputation(&mut self, peer_id: &PeerId, delta: i16, reason: &str) {
-        if let Some(peer) = self.peers.get_mut(peer_id) {
+    /// Adjust peer reputation, flushing the updated record to `store` if
+    /// persistence is enabled.
+    pub async fn adjust_peer_reputation(&mut self, peer_id: &PeerId, delta: i16, reason: &str) {
+        let should_ban = if let Some(peer) = self.peers.get_mut(peer_id) {
             let old_reputation = peer.reputation;
             peer.adjust_reputation(delta);
-            
+
             tracing::debug!(
                 "Adjusted reputation for peer {} from {} to {} (delta: {}, reason: {})",
                 peer_id,
@@ -256,11 +762,21 @@ impl PeerManager {
                 delta,
                 reason
             );
-            
-            // Ban peer if reputation is too low
-            if peer.should_be_banned() && !self.is_peer_banned(peer_id) {
-                self.ban_peer(peer_id, "Low reputation");
+
+            if let Some(store) = &self.store {
+                if let Err(e) = store.save_peer(peer).await {
+                    tracing::warn!("Failed to persist peer {}: {}", peer_id, e);
+                }
             }
+
+            peer.should_be_banned() && !self.is_peer_banned(peer_id)
+        } else {
+            false
+        };
+
+        // Ban peer if reputation is too low
+        if should_ban {
+            self.ban_peer(peer_id, "Low reputation").await;
         }
     }
 
@@ -277,21 +793,29 @@ impl PeerManager {
         self.connected_peer_count() < self.config.max_peers
     }
 
-    /// Clean up old and banned peers
-    pub fn cleanup_old_peers(&mut self) {
+    /// Clean up old and banned peers, flushing every removal to `store` if
+    /// persistence is enabled.
+    pub async fn cleanup_old_peers(&mut self) {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
-        
+
         // Remove expired bans
+        let expired_bans: Vec<PeerId> = self
+            .banned_peers
+            .iter()
+            .filter(|(_, &ban_expiry)| now >= ban_expiry)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
         self.banned_peers.retain(|_, &mut ban_expiry| now < ban_expiry);
-        
+
         // Remove old peers if we have too many stored
+        let mut peers_to_remove = Vec::new();
         if self.peers.len() > self.config.max_stored_peers {
             let retention_threshold = now - self.config.peer_retention_time.as_secs();
-            
-            let peers_to_remove: Vec<PeerId> = self
+
+            peers_to_remove = self
                 .peers
                 .iter()
                 .filter(|(_, peer)| {
@@ -299,9 +823,22 @@ impl PeerManager {
                 })
                 .map(|(peer_id, _)| *peer_id)
                 .collect();
-            
-            for peer_id in peers_to_remove {
-                self.peers.remove(&peer_id);
+
+            for peer_id in &peers_to_remove {
+                self.peers.remove(peer_id);
+            }
+        }
+
+        if let Some(store) = &self.store {
+            for peer_id in &expired_bans {
+                if let Err(e) = store.remove_ban(peer_id).await {
+                    tracing::warn!("Failed to remove expired ban for peer {}: {}", peer_id, e);
+                }
+            }
+            for peer_id in &peers_to_remove {
+                if let Err(e) = store.remove_peer(peer_id).await {
+                    tracing::warn!("Failed to remove stale peer {}: {}", peer_id, e);
+                }
             }
         }
     }
@@ -322,6 +859,7 @@ impl PeerManager {
             total,
             banned,
             good_reputation,
+            requests_rejected_over_limit: self.requests_rejected_over_limit,
         }
     }
 }
@@ -333,4 +871,5 @@ pub struct PeerStats {
     pub total: usize,
     pub banned: usize,
     pub good_reputation: usize,
+    pub requests_rejected_over_limit: u64,
 }
@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
 use ed25519_dalek::{Signer, Verifier};
 use beacon_core::{BeaconError, BeaconResult, Block, Transaction};
+use async_trait::async_trait;
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::request_response;
+use sha2::{Sha256, Digest};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::time::{Duration, Instant};
 
 /// Network protocol version
 pub const PROTOCOL_VERSION: &str = "1.0.0";
@@ -8,6 +15,91 @@ pub const PROTOCOL_VERSION: &str = "1.0.0";
 /// Maximum message size (1MB)
 pub const MAX_MESSAGE_SIZE: usize = 1_048_576;
 
+/// Capability identifiers a peer advertises in its `PeerInfo` handshake,
+/// naming optional protocol extensions layered on top of the base wire
+/// format. Lets a node roll out a new feature (compression, gossip dedup,
+/// authenticated mode) without a hard flag-day upgrade: peers that don't
+/// advertise a capability are assumed not to understand it and are served
+/// the plain fallback instead.
+pub mod capability {
+    /// Peer decodes snappy-compressed (`ContentEncoding::Snappy`) wire frames
+    pub const SNAPPY: &str = "snappy";
+    /// Peer runs topic-based gossip dedup and validation-verdict reporting
+    pub const GOSSIP_DEDUP: &str = "gossip-dedup";
+    /// Peer can verify a signed, anti-replay-guarded `ProtocolMessage`
+    pub const AUTHENTICATED: &str = "authenticated";
+    /// Peer understands BIP37-style `ConnectionFilter` relay filtering
+    /// (`filterload`/`filteradd`/`filterclear`) - see `crate::filter`.
+    pub const BLOOM_FILTER: &str = "bloom-filter";
+}
+
+/// The capabilities this build of `ProtocolHandler` supports, advertised in
+/// every outgoing `PeerInfo` handshake.
+pub const SUPPORTED_CAPABILITIES: &[&str] = &[
+    capability::SNAPPY,
+    capability::GOSSIP_DEDUP,
+    capability::AUTHENTICATED,
+    capability::BLOOM_FILTER,
+];
+
+/// Structured view of a capability identifier, for code that wants to match
+/// on a specific capability rather than compare raw strings (see
+/// `PeerInfo::capabilities`/`PeerManager::peers_with_capability`). `Other`
+/// preserves a capability string this build doesn't recognize, so an older
+/// node doesn't silently drop what a newer peer advertised.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    Snappy,
+    GossipDedup,
+    Authenticated,
+    BloomFilter,
+    Other(String),
+}
+
+impl Capability {
+    /// Parse a wire capability identifier (one of the `capability` module's
+    /// constants, or an identifier this build doesn't recognize).
+    pub fn parse(id: &str) -> Self {
+        match id {
+            capability::SNAPPY => Capability::Snappy,
+            capability::GOSSIP_DEDUP => Capability::GossipDedup,
+            capability::AUTHENTICATED => Capability::Authenticated,
+            capability::BLOOM_FILTER => Capability::BloomFilter,
+            other => Capability::Other(other.to_string()),
+        }
+    }
+
+    /// The wire identifier this capability is advertised/matched under.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Capability::Snappy => capability::SNAPPY,
+            Capability::GossipDedup => capability::GOSSIP_DEDUP,
+            Capability::Authenticated => capability::AUTHENTICATED,
+            Capability::BloomFilter => capability::BLOOM_FILTER,
+            Capability::Other(id) => id,
+        }
+    }
+}
+
+/// How a wire frame's bincode payload is encoded, written as a single-byte
+/// prefix ahead of it so the decoder knows whether to decompress before
+/// deserializing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentEncoding {
+    Identity = 0,
+    Snappy = 1,
+}
+
+impl ContentEncoding {
+    fn from_byte(byte: u8) -> BeaconResult<Self> {
+        match byte {
+            0 => Ok(ContentEncoding::Identity),
+            1 => Ok(ContentEncoding::Snappy),
+            other => Err(BeaconError::network(format!("Unknown content encoding byte: {}", other))),
+        }
+    }
+}
+
 /// Network message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NetworkMessage {
@@ -44,6 +136,8 @@ pub enum NetworkMessage {
         network_id: String,
         best_block_index: u64,
         peer_count: u32,
+        /// Capability identifiers the sender supports, from `SUPPORTED_CAPABILITIES`
+        capabilities: Vec<String>,
     },
     /// Request for peer list
     PeerListRequest,
@@ -53,6 +147,117 @@ pub enum NetworkMessage {
     },
 }
 
+impl NetworkMessage {
+    /// Credits this message costs the sending peer, under a
+    /// `FlowControlConfig`. Only messages that make us do work on a peer's
+    /// behalf are priced; responses and acks cost nothing, so answering our
+    /// own outstanding requests never eats into a peer's balance.
+    pub fn request_cost(&self, config: &crate::FlowControlConfig) -> u64 {
+        match self {
+            NetworkMessage::BlockRequest { count, .. } => {
+                config.base_cost + config.per_block_cost * (*count as u64)
+            }
+            NetworkMessage::TransactionRequest { .. }
+            | NetworkMessage::PeerListRequest
+            | NetworkMessage::Ping
+            | NetworkMessage::PeerInfo { .. } => config.base_cost,
+            _ => 0,
+        }
+    }
+}
+
+/// Parse the major component of a `major.minor.patch`-style version string.
+/// Returns `None` (rather than panicking or rejecting) for anything that
+/// doesn't parse, so `is_version_compatible` can fall back to an exact
+/// string match.
+fn parse_major_version(version: &str) -> Option<u64> {
+    version.split('.').next()?.parse().ok()
+}
+
+/// libp2p protocol name for the block-sync request/response behaviour
+pub const BLOCK_SYNC_PROTOCOL: &str = "/beacon/blocksync/1.0.0";
+
+/// Length-prefixed request/response codec for the block-sync protocol.
+///
+/// Directed fetches (e.g. a `BlockRequest` for a range of blocks) need a
+/// reliable point-to-point reply, which gossipsub can't give us, so this
+/// codec drives a `request_response::Behaviour` instead. It reuses the same
+/// `NetworkMessage` wire format and size limit as gossipsub, just framed
+/// with a 4-byte big-endian length prefix instead of gossipsub's own framing.
+#[derive(Debug, Clone, Default)]
+pub struct BlockSyncCodec;
+
+#[async_trait]
+impl request_response::Codec for BlockSyncCodec {
+    type Protocol = libp2p::StreamProtocol;
+    type Request = NetworkMessage;
+    type Response = NetworkMessage;
+
+    async fn read_request<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_length_prefixed_message(io).await
+    }
+
+    async fn read_response<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        read_length_prefixed_message(io).await
+    }
+
+    async fn write_request<T>(&mut self, _: &Self::Protocol, io: &mut T, request: Self::Request) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_length_prefixed_message(io, &request).await
+    }
+
+    async fn write_response<T>(&mut self, _: &Self::Protocol, io: &mut T, response: Self::Response) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        write_length_prefixed_message(io, &response).await
+    }
+}
+
+async fn read_length_prefixed_message<T>(io: &mut T) -> io::Result<NetworkMessage>
+where
+    T: AsyncRead + Unpin + Send,
+{
+    let mut len_bytes = [0u8; 4];
+    io.read_exact(&mut len_bytes).await?;
+    let len = u32::from_be_bytes(len_bytes) as usize;
+
+    if len > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("block-sync frame too large: {} bytes", len),
+        ));
+    }
+
+    let mut buf = vec![0u8; len];
+    io.read_exact(&mut buf).await?;
+
+    ProtocolHandler::new()
+        .decode_message(&buf, None)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+async fn write_length_prefixed_message<T>(io: &mut T, message: &NetworkMessage) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin + Send,
+{
+    let encoded = ProtocolHandler::new()
+        .encode_message(message)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    io.write_all(&(encoded.len() as u32).to_be_bytes()).await?;
+    io.write_all(&encoded).await?;
+    io.close().await
+}
+
 /// Protocol message with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProtocolMessage {
@@ -64,10 +269,18 @@ pub struct ProtocolMessage {
     pub payload: NetworkMessage,
     /// Message signature (optional)
     pub signature: Option<String>,
+    /// Hex-encoded ed25519 public key of the sender, set by `sign`. Binds
+    /// the message to whoever signed it so `ProtocolHandler`'s authenticated
+    /// mode can confirm it maps to the connecting `libp2p::PeerId`.
+    pub sender: Option<String>,
+    /// Per-sender counter the sender is expected to increase on every
+    /// message it signs. Folded into the signed data so a captured message
+    /// can't be replayed once its `(sender, nonce)` pair has been seen.
+    pub nonce: u64,
 }
 
 impl ProtocolMessage {
-    /// Create a new protocol message
+    /// Create a new, unsigned protocol message
     pub fn new(payload: NetworkMessage) -> Self {
         Self {
             version: PROTOCOL_VERSION.to_string(),
@@ -77,17 +290,24 @@ impl ProtocolMessage {
                 .as_secs(),
             payload,
             signature: None,
+            sender: None,
+            nonce: 0,
         }
     }
-    
-    /// Sign the message with a private key
-    pub fn sign(&mut self, private_key: &ed25519_dalek::SigningKey) -> BeaconResult<()> {
+
+    /// Sign the message with a private key, binding it to the signer's
+    /// public key and `nonce` (the caller is responsible for making `nonce`
+    /// strictly increase per sender, so a captured copy of this exact
+    /// message can't be replayed).
+    pub fn sign(&mut self, private_key: &ed25519_dalek::SigningKey, nonce: u64) -> BeaconResult<()> {
+        self.sender = Some(hex::encode(private_key.verifying_key().to_bytes()));
+        self.nonce = nonce;
         let message_data = self.get_signing_data()?;
         let signature = private_key.sign(&message_data);
         self.signature = Some(hex::encode(signature.to_bytes()));
         Ok(())
     }
-    
+
     /// Verify the message signature
     pub fn verify_signature(&self, public_key: &ed25519_dalek::VerifyingKey) -> bool {
         if let Some(ref signature_hex) = self.signature {
@@ -101,40 +321,366 @@ impl ProtocolMessage {
         }
         false
     }
-    
+
+    /// Deterministic message-id for gossip deduplication: a SHA-256 hash of
+    /// the same bytes the message is signed over, so two nodes that receive
+    /// the same message (signed or not) always agree on its id.
+    pub fn message_id(&self) -> BeaconResult<String> {
+        let data = self.get_signing_data()?;
+        Ok(hex::encode(Sha256::digest(&data)))
+    }
+
     /// Get the data that should be signed
     fn get_signing_data(&self) -> BeaconResult<Vec<u8>> {
         let mut data = Vec::new();
         data.extend_from_slice(self.version.as_bytes());
         data.extend_from_slice(&self.timestamp.to_le_bytes());
-        
+        data.extend_from_slice(&self.nonce.to_le_bytes());
+        if let Some(ref sender) = self.sender {
+            data.extend_from_slice(sender.as_bytes());
+        }
+
         let payload_bytes = bincode::serialize(&self.payload)
             .map_err(|e| BeaconError::serialization(format!("Failed to serialize payload: {}", e)))?;
         data.extend_from_slice(&payload_bytes);
-        
+
         Ok(data)
     }
 }
 
+/// Gossip topics a `NetworkMessage` can be published under. Point-to-point
+/// messages (pings, directed requests/responses, peer-list exchange) aren't
+/// gossiped and have no topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GossipTopic {
+    Blocks,
+    Transactions,
+    PeerInfo,
+}
+
+impl GossipTopic {
+    /// The topic `message` would be published/deduplicated under, or `None`
+    /// if it's a point-to-point message with no gossip topic.
+    pub fn for_message(message: &NetworkMessage) -> Option<Self> {
+        match message {
+            NetworkMessage::Block(_) => Some(GossipTopic::Blocks),
+            NetworkMessage::Transaction(_) => Some(GossipTopic::Transactions),
+            NetworkMessage::PeerInfo { .. } => Some(GossipTopic::PeerInfo),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of handling a received gossip message, mirroring libp2p
+/// gossipsub's `MessageAcceptance`: `Accept` forwards it to other
+/// subscribers, `Ignore` drops it silently (a duplicate, or outside any
+/// gossip topic — not a protocol violation, no penalty), `Reject` drops it
+/// and signals the sender should be penalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GossipVerdict {
+    Accept,
+    Ignore,
+    Reject,
+}
+
+/// Max message-ids remembered per gossip topic before the oldest is evicted
+const GOSSIP_SEEN_CAPACITY: usize = 4096;
+
+/// How long a gossip message-id is remembered before it's forgotten and
+/// would be accepted again if re-received
+const GOSSIP_SEEN_TTL: Duration = Duration::from_secs(120);
+
+/// Per-topic record of message-ids already accepted, in insertion order
+#[derive(Debug, Default)]
+struct TopicSeen {
+    first_seen: HashMap<String, Instant>,
+    order: VecDeque<String>,
+}
+
+/// Bounded, time-expiring cache of gossip message-ids already accepted on
+/// each topic, so a message flooding back through the mesh a second time is
+/// recognized as a duplicate and dropped instead of re-broadcast. Kept
+/// separate per topic so a flood on one topic can't evict another's history.
+#[derive(Debug, Default)]
+struct GossipSeenCache {
+    per_topic: HashMap<GossipTopic, TopicSeen>,
+}
+
+impl GossipSeenCache {
+    /// Record `id` as seen on `topic` if it isn't already (accounting for
+    /// TTL expiry). Returns `true` if this is the first time it's been seen
+    /// on this topic (the caller should forward it), `false` if it's a
+    /// duplicate.
+    fn insert_if_new(&mut self, topic: GossipTopic, id: String) -> bool {
+        let topic_seen = self.per_topic.entry(topic).or_default();
+        let now = Instant::now();
+
+        while let Some(oldest) = topic_seen.order.front() {
+            match topic_seen.first_seen.get(oldest) {
+                Some(inserted_at) if now.duration_since(*inserted_at) >= GOSSIP_SEEN_TTL => {
+                    let oldest = topic_seen.order.pop_front().expect("just peeked");
+                    topic_seen.first_seen.remove(&oldest);
+                }
+                _ => break,
+            }
+        }
+
+        if topic_seen.first_seen.contains_key(&id) {
+            return false;
+        }
+
+        if topic_seen.first_seen.len() >= GOSSIP_SEEN_CAPACITY {
+            if let Some(oldest) = topic_seen.order.pop_front() {
+                topic_seen.first_seen.remove(&oldest);
+            }
+        }
+
+        topic_seen.order.push_back(id.clone());
+        topic_seen.first_seen.insert(id, now);
+        true
+    }
+}
+
+/// Max `(sender, nonce)` pairs remembered before the oldest is evicted
+const REPLAY_GUARD_CAPACITY: usize = 8192;
+
+/// How long a `(sender, nonce)` pair is remembered before it's forgotten.
+/// Kept comfortably longer than any reasonable `AuthConfig::acceptance_window`
+/// so a message can't be replayed right after it ages out of the timestamp
+/// check but before its nonce is forgotten here.
+const REPLAY_GUARD_TTL: Duration = Duration::from_secs(600);
+
+/// Bounded, time-expiring set of `(sender, nonce)` pairs already accepted
+/// under authenticated mode, so a captured signed message can't be
+/// re-injected.
+#[derive(Debug, Default)]
+struct ReplayGuard {
+    first_seen: HashMap<(String, u64), Instant>,
+    order: VecDeque<(String, u64)>,
+}
+
+impl ReplayGuard {
+    /// Record `(sender, nonce)` as seen if it isn't already (accounting for
+    /// TTL expiry). Returns `true` if this is the first time it's been seen
+    /// (the caller should accept the message), `false` if it's a replay.
+    fn insert_if_new(&mut self, sender: &str, nonce: u64) -> bool {
+        let now = Instant::now();
+
+        while let Some(oldest) = self.order.front() {
+            match self.first_seen.get(oldest) {
+                Some(inserted_at) if now.duration_since(*inserted_at) >= REPLAY_GUARD_TTL => {
+                    let oldest = self.order.pop_front().expect("just peeked");
+                    self.first_seen.remove(&oldest);
+                }
+                _ => break,
+            }
+        }
+
+        let key = (sender.to_string(), nonce);
+        if self.first_seen.contains_key(&key) {
+            return false;
+        }
+
+        if self.first_seen.len() >= REPLAY_GUARD_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.first_seen.remove(&oldest);
+            }
+        }
+
+        self.order.push_back(key.clone());
+        self.first_seen.insert(key, now);
+        true
+    }
+}
+
+/// Configuration for `ProtocolHandler`'s optional authenticated mode: when
+/// `require_for_consensus_messages` is set, `decode_message` requires
+/// consensus-critical payloads (`Block`, `Transaction`) to carry a signature
+/// whose embedded public key maps to the connecting peer, to fall within
+/// `acceptance_window` of now, and to not replay an already-seen
+/// `(sender, nonce)` pair.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// Require `Block`/`Transaction` payloads to pass authentication before
+    /// `decode_message` will return them
+    pub require_for_consensus_messages: bool,
+    /// How far a message's `timestamp` may drift from now (in either
+    /// direction) and still be accepted
+    pub acceptance_window: Duration,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            require_for_consensus_messages: false,
+            acceptance_window: Duration::from_secs(30),
+        }
+    }
+}
+
 /// Protocol handler for encoding/decoding messages
 pub struct ProtocolHandler {
     version: String,
+    /// Duplicate-suppression cache for `handle_gossip_message`, keyed by
+    /// gossip topic
+    gossip_seen: GossipSeenCache,
+    /// Graduated per-peer misbehavior/good-behavior tracking, consulted
+    /// alongside `handle_gossip_message`'s verdict to decide whether a peer
+    /// should be dropped.
+    peer_scorer: PeerScorer,
+    /// Authenticated-mode settings consulted by `decode_message`
+    auth_config: AuthConfig,
+    /// Anti-replay state for authenticated mode
+    replay_guard: ReplayGuard,
+    /// Capability set each peer last advertised in its `PeerInfo` handshake,
+    /// consulted by `encode_message` to decide whether an optional wire
+    /// feature is safe to use
+    peer_capabilities: HashMap<libp2p::PeerId, std::collections::HashSet<String>>,
 }
 
 impl ProtocolHandler {
-    /// Create a new protocol handler
+    /// Create a new protocol handler with authenticated mode disabled
     pub fn new() -> Self {
+        Self::with_auth_config(AuthConfig::default())
+    }
+
+    /// Create a new protocol handler with a specific `AuthConfig`, e.g. to
+    /// turn on authenticated mode for consensus-critical messages.
+    pub fn with_auth_config(auth_config: AuthConfig) -> Self {
         Self {
             version: PROTOCOL_VERSION.to_string(),
+            gossip_seen: GossipSeenCache::default(),
+            peer_scorer: PeerScorer::new(PeerScorerConfig::default()),
+            auth_config,
+            replay_guard: ReplayGuard::default(),
+            peer_capabilities: HashMap::new(),
         }
     }
-    
-    /// Encode a network message into bytes
+
+    /// Record the capability set `peer_id` advertised in its `PeerInfo`
+    /// handshake, however it arrived (directed block-sync or gossip).
+    pub fn record_peer_capabilities(&mut self, peer_id: libp2p::PeerId, capabilities: &[String]) {
+        self.peer_capabilities
+            .insert(peer_id, capabilities.iter().cloned().collect());
+    }
+
+    /// Whether `peer_id` advertised `capability` in its last `PeerInfo`
+    /// handshake. A peer we haven't heard from yet is assumed not to
+    /// support it.
+    pub fn peer_supports(&self, peer_id: &libp2p::PeerId, capability: &str) -> bool {
+        self.peer_capabilities
+            .get(peer_id)
+            .is_some_and(|caps| caps.contains(capability))
+    }
+
+    /// Whether every peer whose capabilities we've recorded advertises
+    /// `capability`. Used to gate optional wire features on broadcast paths
+    /// that can't be tailored to one recipient: if no peer's capabilities
+    /// are known yet (e.g. right after startup), this defaults to `true` so
+    /// a node doesn't permanently disable a feature it just hasn't
+    /// negotiated yet.
+    fn all_known_peers_support(&self, capability: &str) -> bool {
+        self.peer_capabilities.values().all(|caps| caps.contains(capability))
+    }
+
+    /// Validate and deduplicate a received gossip message: the single
+    /// decision point a caller needs before deciding whether to re-broadcast
+    /// it to other subscribers. Only a first-seen message that passes
+    /// `MessageValidator` is forwarded; everything else is dropped, with
+    /// `Reject` additionally signaling the sender should be penalized. A
+    /// `Reject` also docks `peer_id`'s score; the caller should follow up
+    /// with `should_disconnect` to decide whether to drop and ban it.
+    pub fn handle_gossip_message(
+        &mut self,
+        peer_id: &libp2p::PeerId,
+        message: &ProtocolMessage,
+    ) -> BeaconResult<GossipVerdict> {
+        let Some(topic) = GossipTopic::for_message(&message.payload) else {
+            return Ok(GossipVerdict::Ignore);
+        };
+
+        let verdict = match &message.payload {
+            NetworkMessage::Block(block) => MessageValidator::validate_block(block),
+            NetworkMessage::Transaction(transaction) => MessageValidator::validate_transaction(transaction),
+            _ => GossipVerdict::Accept,
+        };
+        if verdict != GossipVerdict::Accept {
+            if verdict == GossipVerdict::Reject {
+                self.peer_scorer.record(*peer_id, PeerScoreEvent::InvalidMessage);
+            }
+            return Ok(verdict);
+        }
+
+        let message_id = message.message_id()?;
+        if self.gossip_seen.insert_if_new(topic, message_id) {
+            Ok(GossipVerdict::Accept)
+        } else {
+            Ok(GossipVerdict::Ignore)
+        }
+    }
+
+    /// Record a rate-limit violation against `peer_id`'s score, for a caller
+    /// that just got `false` back from `RateLimiter::allow`.
+    pub fn record_rate_limit_violation(&mut self, peer_id: &libp2p::PeerId) {
+        self.peer_scorer.record(*peer_id, PeerScoreEvent::RateLimitViolation);
+    }
+
+    /// Record a failed `ProtocolMessage::verify_signature` against `peer_id`'s
+    /// score.
+    pub fn record_signature_failure(&mut self, peer_id: &libp2p::PeerId) {
+        self.peer_scorer.record(*peer_id, PeerScoreEvent::SignatureFailure);
+    }
+
+    /// Record `peer_id` serving a useful response (e.g. a valid
+    /// `BlockResponse`), nudging its score back up.
+    pub fn record_useful_response(&mut self, peer_id: &libp2p::PeerId) {
+        self.peer_scorer.record(*peer_id, PeerScoreEvent::UsefulResponse);
+    }
+
+    /// `peer_id`'s current score; higher is better behaved.
+    pub fn peer_score(&self, peer_id: &libp2p::PeerId) -> f64 {
+        self.peer_scorer.score(peer_id)
+    }
+
+    /// Whether `peer_id`'s score has fallen far enough that the caller should
+    /// disconnect and temporarily ban it.
+    pub fn should_disconnect(&self, peer_id: &libp2p::PeerId) -> bool {
+        self.peer_scorer.should_disconnect(peer_id)
+    }
+
+    /// Apply time-decay to all tracked peer scores and drop entries that have
+    /// decayed back to roughly zero. Call periodically (e.g. alongside
+    /// `RateLimiter::cleanup`).
+    pub fn decay_peer_scores(&mut self) {
+        self.peer_scorer.decay();
+    }
+
+    /// Encode a network message into bytes: bincode-serialize it, then
+    /// compress with snappy when that actually shrinks the payload (small
+    /// messages like pings often don't compress well enough to be worth the
+    /// framing byte) and every peer we've negotiated capabilities with
+    /// advertises `capability::SNAPPY`, prefixed with a `ContentEncoding`
+    /// byte so `decode_message` knows whether to decompress. Falling back to
+    /// identity encoding when a peer hasn't advertised the capability lets a
+    /// network roll out compression without a hard flag-day upgrade.
     pub fn encode_message(&self, message: &NetworkMessage) -> BeaconResult<Vec<u8>> {
         let protocol_message = ProtocolMessage::new(message.clone());
-        let encoded = bincode::serialize(&protocol_message)
+        let serialized = bincode::serialize(&protocol_message)
             .map_err(|e| BeaconError::serialization(format!("Failed to encode message: {}", e)))?;
-        
+
+        let (encoding, payload) = if self.all_known_peers_support(capability::SNAPPY) {
+            match snap::raw::Encoder::new().compress_vec(&serialized) {
+                Ok(compressed) if compressed.len() < serialized.len() => (ContentEncoding::Snappy, compressed),
+                _ => (ContentEncoding::Identity, serialized),
+            }
+        } else {
+            (ContentEncoding::Identity, serialized)
+        };
+
+        let mut encoded = Vec::with_capacity(payload.len() + 1);
+        encoded.push(encoding as u8);
+        encoded.extend_from_slice(&payload);
+
         if encoded.len() > MAX_MESSAGE_SIZE {
             return Err(BeaconError::network(format!(
                 "Message too large: {} bytes (max: {} bytes)",
@@ -142,12 +688,27 @@ impl ProtocolHandler {
                 MAX_MESSAGE_SIZE
             )));
         }
-        
+
         Ok(encoded)
     }
-    
-    /// Decode bytes into a network message
-    pub fn decode_message(&self, data: &[u8]) -> BeaconResult<NetworkMessage> {
+
+    /// Decode bytes into a network message, transparently reversing the
+    /// `ContentEncoding` prefix `encode_message` writes. `MAX_MESSAGE_SIZE` is
+    /// enforced on both the wire (compressed) size below and the decompressed
+    /// size, read from snappy's own uncompressed-length header before actually
+    /// decompressing, so a maliciously small frame can't expand into a
+    /// decompression bomb.
+    ///
+    /// `source`, when given, is the `PeerId` the frame was received from. If
+    /// `AuthConfig::require_for_consensus_messages` is set, a `Block` or
+    /// `Transaction` payload is only returned once it passes
+    /// `authenticate`; `source` being `None` in that case is itself a
+    /// rejection, since there's nothing to bind the signature to.
+    pub fn decode_message(
+        &mut self,
+        data: &[u8],
+        source: Option<&libp2p::PeerId>,
+    ) -> BeaconResult<NetworkMessage> {
         if data.len() > MAX_MESSAGE_SIZE {
             return Err(BeaconError::network(format!(
                 "Message too large: {} bytes (max: {} bytes)",
@@ -155,10 +716,30 @@ impl ProtocolHandler {
                 MAX_MESSAGE_SIZE
             )));
         }
-        
-        let protocol_message: ProtocolMessage = bincode::deserialize(data)
+
+        let (&encoding_byte, payload) = data.split_first()
+            .ok_or_else(|| BeaconError::network("Empty message frame"))?;
+        let encoding = ContentEncoding::from_byte(encoding_byte)?;
+
+        let serialized = match encoding {
+            ContentEncoding::Identity => payload.to_vec(),
+            ContentEncoding::Snappy => {
+                let decompressed_len = snap::raw::decompress_len(payload)
+                    .map_err(|e| BeaconError::network(format!("Invalid snappy frame: {}", e)))?;
+                if decompressed_len > MAX_MESSAGE_SIZE {
+                    return Err(BeaconError::network(format!(
+                        "Decompressed message too large: {} bytes (max: {} bytes)",
+                        decompressed_len, MAX_MESSAGE_SIZE
+                    )));
+                }
+                snap::raw::Decoder::new().decompress_vec(payload)
+                    .map_err(|e| BeaconError::network(format!("Failed to decompress message: {}", e)))?
+            }
+        };
+
+        let protocol_message: ProtocolMessage = bincode::deserialize(&serialized)
             .map_err(|e| BeaconError::serialization(format!("Failed to decode message: {}", e)))?;
-        
+
         // Verify protocol version compatibility
         if !self.is_version_compatible(&protocol_message.version) {
             return Err(BeaconError::network(format!(
@@ -166,16 +747,87 @@ impl ProtocolHandler {
                 protocol_message.version, self.version
             )));
         }
-        
+
+        if self.auth_config.require_for_consensus_messages
+            && matches!(protocol_message.payload, NetworkMessage::Block(_) | NetworkMessage::Transaction(_))
+        {
+            let source = source.ok_or_else(|| {
+                BeaconError::network("Authenticated mode requires a connecting peer id for consensus messages")
+            })?;
+            self.authenticate(&protocol_message, source)?;
+        }
+
         Ok(protocol_message.payload)
     }
-    
-    /// Check if a protocol version is compatible
+
+    /// Authenticate a decoded `ProtocolMessage` against `peer_id`: verify its
+    /// signature against its embedded `sender` public key, confirm that key
+    /// maps to `peer_id`, confirm `timestamp` falls within
+    /// `AuthConfig::acceptance_window`, and reject a replayed `(sender, nonce)`.
+    fn authenticate(&mut self, message: &ProtocolMessage, peer_id: &libp2p::PeerId) -> BeaconResult<()> {
+        let sender_hex = message
+            .sender
+            .as_deref()
+            .ok_or_else(|| BeaconError::network("Authenticated mode requires a signed sender public key"))?;
+
+        let sender_bytes = hex::decode(sender_hex)
+            .map_err(|e| BeaconError::network(format!("Invalid sender public key encoding: {}", e)))?;
+        let sender_bytes: [u8; 32] = sender_bytes
+            .try_into()
+            .map_err(|_| BeaconError::network("Sender public key must be 32 bytes"))?;
+        let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&sender_bytes)
+            .map_err(|e| BeaconError::network(format!("Invalid sender public key: {}", e)))?;
+
+        if !message.verify_signature(&verifying_key) {
+            self.peer_scorer.record(*peer_id, PeerScoreEvent::SignatureFailure);
+            return Err(BeaconError::network("Signature verification failed"));
+        }
+
+        let libp2p_public_key = libp2p::identity::ed25519::PublicKey::try_from_bytes(&sender_bytes)
+            .map_err(|e| BeaconError::network(format!("Invalid libp2p public key: {}", e)))?;
+        let derived_peer = libp2p::PeerId::from_public_key(&libp2p::identity::PublicKey::from(libp2p_public_key));
+        if &derived_peer != peer_id {
+            self.peer_scorer.record(*peer_id, PeerScoreEvent::SignatureFailure);
+            return Err(BeaconError::network(format!(
+                "Sender public key does not map to connecting peer {} (derived {})",
+                peer_id, derived_peer
+            )));
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let window = self.auth_config.acceptance_window.as_secs();
+        if message.timestamp.abs_diff(now) > window {
+            return Err(BeaconError::network(format!(
+                "Message timestamp {} outside +/-{}s acceptance window (now: {})",
+                message.timestamp, window, now
+            )));
+        }
+
+        if !self.replay_guard.insert_if_new(sender_hex, message.nonce) {
+            return Err(BeaconError::network(format!(
+                "Replayed message: sender {} nonce {} already seen",
+                sender_hex, message.nonce
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Check if a protocol version is compatible: peers with the same
+    /// semver major version are wire-compatible (a `1.0.1` node can talk to
+    /// a `1.0.0` node), since minor/patch bumps are expected to only add
+    /// capabilities rather than break the base wire format. Falls back to an
+    /// exact string match if either version doesn't parse as semver.
     fn is_version_compatible(&self, version: &str) -> bool {
-        // For now, only exact version match
-        version == self.version
+        match (parse_major_version(version), parse_major_version(&self.version)) {
+            (Some(their_major), Some(our_major)) => their_major == our_major,
+            _ => version == self.version,
+        }
     }
-    
+
     /// Create a ping message
     pub fn create_ping(&self) -> BeaconResult<Vec<u8>> {
         self.encode_message(&NetworkMessage::Ping)
@@ -218,6 +870,7 @@ impl ProtocolHandler {
             network_id,
             best_block_index,
             peer_count,
+            capabilities: SUPPORTED_CAPABILITIES.iter().map(|s| s.to_string()).collect(),
         })
     }
 }
@@ -232,38 +885,44 @@ impl Default for ProtocolHandler {
 pub struct MessageValidator;
 
 impl MessageValidator {
-    /// Validate a block message
-    pub fn validate_block(block: &Block) -> BeaconResult<()> {
-        block.validate()?;
-        
+    /// Validate a block message, producing a gossip verdict rather than
+    /// `Result`: an invalid block is `Reject` (penalize the sender), a valid
+    /// one is `Accept` (the caller still runs it through the seen-cache
+    /// before actually re-broadcasting it).
+    pub fn validate_block(block: &Block) -> GossipVerdict {
+        if let Err(e) = block.validate() {
+            tracing::debug!("Rejecting invalid gossiped block: {}", e);
+            return GossipVerdict::Reject;
+        }
+
         // Additional network-specific validation
         if block.size() > MAX_MESSAGE_SIZE {
-            return Err(BeaconError::network(format!(
-                "Block too large for network transmission: {} bytes",
-                block.size()
-            )));
+            tracing::debug!("Rejecting oversized gossiped block: {} bytes", block.size());
+            return GossipVerdict::Reject;
         }
-        
-        Ok(())
+
+        GossipVerdict::Accept
     }
-    
-    /// Validate a transaction message
-    pub fn validate_transaction(transaction: &Transaction) -> BeaconResult<()> {
-        transaction.validate()?;
-        
+
+    /// Validate a transaction message, producing a gossip verdict rather
+    /// than `Result` (see `validate_block`).
+    pub fn validate_transaction(transaction: &Transaction) -> GossipVerdict {
+        if let Err(e) = transaction.validate() {
+            tracing::debug!("Rejecting invalid gossiped transaction: {}", e);
+            return GossipVerdict::Reject;
+        }
+
         // Additional network-specific validation
         let tx_size = bincode::serialize(transaction)
             .map(|data| data.len())
             .unwrap_or(0);
-        
+
         if tx_size > MAX_MESSAGE_SIZE / 10 {
-            return Err(BeaconError::network(format!(
-                "Transaction too large for network transmission: {} bytes",
-                tx_size
-            )));
+            tracing::debug!("Rejecting oversized gossiped transaction: {} bytes", tx_size);
+            return GossipVerdict::Reject;
         }
-        
-        Ok(())
+
+        GossipVerdict::Accept
     }
     
     /// Validate message rate limits
@@ -358,3 +1017,106 @@ impl Default for RateLimiter {
         Self::new()
     }
 }
+
+/// A weighted event used to adjust a peer's score. `RateLimiter` and
+/// `MessageValidator` only answer allow/deny for a single message; these
+/// events let a caller accumulate a peer's track record over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeerScoreEvent {
+    /// Tripped `RateLimiter::allow`
+    RateLimitViolation,
+    /// Failed `MessageValidator::validate_block` / `validate_transaction`
+    InvalidMessage,
+    /// Failed `ProtocolMessage::verify_signature`
+    SignatureFailure,
+    /// Served something useful, e.g. a valid `BlockResponse`
+    UsefulResponse,
+}
+
+impl PeerScoreEvent {
+    /// The score adjustment this event applies. Negative events are weighted
+    /// roughly by how expensive they are to fake: a rate-limit violation is
+    /// cheap and common, an invalid block/transaction took more effort to
+    /// construct, and a forged signature is the costliest to produce by
+    /// accident, so it's penalized hardest.
+    fn weight(self) -> f64 {
+        match self {
+            PeerScoreEvent::RateLimitViolation => -5.0,
+            PeerScoreEvent::InvalidMessage => -20.0,
+            PeerScoreEvent::SignatureFailure => -40.0,
+            PeerScoreEvent::UsefulResponse => 2.0,
+        }
+    }
+}
+
+/// Tuning for `PeerScorer`.
+#[derive(Debug, Clone)]
+pub struct PeerScorerConfig {
+    /// A peer whose score falls below this should be disconnected
+    pub disconnect_threshold: f64,
+    /// Fraction of a peer's score that survives each `decay()` tick, pulling
+    /// old penalties (and credits) back toward zero over time
+    pub decay_factor: f64,
+    /// Scores with absolute value below this are treated as zero and their
+    /// entry is dropped, so well-behaved peers don't accumulate dead state
+    /// forever
+    pub prune_epsilon: f64,
+}
+
+impl Default for PeerScorerConfig {
+    fn default() -> Self {
+        Self {
+            disconnect_threshold: -100.0,
+            decay_factor: 0.9,
+            prune_epsilon: 0.5,
+        }
+    }
+}
+
+/// Graduated per-peer reputation built on weighted `PeerScoreEvent`s, turning
+/// `RateLimiter`'s flat allow/deny into accumulated misbehavior tracking:
+/// a peer that occasionally trips a rate limit is tolerated, but one that
+/// also sends invalid data or forged signatures quickly crosses
+/// `should_disconnect`. Scores decay exponentially toward zero on each
+/// `decay()` tick so old penalties fade instead of following a peer forever.
+pub struct PeerScorer {
+    scores: std::collections::HashMap<libp2p::PeerId, f64>,
+    config: PeerScorerConfig,
+}
+
+impl PeerScorer {
+    /// Create a new peer scorer
+    pub fn new(config: PeerScorerConfig) -> Self {
+        Self {
+            scores: std::collections::HashMap::new(),
+            config,
+        }
+    }
+
+    /// Apply `event`'s weight to `peer_id`'s score.
+    pub fn record(&mut self, peer_id: libp2p::PeerId, event: PeerScoreEvent) {
+        let score = self.scores.entry(peer_id).or_insert(0.0);
+        *score += event.weight();
+    }
+
+    /// `peer_id`'s current score; peers with no recorded events score 0.0.
+    pub fn score(&self, peer_id: &libp2p::PeerId) -> f64 {
+        self.scores.get(peer_id).copied().unwrap_or(0.0)
+    }
+
+    /// Whether `peer_id`'s score has fallen below `disconnect_threshold`.
+    pub fn should_disconnect(&self, peer_id: &libp2p::PeerId) -> bool {
+        self.score(peer_id) < self.config.disconnect_threshold
+    }
+
+    /// Multiply every tracked score by `decay_factor`, pulling old penalties
+    /// and credits back toward zero, and drop entries that have decayed to
+    /// roughly nothing so well-behaved peers don't linger in memory forever.
+    pub fn decay(&mut self) {
+        self.scores
+            .retain(|_, score| {
+                *score *= self.config.decay_factor;
+                score.abs() >= self.config.prune_epsilon
+            });
+    }
+}
@@ -0,0 +1,121 @@
+use beacon_core::{BeaconError, BeaconResult, Block, BlockHeader, Timestamp};
+use beacon_storage::BlockchainStorage;
+use std::sync::Arc;
+use tracing::info;
+
+/// Fetch a trusted checkpoint from a remote node's HTTP API and seed local
+/// blockchain storage from it, so a fresh node can start syncing forward
+/// from that height instead of replaying the whole chain from genesis.
+///
+/// Before trusting the checkpoint: the remote's reported genesis hash must
+/// match our own (if we already have a genesis block), and its latest block
+/// must chain back to a distinct parent hash, i.e. it can't be genesis
+/// itself or claim to be its own parent.
+pub async fn checkpoint_sync(
+    checkpoint_sync_url: &str,
+    blockchain_storage: &Arc<BlockchainStorage>,
+) -> BeaconResult<()> {
+    let base_url = reqwest::Url::parse(checkpoint_sync_url)
+        .map_err(|e| BeaconError::config(format!("Invalid checkpoint-sync URL: {}", e)))?;
+    let client = reqwest::Client::new();
+
+    let info: serde_json::Value = client
+        .get(base_url.join("api/v1/blockchain/info").map_err(|e| {
+            BeaconError::config(format!("Invalid checkpoint-sync URL: {}", e))
+        })?)
+        .send()
+        .await
+        .map_err(|e| BeaconError::network(format!("Failed to fetch checkpoint info: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| BeaconError::network(format!("Invalid checkpoint info response: {}", e)))?;
+
+    let remote_genesis_hash = info["genesis_hash"].as_str().ok_or_else(|| {
+        BeaconError::network("Checkpoint info response missing genesis_hash")
+    })?;
+    if let Some(local_genesis) = blockchain_storage.get_block_by_index(0).await? {
+        if local_genesis.hash != remote_genesis_hash {
+            return Err(BeaconError::network(format!(
+                "Refusing checkpoint sync from {}: genesis hash mismatch (theirs: {}, ours: {})",
+                base_url, remote_genesis_hash, local_genesis.hash
+            )));
+        }
+    }
+
+    let latest: serde_json::Value = client
+        .get(base_url.join("api/v1/blocks/latest").map_err(|e| {
+            BeaconError::config(format!("Invalid checkpoint-sync URL: {}", e))
+        })?)
+        .query(&[("limit", "1")])
+        .send()
+        .await
+        .map_err(|e| BeaconError::network(format!("Failed to fetch checkpoint block: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| BeaconError::network(format!("Invalid checkpoint block response: {}", e)))?;
+
+    let block_json = latest["blocks"]
+        .as_array()
+        .and_then(|blocks| blocks.first())
+        .ok_or_else(|| BeaconError::network("Checkpoint response had no latest block"))?;
+
+    let index = block_json["number"]
+        .as_u64()
+        .ok_or_else(|| BeaconError::network("Checkpoint block missing number"))?;
+    let hash = block_json["hash"]
+        .as_str()
+        .ok_or_else(|| BeaconError::network("Checkpoint block missing hash"))?
+        .to_string();
+    let previous_hash = block_json["parent_hash"].as_str().map(str::to_string);
+
+    if index == 0 {
+        return Err(BeaconError::network(
+            "Refusing checkpoint sync: remote's latest block is genesis, nothing to skip ahead to",
+        ));
+    }
+    let Some(previous_hash) = previous_hash else {
+        return Err(BeaconError::network(format!(
+            "Refusing checkpoint sync: block {} doesn't chain back to a parent",
+            index
+        )));
+    };
+    if previous_hash == hash {
+        return Err(BeaconError::network(format!(
+            "Refusing checkpoint sync: block {} reports itself as its own parent",
+            index
+        )));
+    }
+
+    let validator = block_json["validator"].as_str().unwrap_or("checkpoint").to_string();
+    let timestamp = block_json["timestamp"]
+        .as_str()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| Timestamp::from_millis(dt.timestamp_millis()))
+        .unwrap_or_else(Timestamp::now);
+
+    // The checkpoint endpoint doesn't carry the block's transactions or a
+    // merkle root; we're trusting the remote's reported hash rather than
+    // recomputing it, so the stand-in header fields below aren't asked to
+    // cross-check against it.
+    let checkpoint_block = Block {
+        header: BlockHeader {
+            index,
+            previous_hash,
+            merkle_root: String::new(),
+            timestamp,
+            validator,
+            difficulty: 0,
+            nonce: 0,
+            version: 1,
+            metadata: std::collections::HashMap::new(),
+        },
+        transactions: Vec::new(),
+        transaction_results: Vec::new(),
+        hash,
+    };
+
+    blockchain_storage.store_checkpoint_block(&checkpoint_block).await?;
+    info!("Checkpoint-synced to block {} from {}", index, base_url);
+
+    Ok(())
+}
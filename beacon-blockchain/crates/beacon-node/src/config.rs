@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::net::SocketAddr;
 use libp2p::Multiaddr;
-use beacon_core::{BeaconError, BeaconResult, ConsensusParams};
+use beacon_core::{Address, BeaconError, BeaconResult, ConsensusParams, ForkSchedule, ServiceTransactionPolicy, SignatureScheme};
+use beacon_consensus::Checkpoint;
 
 /// Complete node configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +16,7 @@ pub struct NodeConfig {
     pub chaincode: ChaincodeConfig,
     pub security: SecurityConfig,
     pub monitoring: MonitoringConfig,
+    pub sync: SyncConfig,
 }
 
 /// Node-specific settings
@@ -39,6 +41,11 @@ pub struct NetworkConfig {
     pub max_connections: usize,
     /// Network identifier
     pub network_id: String,
+    /// Base URL of a trusted peer's HTTP API to checkpoint-sync from on
+    /// startup, as an alternative to replaying the whole chain from genesis.
+    /// See `checkpoint::checkpoint_sync` for what gets validated before the
+    /// checkpoint is trusted.
+    pub checkpoint_sync_url: Option<String>,
 }
 
 /// Consensus configuration
@@ -52,12 +59,46 @@ pub struct ConsensusConfig {
     pub validators: Vec<String>,
     /// Consensus parameters
     pub params: ConsensusParams,
+    /// Scheduled protocol upgrades - see `beacon_core::ForkSchedule`. Empty
+    /// means every block height is judged by the same rules, which is the
+    /// default.
+    #[serde(default)]
+    pub fork_schedule: ForkSchedule,
+}
+
+/// How a node bootstraps its local view of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// Replay every block from genesis.
+    Full,
+    /// Adopt `SyncConfig::checkpoint` as a trusted weak-subjectivity root
+    /// and sync forward from it, skipping the genesis-to-checkpoint replay.
+    /// See `Consensus::verify_checkpoint`.
+    Checkpoint,
+}
+
+impl Default for SyncMode {
+    fn default() -> Self {
+        SyncMode::Full
+    }
+}
+
+/// Bootstrap sync configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub mode: SyncMode,
+    /// Required when `mode` is `Checkpoint`; ignored under `Full`. Verified
+    /// against the configured validator set via `Consensus::verify_checkpoint`
+    /// before the node adopts it as its trusted root.
+    pub checkpoint: Option<Checkpoint>,
 }
 
 /// Storage configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageConfig {
-    /// Storage engine (currently only RocksDB)
+    /// Storage engine: "rocksdb" for a durable on-disk database, or "memory"
+    /// for an in-memory backend (tests, CI)
     pub engine: String,
     /// Cache size in MB
     pub cache_size: usize,
@@ -100,6 +141,24 @@ pub struct SecurityConfig {
     pub tls_key: Option<String>,
     /// Validator private key file path
     pub validator_key: Option<String>,
+    /// Signature schemes this node accepts on submitted transactions - see
+    /// `Transaction::validate_signature_scheme`. Lets an operator keep a
+    /// new scheme (e.g. `Secp256k1Recoverable`) disabled until they're
+    /// ready for the interop it brings.
+    pub enabled_signature_schemes: Vec<SignatureScheme>,
+    /// Admission policy for zero-gas-price "service transactions" - see
+    /// `Transaction::validate_service_transaction`.
+    pub service_transaction_policy: ServiceTransactionPolicy,
+    /// Sender addresses permitted to submit zero-gas-price transactions when
+    /// `service_transaction_policy` is `AllowlistOnly`. Ignored otherwise.
+    pub service_transaction_allowlist: Vec<Address>,
+    /// Chaincode ID of an optional on-chain allowlist, consulted in addition
+    /// to `service_transaction_allowlist` when `service_transaction_policy`
+    /// is `AllowlistOnly`. The chaincode is expected to store its allowlist
+    /// as a JSON array of addresses under the state key
+    /// `"{chaincode_id}:service_tx_allowlist"`, so it can be updated by
+    /// ordinary transactions rather than a node restart.
+    pub service_transaction_allowlist_chaincode: Option<String>,
 }
 
 /// Monitoring configuration
@@ -124,12 +183,14 @@ impl Default for NodeConfig {
                 bootstrap_peers: Vec::new(),
                 max_connections: 50,
                 network_id: "beacon_devnet".to_string(),
+                checkpoint_sync_url: None,
             },
             consensus: ConsensusConfig {
                 consensus_type: "proof_of_authority".to_string(),
                 is_validator: false,
                 validators: Vec::new(),
                 params: ConsensusParams::default(),
+                fork_schedule: ForkSchedule::default(),
             },
             storage: StorageConfig {
                 engine: "rocksdb".to_string(),
@@ -152,11 +213,16 @@ impl Default for NodeConfig {
                 tls_cert: None,
                 tls_key: None,
                 validator_key: None,
+                enabled_signature_schemes: vec![SignatureScheme::Ed25519],
+                service_transaction_policy: ServiceTransactionPolicy::default(),
+                service_transaction_allowlist: Vec::new(),
+                service_transaction_allowlist_chaincode: None,
             },
             monitoring: MonitoringConfig {
                 metrics_enabled: false,
                 metrics_addr: ([0, 0, 0, 0], 9091).into(),
             },
+            sync: SyncConfig::default(),
         }
     }
 }
@@ -209,6 +275,18 @@ impl NodeConfig {
             return Err(BeaconError::config("Validator node must have validator list"));
         }
 
+        self.consensus
+            .fork_schedule
+            .validate()
+            .map_err(BeaconError::config)?;
+
+        // Validate sync configuration
+        if self.sync.mode == SyncMode::Checkpoint && self.sync.checkpoint.is_none() {
+            return Err(BeaconError::config(
+                "Checkpoint sync mode requires a checkpoint to be configured",
+            ));
+        }
+
         // Validate storage configuration
         if self.storage.cache_size == 0 {
             return Err(BeaconError::config("Cache size must be greater than 0"));
@@ -223,6 +301,17 @@ impl NodeConfig {
             return Err(BeaconError::config("Max concurrent executions must be greater than 0"));
         }
 
+        // Validate monitoring configuration
+        if self.monitoring.metrics_enabled
+            && self.api.enabled
+            && self.monitoring.metrics_addr == self.api.bind_addr
+        {
+            return Err(BeaconError::config(format!(
+                "monitoring.metrics_addr ({}) must not collide with api.bind_addr - both can't bind the same address",
+                self.monitoring.metrics_addr
+            )));
+        }
+
         Ok(())
     }
 
@@ -313,4 +402,50 @@ mod tests {
         config.node.id = String::new();
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_fork_schedule_validation() {
+        use beacon_core::ForkActivation;
+
+        let mut config = NodeConfig::default();
+        config.consensus.fork_schedule = ForkSchedule::new(vec![
+            ForkActivation { fork_name: "alpha".to_string(), activation_height: 100, required_version: 2 },
+            ForkActivation { fork_name: "beta".to_string(), activation_height: 200, required_version: 3 },
+        ]);
+        assert!(config.validate().is_ok());
+
+        // Non-increasing activation heights should fail
+        config.consensus.fork_schedule = ForkSchedule::new(vec![
+            ForkActivation { fork_name: "alpha".to_string(), activation_height: 100, required_version: 2 },
+            ForkActivation { fork_name: "beta".to_string(), activation_height: 100, required_version: 3 },
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_sync_requires_checkpoint() {
+        let mut config = NodeConfig::default();
+        config.sync.mode = SyncMode::Checkpoint;
+
+        // Checkpoint mode with no checkpoint supplied should fail
+        assert!(config.validate().is_err());
+
+        config.sync.checkpoint = Some(Checkpoint {
+            block_hash: "c".repeat(64),
+            height: 42,
+            quorum_cert: beacon_consensus::QuorumCert::new("c".repeat(64)),
+        });
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_metrics_addr_cannot_collide_with_api_bind_addr() {
+        let mut config = NodeConfig::default();
+        config.monitoring.metrics_enabled = true;
+        config.monitoring.metrics_addr = config.api.bind_addr;
+        assert!(config.validate().is_err());
+
+        config.monitoring.metrics_addr = ([0, 0, 0, 0], config.api.bind_addr.port() + 1).into();
+        assert!(config.validate().is_ok());
+    }
 }
@@ -4,6 +4,10 @@ use std::path::PathBuf;
 
 mod node;
 mod config;
+mod checkpoint;
+mod peer_store;
+mod slasher_store;
+mod metrics_server;
 
 use node::BeaconNode;
 use config::NodeConfig;
@@ -33,6 +37,12 @@ struct Cli {
     #[arg(short, long)]
     bootstrap: Vec<String>,
 
+    /// Base URL of a trusted peer's HTTP API to checkpoint-sync from,
+    /// seeding local chain state from its latest block instead of replaying
+    /// the whole chain from genesis
+    #[arg(long, value_name = "URL")]
+    checkpoint_sync_url: Option<String>,
+
     /// Enable validator mode
     #[arg(long)]
     validator: bool,
@@ -139,6 +149,10 @@ async fn load_config(cli: &Cli) -> Result<NodeConfig, Box<dyn std::error::Error>
             .collect::<Result<Vec<_>, _>>()?;
     }
 
+    if let Some(checkpoint_sync_url) = &cli.checkpoint_sync_url {
+        config.network.checkpoint_sync_url = Some(checkpoint_sync_url.clone());
+    }
+
     config.consensus.is_validator = cli.validator;
     config.api.bind_addr = ([0, 0, 0, 0], cli.api_port).into();
     config.monitoring.metrics_enabled = cli.metrics;
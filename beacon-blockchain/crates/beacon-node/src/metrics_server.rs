@@ -0,0 +1,32 @@
+use axum::{http::StatusCode, response::IntoResponse, routing::get, Router};
+use beacon_core::BeaconResult;
+use std::net::SocketAddr;
+
+/// Standalone Prometheus `/metrics` server for `MonitoringConfig::metrics_addr`,
+/// separate from the main API server's own `/metrics` route (mounted on
+/// `api.bind_addr`, behind that server's auth/rate-limit middleware) - the
+/// same split Lighthouse makes between its REST API and its dedicated
+/// `http_metrics` server. Only ever started when
+/// `MonitoringConfig::metrics_enabled` is set - see `BeaconNode::run`.
+pub async fn run(addr: SocketAddr) -> BeaconResult<()> {
+    let app = Router::new().route("/metrics", get(metrics_handler));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Metrics server listening on {}", addr);
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    match beacon_api::metrics::render() {
+        Ok(buffer) => (
+            StatusCode::OK,
+            [("Content-Type", prometheus::TextEncoder::new().format_type())],
+            buffer,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("failed to encode metrics: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "failed to encode metrics").into_response()
+        }
+    }
+}
@@ -1,25 +1,67 @@
-use crate::config::NodeConfig;
-use beacon_core::{BeaconResult};
-use beacon_storage::{Database, DatabaseConfig, BlockchainStorage, StateStorage, TransactionStorage};
+use crate::checkpoint;
+use crate::config::{NodeConfig, SyncMode};
+use crate::slasher_store::RocksDbSlasherStore;
+use beacon_core::{Address, BeaconError, BeaconResult, Transaction};
+use beacon_storage::{Database, DatabaseConfig, InMemoryBackend, StorageBackend, BlockchainStorage, StateStorage, TransactionStorage};
 use beacon_api::ApiServer;
-use beacon_consensus::{ProofOfAuthority, Consensus};
+use beacon_consensus::{ProofOfAuthority, Consensus, BlockImportPipeline, ConsensusContext, Slasher, StageTiming};
 use beacon_crypto::KeyStore;
 use beacon_chaincode::{ChaincodeExecutor, ChaincodeExecutorConfig, ChaincodeShimService};
+use beacon_networking::{ChainReader, NetworkConfig as P2pNetworkConfig, NetworkEvent, NetworkManager};
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tracing::{info, error, debug};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tracing::{info, warn, error, debug};
+
+/// Bridges `BlockchainStorage` to the `ChainReader` the networking layer
+/// needs to answer block-sync requests, without beacon-storage depending on
+/// beacon-networking.
+struct NodeChainReader(Arc<BlockchainStorage>);
+
+#[async_trait::async_trait]
+impl ChainReader for NodeChainReader {
+    async fn get_blocks_range(&self, start_index: u64, count: u32) -> BeaconResult<Vec<beacon_core::Block>> {
+        self.0.get_blocks_range(start_index, count).await
+    }
+
+    async fn best_block(&self) -> BeaconResult<Option<beacon_core::Block>> {
+        self.0.get_latest_block().await
+    }
+}
 
 /// Main BEACON blockchain node
 pub struct BeaconNode {
     config: NodeConfig,
-    database: Arc<Database>,
+    /// Concrete RocksDB handle, when `storage.engine` selects it. Only this
+    /// node's own maintenance loop and the API server need the concrete
+    /// type; the storage layers run against the `StorageBackend` trait object.
+    database: Option<Arc<Database>>,
+    storage_backend: Arc<dyn StorageBackend>,
     blockchain_storage: Arc<BlockchainStorage>,
     state_storage: Arc<StateStorage>,
     transaction_storage: Arc<TransactionStorage>,
     consensus: Arc<dyn Consensus>,
     key_store: KeyStore,
     chaincode_executor: Arc<ChaincodeExecutor>,
+    /// This node's X25519 identity for unwrapping `ConfidentialTransaction`
+    /// payloads it holds a wrapped-key entry for - see
+    /// `load_confidential_decryption_key`. `None` means this node only ever
+    /// orders confidential transactions as opaque ciphertext.
+    confidential_decryption_key: Option<x25519_dalek::StaticSecret>,
+    /// The running block-import pipeline, once `run` has started it - the
+    /// networking task's foreign-block importer needs a handle to the same
+    /// pipeline the consensus task drives, not a second one, so reorgs land
+    /// in the same state/blockchain storage. `None` before `run` is called.
+    import_pipeline: Option<Arc<BlockImportPipeline>>,
     shutdown_sender: broadcast::Sender<()>,
+    /// Submitted transactions waiting to be picked up by the block-import
+    /// pipeline. The receiving end is only taken (and the loop started) once,
+    /// by `run`.
+    pending_tx_sender: mpsc::Sender<Transaction>,
+    pending_tx_receiver: Option<mpsc::Receiver<Transaction>>,
+    /// Per-stage timing from the most recently imported block, surfaced
+    /// through `NodeStatus` for profiling.
+    last_pipeline_timing: Arc<RwLock<Option<StageTiming>>>,
 }
 
 impl BeaconNode {
@@ -30,51 +72,115 @@ impl BeaconNode {
         // Create necessary directories
         config.create_directories().await?;
 
-        // Initialize database
-        let db_config = DatabaseConfig {
-            path: config.database_path(),
-            cache_size: config.storage.cache_size,
-            write_buffer_size: config.storage.write_buffer_size,
-            max_open_files: config.storage.max_open_files,
-            ..Default::default()
+        // Select the storage backend based on configuration. "memory" is for
+        // tests and CI; anything else opens a durable RocksDB database.
+        let database: Option<Arc<Database>> = if config.storage.engine == "memory" {
+            None
+        } else {
+            let db_config = DatabaseConfig {
+                path: config.database_path(),
+                cache_size: config.storage.cache_size,
+                write_buffer_size: config.storage.write_buffer_size,
+                max_open_files: config.storage.max_open_files,
+                ..Default::default()
+            };
+            Some(Arc::new(Database::open(db_config)?))
+        };
+
+        let storage_backend: Arc<dyn StorageBackend> = match &database {
+            Some(database) => database.clone(),
+            None => Arc::new(InMemoryBackend::new()),
         };
-        let database = Arc::new(Database::open(db_config)?);
 
         // Initialize storage layers
-        let blockchain_storage = Arc::new(BlockchainStorage::new(database.clone()));
-        let state_storage = Arc::new(StateStorage::new(database.clone()));
-        let transaction_storage = Arc::new(TransactionStorage::new(database.clone()));
+        let blockchain_storage = Arc::new(BlockchainStorage::new(storage_backend.clone()));
+        let state_storage = Arc::new(StateStorage::new(storage_backend.clone()));
+        let transaction_storage = Arc::new(TransactionStorage::new(storage_backend.clone()));
 
         // Initialize blockchain with genesis block if needed
         blockchain_storage.initialize(&config.network.network_id).await?;
 
+        // Checkpoint-sync from a trusted peer's HTTP API if configured, so
+        // this node can start syncing forward from that peer's latest block
+        // instead of replaying the whole chain from genesis.
+        if let Some(checkpoint_sync_url) = &config.network.checkpoint_sync_url {
+            checkpoint::checkpoint_sync(checkpoint_sync_url, &blockchain_storage).await?;
+        }
+
+        // Initialize key store and, for validator nodes, load this node's
+        // signing key so the consensus engine can sign the blocks it proposes.
+        let key_store = KeyStore::new(config.keys_path());
+        let signing_key = if config.consensus.is_validator {
+            let passphrase = load_validator_key_passphrase();
+            let keypair = key_store.load_or_generate_keypair(&config.node.id, &passphrase).await?;
+            Some(keypair.signing_key)
+        } else {
+            None
+        };
+
         // Initialize consensus
-        let consensus: Arc<dyn Consensus> = Arc::new(ProofOfAuthority::new(
+        let mut proof_of_authority = ProofOfAuthority::with_fork_schedule(
             config.consensus.validators.clone(),
             config.node.id.clone(),
-        ));
+            signing_key,
+            config.consensus.fork_schedule.clone(),
+        );
+
+        // Under weak-subjectivity checkpoint sync, adopt the configured
+        // checkpoint as this node's trusted root instead of replaying from
+        // genesis - but only once it's confirmed signed by a quorum of the
+        // validator set, so a malicious or stale checkpoint can't be forced
+        // on the node by config alone.
+        if config.sync.mode == SyncMode::Checkpoint {
+            let checkpoint = config
+                .sync
+                .checkpoint
+                .clone()
+                .ok_or_else(|| BeaconError::config("checkpoint sync mode requires a checkpoint"))?;
+            if !proof_of_authority.verify_checkpoint(&checkpoint).await? {
+                return Err(BeaconError::consensus(format!(
+                    "checkpoint at height {} failed quorum verification; refusing to adopt as trusted root",
+                    checkpoint.height
+                )));
+            }
+            info!("Adopting verified checkpoint at height {} as sync root", checkpoint.height);
+            proof_of_authority.set_checkpoint(checkpoint);
+        }
 
-        // Initialize key store
-        let key_store = KeyStore::new(config.keys_path());
+        let consensus: Arc<dyn Consensus> = Arc::new(proof_of_authority);
 
         // Initialize chaincode services
-        let chaincode_shim_service = Arc::new(ChaincodeShimService::new(state_storage.clone()));
-        let chaincode_config = ChaincodeExecutorConfig::default();
+        let chaincode_config = ChaincodeExecutorConfig {
+            service_transaction_policy: config.security.service_transaction_policy.clone(),
+            service_transaction_allowlist: config.security.service_transaction_allowlist.clone(),
+            ..ChaincodeExecutorConfig::default()
+        };
+        let chaincode_shim_service = Arc::new(ChaincodeShimService::new(state_storage.clone(), chaincode_config.trace_execution));
         let chaincode_executor = Arc::new(ChaincodeExecutor::new(chaincode_config, chaincode_shim_service));
 
         // Create shutdown channel
         let (shutdown_sender, _) = broadcast::channel(1);
 
+        // Channel used by `submit_transaction` to hand transactions to the
+        // block-import pipeline running in `run`.
+        let (pending_tx_sender, pending_tx_receiver) = mpsc::channel(1024);
+
         let node = Self {
             config,
             database,
+            storage_backend,
             blockchain_storage,
             state_storage,
             transaction_storage,
             consensus,
             key_store,
             chaincode_executor,
+            confidential_decryption_key: load_confidential_decryption_key(),
+            import_pipeline: None,
             shutdown_sender,
+            pending_tx_sender,
+            pending_tx_receiver: Some(pending_tx_receiver),
+            last_pipeline_timing: Arc::new(RwLock::new(None)),
         };
 
         info!("BEACON node initialized successfully");
@@ -87,54 +193,203 @@ impl BeaconNode {
 
         let mut shutdown_receiver = self.shutdown_sender.subscribe();
 
-        // Start API server if enabled
+        // Start API server if enabled. It talks to RocksDB directly, so it
+        // only comes up when the node is running against a durable database.
         let api_handle = if self.config.api.enabled {
-            let api_server = ApiServer::new(
-                self.config.api.bind_addr,
-                self.database.clone(),
-                self.chaincode_executor.clone()
-            );
+            match &self.database {
+                Some(database) => {
+                    let api_server = ApiServer::new(
+                        self.config.api.bind_addr,
+                        database.clone(),
+                        self.chaincode_executor.clone()
+                    )?;
+                    Some(tokio::spawn(async move {
+                        if let Err(e) = api_server.run().await {
+                            error!("API server error: {}", e);
+                        }
+                    }))
+                }
+                None => {
+                    warn!("API server requires a durable storage engine; skipping (storage.engine = \"memory\")");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Start the standalone Prometheus metrics server, a no-op unless
+        // explicitly enabled - see `metrics_server::run`.
+        let metrics_handle = if self.config.monitoring.metrics_enabled {
+            let metrics_addr = self.config.monitoring.metrics_addr;
             Some(tokio::spawn(async move {
-                if let Err(e) = api_server.run().await {
-                    error!("API server error: {}", e);
+                if let Err(e) = crate::metrics_server::run(metrics_addr).await {
+                    error!("Metrics server error: {}", e);
                 }
             }))
         } else {
             None
         };
 
-        // Start consensus engine
+        // Start consensus engine: batches submitted transactions on the
+        // configured block interval and runs them through the block-import
+        // pipeline (validate_signatures -> check_state_preconditions ->
+        // execute_chaincode -> commit).
         let consensus_handle = {
             let consensus = self.consensus.clone();
+            let slasher = Slasher::new(
+                Arc::new(RocksDbSlasherStore::new(self.storage_backend.clone())),
+                self.config.consensus.params.slashing_evidence_retention_blocks,
+            );
+            let mut pipeline = BlockImportPipeline::new(
+                self.state_storage.clone(),
+                self.transaction_storage.clone(),
+                self.blockchain_storage.clone(),
+                self.chaincode_executor.clone(),
+            )
+            .with_slasher(slasher);
+            if let Some(key) = std::mem::take(&mut self.confidential_decryption_key) {
+                pipeline = pipeline.with_confidential_key(key);
+            }
+            let pipeline = Arc::new(pipeline);
+            self.import_pipeline = Some(pipeline.clone());
+            let mut pending_rx = self
+                .pending_tx_receiver
+                .take()
+                .expect("BeaconNode::run called more than once");
+            let last_pipeline_timing = self.last_pipeline_timing.clone();
+            let block_interval = Duration::from_millis(self.config.consensus.params.block_time);
+
             tokio::spawn(async move {
                 debug!("Consensus engine started");
-                // Consensus engine would run here
-                // For now, just wait for shutdown
+                let mut interval = tokio::time::interval(block_interval);
+                let mut batch: Vec<Transaction> = Vec::new();
+
                 loop {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            if batch.is_empty() || !consensus.can_create_blocks() {
+                                continue;
+                            }
+
+                            let transactions = std::mem::take(&mut batch);
+                            let context = ConsensusContext::new();
+                            match pipeline.import_block(transactions, consensus.as_ref(), &context).await {
+                                Ok(Some(imported)) => {
+                                    info!(
+                                        "Imported block {} with {} transaction(s)",
+                                        imported.block.header.index,
+                                        imported.block.transactions.len()
+                                    );
+                                    if let Some(evidence) = &imported.slashing_evidence {
+                                        // Nothing broadcasts this evidence to peers or submits
+                                        // a validator-removal governance transaction yet - this
+                                        // is the hook that would attach to, once it exists. See
+                                        // `Slasher`'s module doc for what this can and can't
+                                        // detect today.
+                                        error!(
+                                            "Detected equivocation by validator {} at height {}: signed both {} and {}",
+                                            evidence.validator, evidence.height, evidence.block_a, evidence.block_b
+                                        );
+                                    }
+                                    *last_pipeline_timing.write().await = Some(imported.timing);
+                                }
+                                Ok(None) => {}
+                                Err(e) => error!("Block import failed: {}", e),
+                            }
+                        }
+                        transaction = pending_rx.recv() => {
+                            match transaction {
+                                Some(transaction) => batch.push(transaction),
+                                None => break,
+                            }
+                        }
+                    }
                 }
             })
         };
 
-        // Start networking layer
+        // Start networking layer. `chain_reader` answers peers' block-sync
+        // requests from local storage; the swarm identity is freshly
+        // generated each start (no peer-id persistence yet - reconnecting
+        // peers re-discover this node under bootstrap/mDNS like any new one).
+        let network_config = P2pNetworkConfig {
+            listen_addr: self.config.network.listen_addr.clone(),
+            bootstrap_peers: self.config.network.bootstrap_peers.clone(),
+            max_connections: self.config.network.max_connections,
+            network_id: self.config.network.network_id.clone(),
+            bootstrap_http: None,
+        };
+        let chain_reader: Arc<dyn ChainReader> = Arc::new(NodeChainReader(self.blockchain_storage.clone()));
+        let (network_manager, mut network_events, _network_commands) = NetworkManager::new(
+            network_config,
+            libp2p::identity::Keypair::generate_ed25519(),
+            chain_reader,
+            None,
+        )
+        .await?;
+
         let networking_handle = tokio::spawn(async move {
             debug!("Networking layer started");
-            // P2P networking would run here
-            loop {
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            if let Err(e) = network_manager.run().await {
+                error!("Networking layer error: {}", e);
             }
         });
 
+        // The hook a peer-sync subsystem calls on observing a block from a
+        // competing chain (see `BlockImportPipeline::import_foreign_block`):
+        // every `NetworkEvent::BlockReceived` that `validate_gossip_block`
+        // forwarded (including same-height forks it no longer hard-rejects)
+        // is stored and, if it's now the longer chain, reorganized onto.
+        let foreign_block_handle = {
+            let pipeline = self.import_pipeline.clone();
+            tokio::spawn(async move {
+                let Some(pipeline) = pipeline else {
+                    return;
+                };
+                loop {
+                    match network_events.recv().await {
+                        Ok(NetworkEvent::BlockReceived(block, peer)) => {
+                            let block_index = block.header.index;
+                            match pipeline.import_foreign_block(block).await {
+                                Ok(Some(_)) => {
+                                    info!("Reorganized onto block {} received from peer {}", block_index, peer);
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    warn!("Failed to import block {} from peer {}: {}", block_index, peer, e);
+                                }
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Foreign-block importer lagged, skipped {} network event(s)", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            })
+        };
+
         // Start background maintenance tasks
         let maintenance_handle = {
-            let database = self.database.clone();
+            let storage_backend = self.storage_backend.clone();
+            let revocation_store = self.database.clone().map(|database| {
+                beacon_api::handlers::auth::TokenRevocationStore::new(database)
+            });
             tokio::spawn(async move {
                 let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600)); // 1 hour
                 loop {
                     interval.tick().await;
-                    debug!("Running database maintenance");
-                    if let Err(e) = database.maintenance().await {
-                        error!("Database maintenance error: {}", e);
+                    debug!("Running storage maintenance");
+                    if let Err(e) = storage_backend.maintenance().await {
+                        error!("Storage maintenance error: {}", e);
+                    }
+                    if let Some(revocation_store) = &revocation_store {
+                        match revocation_store.prune_expired().await {
+                            Ok(pruned) => debug!("Pruned {} expired token revocations", pruned),
+                            Err(e) => error!("Token revocation pruning error: {}", e),
+                        }
                     }
                 }
             })
@@ -157,6 +412,11 @@ impl BeaconNode {
                     error!("Networking layer error: {}", e);
                 }
             }
+            result = foreign_block_handle => {
+                if let Err(e) = result {
+                    error!("Foreign-block importer error: {}", e);
+                }
+            }
             result = maintenance_handle => {
                 if let Err(e) = result {
                     error!("Maintenance task error: {}", e);
@@ -164,10 +424,13 @@ impl BeaconNode {
             }
         }
 
-        // Shutdown API server
+        // Shutdown API server and metrics server
         if let Some(handle) = api_handle {
             handle.abort();
         }
+        if let Some(handle) = metrics_handle {
+            handle.abort();
+        }
 
         info!("BEACON node stopped");
         Ok(())
@@ -183,7 +446,7 @@ impl BeaconNode {
         }
 
         // Perform any cleanup tasks
-        self.database.maintenance().await?;
+        self.storage_backend.maintenance().await?;
 
         info!("BEACON node shutdown complete");
         Ok(())
@@ -192,14 +455,17 @@ impl BeaconNode {
     /// Get node status information
     pub async fn get_status(&self) -> BeaconResult<NodeStatus> {
         let blockchain_stats = self.blockchain_storage.get_stats().await?;
-        let consensus_state = self.consensus.get_state();
-        
+        let next_height = blockchain_stats.latest_block_index.map(|index| index + 1).unwrap_or(0);
+        let consensus_state = self.consensus.get_state(next_height);
+        let pipeline_timing = self.last_pipeline_timing.read().await.clone();
+
         Ok(NodeStatus {
             node_id: self.config.node.id.clone(),
             network_id: self.config.network.network_id.clone(),
             is_validator: self.config.consensus.is_validator,
             blockchain_stats,
             consensus_state,
+            pipeline_timing,
             uptime: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -213,17 +479,51 @@ impl BeaconNode {
 
         // Validate transaction
         transaction.validate()?;
+        transaction.validate_signature_scheme(&self.config.security.enabled_signature_schemes)?;
+        transaction.validate_service_transaction(
+            &self.config.security.service_transaction_policy,
+            &self.service_transaction_allowlist().await,
+        )?;
 
         // Store transaction (as pending)
         self.transaction_storage.store_transaction(&transaction).await?;
 
-        // Forward to consensus layer for inclusion in next block
-        // This would be handled by the consensus engine in a real implementation
+        // Forward to the consensus engine's block-import pipeline
+        let tx_id = transaction.id.clone();
+        if self.pending_tx_sender.send(transaction).await.is_err() {
+            warn!("Consensus engine is not running; transaction {} left pending", tx_id.as_str());
+        }
 
-        info!("Transaction {} submitted successfully", transaction.id.as_str());
+        info!("Transaction {} submitted successfully", tx_id.as_str());
         Ok(())
     }
 
+    /// Addresses currently permitted to submit zero-gas-price service
+    /// transactions: `SecurityConfig::service_transaction_allowlist` plus,
+    /// when configured, the JSON array stored by
+    /// `service_transaction_allowlist_chaincode` under
+    /// `"{chaincode_id}:service_tx_allowlist"`. A missing or unparsable
+    /// on-chain entry is treated as empty rather than failing the whole
+    /// lookup, so a misconfigured chaincode ID degrades to the static
+    /// config allowlist instead of blocking every service transaction.
+    async fn service_transaction_allowlist(&self) -> Vec<Address> {
+        let mut allowlist = self.config.security.service_transaction_allowlist.clone();
+
+        if let Some(chaincode_id) = &self.config.security.service_transaction_allowlist_chaincode {
+            let key = format!("{}:service_tx_allowlist", chaincode_id);
+            match self.state_storage.get_json::<Vec<String>>(&key).await {
+                Ok(Some(addresses)) => allowlist.extend(addresses.iter().map(|a| Address::new(a))),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "Failed to read on-chain service transaction allowlist from chaincode {}: {}",
+                    chaincode_id, e
+                ),
+            }
+        }
+
+        allowlist
+    }
+
     /// Get blockchain information
     pub async fn get_blockchain_info(&self) -> BeaconResult<beacon_storage::BlockchainStats> {
         self.blockchain_storage.get_stats().await
@@ -246,6 +546,42 @@ impl BeaconNode {
     }
 }
 
+/// Load the passphrase protecting this node's validator keystore. Falls back
+/// to a fixed, clearly-insecure passphrase (logged loudly) so single-node and
+/// CI setups still start without manual key provisioning.
+fn load_validator_key_passphrase() -> String {
+    match std::env::var("BEACON_VALIDATOR_KEY_PASSPHRASE") {
+        Ok(passphrase) if !passphrase.is_empty() => passphrase,
+        _ => {
+            warn!("BEACON_VALIDATOR_KEY_PASSPHRASE not set; using an insecure default passphrase for the validator keystore");
+            "beacon-insecure-default-passphrase".to_string()
+        }
+    }
+}
+
+/// Load this node's X25519 identity for unwrapping `ConfidentialTransaction`
+/// payloads, if `BEACON_CONFIDENTIAL_DECRYPTION_KEY` (64 hex chars, a raw
+/// 32-byte X25519 scalar) is set. Unlike `load_validator_key_passphrase`
+/// there's no insecure default to fall back to - a node with no key
+/// configured simply never decrypts confidential transactions, the same as
+/// any node without a wrapped-key entry for its identity.
+fn load_confidential_decryption_key() -> Option<x25519_dalek::StaticSecret> {
+    let hex_key = std::env::var("BEACON_CONFIDENTIAL_DECRYPTION_KEY").ok()?;
+    let bytes = match hex::decode(hex_key.trim()) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("BEACON_CONFIDENTIAL_DECRYPTION_KEY is not valid hex ({}); confidential transactions will not be decrypted", e);
+            return None;
+        }
+    };
+    let Ok(array): Result<[u8; 32], _> = bytes.try_into() else {
+        warn!("BEACON_CONFIDENTIAL_DECRYPTION_KEY must decode to 32 bytes; confidential transactions will not be decrypted");
+        return None;
+    };
+    info!("Loaded confidential transaction decryption key from BEACON_CONFIDENTIAL_DECRYPTION_KEY");
+    Some(x25519_dalek::StaticSecret::from(array))
+}
+
 /// Node status information
 #[derive(Debug, serde::Serialize)]
 pub struct NodeStatus {
@@ -254,5 +590,8 @@ pub struct NodeStatus {
     pub is_validator: bool,
     pub blockchain_stats: beacon_storage::BlockchainStats,
     pub consensus_state: beacon_consensus::ConsensusState,
+    /// Per-stage timing from the most recently imported block, if one has
+    /// been imported since the node started.
+    pub pipeline_timing: Option<beacon_consensus::StageTiming>,
     pub uptime: u64,
 }
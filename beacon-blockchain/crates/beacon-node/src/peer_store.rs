@@ -0,0 +1,144 @@
+use beacon_core::{BeaconError, BeaconResult};
+use beacon_networking::{DiscoveredPeer, DiscoveryStore, PeerStore};
+use beacon_networking::{PeerId, PeerInfo};
+use beacon_storage::{Keys, StorageBackend, CF_PEERS};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// `PeerStore` backed by the node's own `Database`/`StorageBackend`, under the
+/// `CF_PEERS` column family. This is the concrete implementation the
+/// `PeerStore` trait doc comment in `beacon-networking` describes as
+/// "supplied by whoever wires `PeerManager` up to real storage" - it lives
+/// here, rather than in `beacon-storage`, so that crate doesn't have to take
+/// on a new dependency on `beacon-networking` just to name this trait.
+pub struct NetworkPeerStore {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl NetworkPeerStore {
+    pub fn new(backend: Arc<dyn StorageBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+#[async_trait::async_trait]
+impl PeerStore for NetworkPeerStore {
+    async fn save_peer(&self, peer: &PeerInfo) -> BeaconResult<()> {
+        let key = Keys::peer(&hex::encode(peer.peer_id.to_bytes()));
+        let value = bincode::serialize(peer)?;
+        self.backend.put(CF_PEERS, &key, &value).await
+    }
+
+    async fn remove_peer(&self, peer_id: &PeerId) -> BeaconResult<()> {
+        let key = Keys::peer(&hex::encode(peer_id.to_bytes()));
+        self.backend.delete(CF_PEERS, &key).await
+    }
+
+    async fn save_ban(&self, peer_id: &PeerId, ban_expiry: u64) -> BeaconResult<()> {
+        let key = Keys::peer_ban(&hex::encode(peer_id.to_bytes()));
+        let value = bincode::serialize(&ban_expiry)?;
+        self.backend.put(CF_PEERS, &key, &value).await
+    }
+
+    async fn remove_ban(&self, peer_id: &PeerId) -> BeaconResult<()> {
+        let key = Keys::peer_ban(&hex::encode(peer_id.to_bytes()));
+        self.backend.delete(CF_PEERS, &key).await
+    }
+
+    async fn load_all(&self) -> BeaconResult<(Vec<PeerInfo>, HashMap<PeerId, u64>)> {
+        let mut peers = Vec::new();
+        for (_, value) in self.backend.scan_prefix(CF_PEERS, b"peer:").await? {
+            peers.push(bincode::deserialize::<PeerInfo>(&value).map_err(|e| {
+                BeaconError::serialization(format!("Failed to deserialize persisted peer: {}", e))
+            })?);
+        }
+
+        let mut bans = HashMap::new();
+        for (key, value) in self.backend.scan_prefix(CF_PEERS, b"peer_ban:").await? {
+            let Some(peer_id_hex) = key.strip_prefix(b"peer_ban:") else {
+                continue;
+            };
+            let peer_id_hex = String::from_utf8_lossy(peer_id_hex);
+            let peer_id_bytes = hex::decode(peer_id_hex.as_ref()).map_err(|e| {
+                BeaconError::serialization(format!("Invalid persisted peer ban key: {}", e))
+            })?;
+            let peer_id = PeerId::from_bytes(&peer_id_bytes).map_err(|e| {
+                BeaconError::serialization(format!("Invalid persisted peer ban key: {}", e))
+            })?;
+            let ban_expiry = bincode::deserialize::<u64>(&value).map_err(|e| {
+                BeaconError::serialization(format!("Failed to deserialize persisted ban: {}", e))
+            })?;
+            bans.insert(peer_id, ban_expiry);
+        }
+
+        Ok((peers, bans))
+    }
+}
+
+/// `DiscoveryStore` backed by the node's own `Database`/`StorageBackend`,
+/// sharing `CF_PEERS` with `NetworkPeerStore` but under the
+/// `discovered_peer`/`discovery_ban` key prefixes so the two record kinds
+/// don't collide.
+pub struct NetworkDiscoveryStore {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl NetworkDiscoveryStore {
+    pub fn new(backend: Arc<dyn StorageBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+#[async_trait::async_trait]
+impl DiscoveryStore for NetworkDiscoveryStore {
+    async fn save_peer(&self, peer: &DiscoveredPeer) -> BeaconResult<()> {
+        let key = Keys::discovered_peer(&hex::encode(peer.peer_id.to_bytes()));
+        let value = bincode::serialize(peer)?;
+        self.backend.put(CF_PEERS, &key, &value).await
+    }
+
+    async fn remove_peer(&self, peer_id: &PeerId) -> BeaconResult<()> {
+        let key = Keys::discovered_peer(&hex::encode(peer_id.to_bytes()));
+        self.backend.delete(CF_PEERS, &key).await
+    }
+
+    async fn save_ban(&self, peer_id: &PeerId, ban_expiry: u64) -> BeaconResult<()> {
+        let key = Keys::discovery_ban(&hex::encode(peer_id.to_bytes()));
+        let value = bincode::serialize(&ban_expiry)?;
+        self.backend.put(CF_PEERS, &key, &value).await
+    }
+
+    async fn remove_ban(&self, peer_id: &PeerId) -> BeaconResult<()> {
+        let key = Keys::discovery_ban(&hex::encode(peer_id.to_bytes()));
+        self.backend.delete(CF_PEERS, &key).await
+    }
+
+    async fn load_all(&self) -> BeaconResult<(Vec<DiscoveredPeer>, HashMap<PeerId, u64>)> {
+        let mut peers = Vec::new();
+        for (_, value) in self.backend.scan_prefix(CF_PEERS, b"discovered_peer:").await? {
+            peers.push(bincode::deserialize::<DiscoveredPeer>(&value).map_err(|e| {
+                BeaconError::serialization(format!("Failed to deserialize persisted discovered peer: {}", e))
+            })?);
+        }
+
+        let mut bans = HashMap::new();
+        for (key, value) in self.backend.scan_prefix(CF_PEERS, b"discovery_ban:").await? {
+            let Some(peer_id_hex) = key.strip_prefix(b"discovery_ban:") else {
+                continue;
+            };
+            let peer_id_hex = String::from_utf8_lossy(peer_id_hex);
+            let peer_id_bytes = hex::decode(peer_id_hex.as_ref()).map_err(|e| {
+                BeaconError::serialization(format!("Invalid persisted discovery ban key: {}", e))
+            })?;
+            let peer_id = PeerId::from_bytes(&peer_id_bytes).map_err(|e| {
+                BeaconError::serialization(format!("Invalid persisted discovery ban key: {}", e))
+            })?;
+            let ban_expiry = bincode::deserialize::<u64>(&value).map_err(|e| {
+                BeaconError::serialization(format!("Failed to deserialize persisted discovery ban: {}", e))
+            })?;
+            bans.insert(peer_id, ban_expiry);
+        }
+
+        Ok((peers, bans))
+    }
+}
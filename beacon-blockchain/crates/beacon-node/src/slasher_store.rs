@@ -0,0 +1,53 @@
+use beacon_consensus::SlasherStore;
+use beacon_core::{BeaconResult, BlockIndex, Hash};
+use beacon_storage::{Keys, StorageBackend, CF_SLASHING};
+use std::sync::Arc;
+
+/// `SlasherStore` backed by the node's own `Database`/`StorageBackend`, under
+/// the `CF_SLASHING` column family - the `beacon-consensus` counterpart to
+/// `NetworkPeerStore`/`NetworkDiscoveryStore` in `peer_store.rs`.
+pub struct RocksDbSlasherStore {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl RocksDbSlasherStore {
+    pub fn new(backend: Arc<dyn StorageBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+#[async_trait::async_trait]
+impl SlasherStore for RocksDbSlasherStore {
+    async fn get(&self, validator_id: &str, height: BlockIndex) -> BeaconResult<Option<(Hash, String)>> {
+        let key = Keys::slashing_evidence(validator_id, height);
+        match self.backend.get(CF_SLASHING, &key).await? {
+            Some(value) => Ok(Some(bincode::deserialize(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn put(&self, validator_id: &str, height: BlockIndex, block_hash: Hash, signature: String) -> BeaconResult<()> {
+        let key = Keys::slashing_evidence(validator_id, height);
+        let value = bincode::serialize(&(block_hash, signature))?;
+        self.backend.put(CF_SLASHING, &key, &value).await
+    }
+
+    async fn prune_below(&self, min_height: BlockIndex) -> BeaconResult<()> {
+        for (key, _) in self.backend.scan_prefix(CF_SLASHING, b"slashing:").await? {
+            if let Some(height) = height_from_key(&key) {
+                if height < min_height {
+                    self.backend.delete(CF_SLASHING, &key).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extracts the zero-padded height suffix from a `Keys::slashing_evidence`
+/// key ("slashing:{validator_id}:{height:020}"), skipping anything that
+/// doesn't parse rather than failing the whole prune pass.
+fn height_from_key(key: &[u8]) -> Option<BlockIndex> {
+    let key = std::str::from_utf8(key).ok()?;
+    key.rsplit(':').next()?.parse().ok()
+}
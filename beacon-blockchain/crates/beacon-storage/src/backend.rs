@@ -0,0 +1,656 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use beacon_core::{BeaconError, BeaconResult};
+use crate::Database;
+
+/// A single operation in an atomic multi-key write
+#[derive(Debug, Clone)]
+pub enum BatchOp {
+    Put { cf: String, key: Vec<u8>, value: Vec<u8> },
+    Delete { cf: String, key: Vec<u8> },
+}
+
+impl BatchOp {
+    pub fn put(cf: impl Into<String>, key: Vec<u8>, value: Vec<u8>) -> Self {
+        Self::Put { cf: cf.into(), key, value }
+    }
+
+    pub fn delete(cf: impl Into<String>, key: Vec<u8>) -> Self {
+        Self::Delete { cf: cf.into(), key }
+    }
+}
+
+/// Smallest key that is NOT prefixed by `prefix`, for use as the exclusive
+/// upper bound of a prefix scan. Found by incrementing the last byte that
+/// isn't already `0xFF`, dropping any `0xFF` bytes after it (they'd carry
+/// over); `None` means `prefix` has no upper bound (e.g. all `0xFF`s) and the
+/// scan must run to the end of the column family.
+pub fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut bound = prefix.to_vec();
+    while let Some(&last) = bound.last() {
+        if last == 0xFF {
+            bound.pop();
+            continue;
+        }
+        *bound.last_mut().unwrap() += 1;
+        return Some(bound);
+    }
+    None
+}
+
+/// A single bounded, paginated range read within `batch_read`/`range_query`:
+/// all keys in `cf` starting with `prefix`, optionally narrowed further by
+/// `start` (inclusive lower bound) and `end` (exclusive upper bound), capped
+/// at `limit` entries and walked in descending order if `reverse` is set.
+/// Modeled on Garage's K2V batch item handler.
+#[derive(Debug, Clone)]
+pub struct RangeQuery {
+    pub cf: String,
+    pub prefix: Vec<u8>,
+    pub start: Option<Vec<u8>>,
+    pub end: Option<Vec<u8>>,
+    pub limit: usize,
+    pub reverse: bool,
+}
+
+impl RangeQuery {
+    pub fn new(cf: impl Into<String>, prefix: Vec<u8>, limit: usize) -> Self {
+        Self { cf: cf.into(), prefix, start: None, end: None, limit, reverse: false }
+    }
+
+    pub fn with_bounds(mut self, start: Option<Vec<u8>>, end: Option<Vec<u8>>) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    pub fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+}
+
+/// Result of one `RangeQuery`. `next_start` is set when the query stopped
+/// because it hit `limit` rather than running out of matching keys, and is
+/// the first matching key the query did not return; resume a forward scan
+/// by passing it back as `start`. On a reverse query it is the highest key
+/// below the page; since `end` is exclusive, passing it back as `end` leaves
+/// that key out of the next page.
+#[derive(Debug, Clone, Default)]
+pub struct RangeResult {
+    pub items: Vec<(Vec<u8>, Vec<u8>)>,
+    pub next_start: Option<Vec<u8>>,
+}
+
+/// Storage engine abstraction so the storage layers don't hard-wire a
+/// specific database. Column families are addressed by name so existing
+/// callers (`CF_BLOCKS`, `CF_STATE`, ...) carry over unchanged.
+#[async_trait::async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Get a value from a column family
+    async fn get(&self, cf: &str, key: &[u8]) -> BeaconResult<Option<Vec<u8>>>;
+
+    /// Put a value into a column family
+    async fn put(&self, cf: &str, key: &[u8], value: &[u8]) -> BeaconResult<()>;
+
+    /// Delete a key from a column family
+    async fn delete(&self, cf: &str, key: &[u8]) -> BeaconResult<()>;
+
+    /// All key-value pairs in a column family whose key starts with `prefix`,
+    /// in ascending key order
+    async fn scan_prefix(&self, cf: &str, prefix: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// All key-value pairs in a column family with `start <= key < end`, in
+    /// ascending key order. Unlike `scan_prefix`, the iterator is bounded by
+    /// `end` directly (`set_iterate_upper_bound` on the RocksDB backend) so
+    /// the scan stops exactly at the boundary instead of reading past it and
+    /// discarding the tail.
+    async fn scan_range(&self, cf: &str, start: &[u8], end: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Apply a set of puts/deletes atomically
+    async fn batch(&self, ops: Vec<BatchOp>) -> BeaconResult<()>;
+
+    /// Run one bounded, paginated range read. See `RangeQuery`.
+    async fn range_query(&self, query: &RangeQuery) -> BeaconResult<RangeResult>;
+
+    /// Apply a set of puts/deletes atomically. Alias for `batch`, named to
+    /// pair with `batch_read` for callers doing a K2V-style batch round-trip.
+    async fn batch_write(&self, ops: Vec<BatchOp>) -> BeaconResult<()> {
+        self.batch(ops).await
+    }
+
+    /// Run a list of range reads in one round-trip. Queries are independent
+    /// (no cross-query atomicity) but this is the entry point for batched
+    /// reads - e.g. fetching all `tx_block:{index}:*` entries for several
+    /// blocks at once - so callers don't pay a round-trip per query.
+    async fn batch_read(&self, queries: Vec<RangeQuery>) -> BeaconResult<Vec<RangeResult>> {
+        let mut results = Vec::with_capacity(queries.len());
+        for query in &queries {
+            results.push(self.range_query(query).await?);
+        }
+        Ok(results)
+    }
+
+    /// Run any backend-specific maintenance (compaction, stats logging, ...)
+    async fn maintenance(&self) -> BeaconResult<()>;
+}
+
+#[async_trait::async_trait]
+impl StorageBackend for Database {
+    async fn get(&self, cf: &str, key: &[u8]) -> BeaconResult<Option<Vec<u8>>> {
+        self.get_cf(cf, key)
+    }
+
+    async fn put(&self, cf: &str, key: &[u8], value: &[u8]) -> BeaconResult<()> {
+        self.put_cf(cf, key, value)
+    }
+
+    async fn delete(&self, cf: &str, key: &[u8]) -> BeaconResult<()> {
+        self.delete_cf(cf, key)
+    }
+
+    async fn scan_prefix(&self, cf: &str, prefix: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        match prefix_upper_bound(prefix) {
+            Some(upper_bound) => self.scan_range(cf, prefix, &upper_bound).await,
+            None => {
+                // `prefix` has no upper bound (e.g. all 0xFF bytes); scan to the
+                // end of the column family, same as before.
+                let iter = self.iter_cf_mode(cf, rocksdb::IteratorMode::From(prefix, rocksdb::Direction::Forward))?;
+                let mut results = Vec::new();
+                for item in iter {
+                    let (key, value) = item.map_err(|e| BeaconError::storage(format!("scan failed: {}", e)))?;
+                    if !key.starts_with(prefix) {
+                        break;
+                    }
+                    results.push((key.to_vec(), value.to_vec()));
+                }
+                Ok(results)
+            }
+        }
+    }
+
+    async fn scan_range(&self, cf: &str, start: &[u8], end: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let cf_handle = self.cf_handle(cf)?;
+
+        let mut read_opts = rocksdb::ReadOptions::default();
+        read_opts.set_iterate_upper_bound(end.to_vec());
+
+        let iter = self.inner().iterator_cf_opt(
+            cf_handle,
+            read_opts,
+            rocksdb::IteratorMode::From(start, rocksdb::Direction::Forward),
+        );
+
+        let mut results = Vec::new();
+        for item in iter {
+            let (key, value) = item.map_err(|e| BeaconError::storage(format!("scan failed: {}", e)))?;
+            results.push((key.to_vec(), value.to_vec()));
+        }
+
+        Ok(results)
+    }
+
+    async fn batch(&self, ops: Vec<BatchOp>) -> BeaconResult<()> {
+        let mut batch = self.create_batch();
+
+        for op in ops {
+            match op {
+                BatchOp::Put { cf, key, value } => {
+                    let handle = self.cf_handle(&cf)?;
+                    batch.put_cf(handle, &key, &value);
+                }
+                BatchOp::Delete { cf, key } => {
+                    let handle = self.cf_handle(&cf)?;
+                    batch.delete_cf(handle, &key);
+                }
+            }
+        }
+
+        self.write_batch(batch)
+    }
+
+    async fn range_query(&self, query: &RangeQuery) -> BeaconResult<RangeResult> {
+        let cf_handle = self.cf_handle(&query.cf)?;
+        let upper_bound = query.end.clone().or_else(|| prefix_upper_bound(&query.prefix));
+
+        let mut read_opts = rocksdb::ReadOptions::default();
+        if let Some(upper_bound) = &upper_bound {
+            read_opts.set_iterate_upper_bound(upper_bound.clone());
+        }
+
+        let mode = if query.reverse {
+            match &upper_bound {
+                Some(bound) => rocksdb::IteratorMode::From(bound, rocksdb::Direction::Reverse),
+                None => rocksdb::IteratorMode::End,
+            }
+        } else {
+            let start = query.start.as_deref().unwrap_or(&query.prefix);
+            rocksdb::IteratorMode::From(start, rocksdb::Direction::Forward)
+        };
+
+        let iter = self.inner().iterator_cf_opt(cf_handle, read_opts, mode);
+
+        let mut items = Vec::new();
+        let mut next_start = None;
+        for item in iter {
+            let (key, value) = item.map_err(|e| BeaconError::storage(format!("range query failed: {}", e)))?;
+
+            if !key.starts_with(&query.prefix) {
+                break;
+            }
+            if let Some(start) = &query.start {
+                if key.as_ref() < start.as_slice() {
+                    break;
+                }
+            }
+
+            if items.len() == query.limit {
+                next_start = Some(key.to_vec());
+                break;
+            }
+            items.push((key.to_vec(), value.to_vec()));
+        }
+
+        Ok(RangeResult { items, next_start })
+    }
+
+    async fn maintenance(&self) -> BeaconResult<()> {
+        Database::maintenance(self).await
+    }
+}
+
+/// In-memory storage backend, for tests and CI where a durable database isn't
+/// wanted. Column families are kept as ordered maps so `scan_prefix` matches
+/// the RocksDB backend's ascending-key-order behavior.
+#[derive(Default)]
+pub struct InMemoryBackend {
+    column_families: RwLock<HashMap<String, BTreeMap<Vec<u8>, Vec<u8>>>>,
+}
+
+impl InMemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageBackend for InMemoryBackend {
+    async fn get(&self, cf: &str, key: &[u8]) -> BeaconResult<Option<Vec<u8>>> {
+        let column_families = self.column_families.read().await;
+        Ok(column_families.get(cf).and_then(|cf| cf.get(key).cloned()))
+    }
+
+    async fn put(&self, cf: &str, key: &[u8], value: &[u8]) -> BeaconResult<()> {
+        let mut column_families = self.column_families.write().await;
+        column_families.entry(cf.to_string()).or_default().insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    async fn delete(&self, cf: &str, key: &[u8]) -> BeaconResult<()> {
+        let mut column_families = self.column_families.write().await;
+        if let Some(cf) = column_families.get_mut(cf) {
+            cf.remove(key);
+        }
+        Ok(())
+    }
+
+    async fn scan_prefix(&self, cf: &str, prefix: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let column_families = self.column_families.read().await;
+        let Some(cf) = column_families.get(cf) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(cf
+            .range(prefix.to_vec()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    async fn scan_range(&self, cf: &str, start: &[u8], end: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let column_families = self.column_families.read().await;
+        let Some(cf) = column_families.get(cf) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(cf
+            .range(start.to_vec()..end.to_vec())
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    async fn batch(&self, ops: Vec<BatchOp>) -> BeaconResult<()> {
+        let mut column_families = self.column_families.write().await;
+
+        for op in ops {
+            match op {
+                BatchOp::Put { cf, key, value } => {
+                    column_families.entry(cf).or_default().insert(key, value);
+                }
+                BatchOp::Delete { cf, key } => {
+                    if let Some(cf) = column_families.get_mut(&cf) {
+                        cf.remove(&key);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn range_query(&self, query: &RangeQuery) -> BeaconResult<RangeResult> {
+        let column_families = self.column_families.read().await;
+        let Some(cf) = column_families.get(&query.cf) else {
+            return Ok(RangeResult::default());
+        };
+
+        let lower = query.start.clone().unwrap_or_else(|| query.prefix.clone());
+        let upper = match query.end.clone().or_else(|| prefix_upper_bound(&query.prefix)) {
+            Some(end) => std::ops::Bound::Excluded(end),
+            None => std::ops::Bound::Unbounded,
+        };
+
+        let mut matched: Vec<(Vec<u8>, Vec<u8>)> = cf
+            .range((std::ops::Bound::Included(lower), upper))
+            .take_while(|(key, _)| key.starts_with(&query.prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        if query.reverse {
+            matched.reverse();
+        }
+
+        let next_start = if matched.len() > query.limit {
+            let cursor = matched[query.limit].0.clone();
+            matched.truncate(query.limit);
+            Some(cursor)
+        } else {
+            None
+        };
+
+        Ok(RangeResult { items: matched, next_start })
+    }
+
+    async fn maintenance(&self) -> BeaconResult<()> {
+        Ok(())
+    }
+}
+
+/// Object-store-backed storage for shared/cloud deployments (S3, GCS, Azure
+/// Blob, ...) via the `object_store` crate. Column family and key are encoded
+/// into the object path, so unlike RocksDB there's no native multi-key atomic
+/// write; `batch` applies each operation sequentially.
+pub struct ObjectStoreBackend {
+    store: Arc<dyn object_store::ObjectStore>,
+    prefix: String,
+}
+
+impl ObjectStoreBackend {
+    pub fn new(store: Arc<dyn object_store::ObjectStore>, prefix: impl Into<String>) -> Self {
+        Self { store, prefix: prefix.into() }
+    }
+
+    fn object_path(&self, cf: &str, key: &[u8]) -> object_store::path::Path {
+        object_store::path::Path::from(format!("{}/{}/{}", self.prefix, cf, hex::encode(key)))
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageBackend for ObjectStoreBackend {
+    async fn get(&self, cf: &str, key: &[u8]) -> BeaconResult<Option<Vec<u8>>> {
+        match self.store.get(&self.object_path(cf, key)).await {
+            Ok(result) => {
+                let bytes = result
+                    .bytes()
+                    .await
+                    .map_err(|e| BeaconError::storage(format!("object store read failed: {}", e)))?;
+                Ok(Some(bytes.to_vec()))
+            }
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(BeaconError::storage(format!("object store get failed: {}", e))),
+        }
+    }
+
+    async fn put(&self, cf: &str, key: &[u8], value: &[u8]) -> BeaconResult<()> {
+        self.store
+            .put(&self.object_path(cf, key), bytes::Bytes::copy_from_slice(value).into())
+            .await
+            .map_err(|e| BeaconError::storage(format!("object store put failed: {}", e)))?;
+        Ok(())
+    }
+
+    async fn delete(&self, cf: &str, key: &[u8]) -> BeaconResult<()> {
+        match self.store.delete(&self.object_path(cf, key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(BeaconError::storage(format!("object store delete failed: {}", e))),
+        }
+    }
+
+    async fn scan_prefix(&self, cf: &str, prefix: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        use futures::TryStreamExt;
+
+        let cf_path = object_store::path::Path::from(format!("{}/{}", self.prefix, cf));
+        let mut stream = self.store.list(Some(&cf_path));
+        let mut entries = Vec::new();
+
+        while let Some(meta) = stream
+            .try_next()
+            .await
+            .map_err(|e| BeaconError::storage(format!("object store list failed: {}", e)))?
+        {
+            let Some(key_hex) = meta.location.filename() else {
+                continue;
+            };
+            let Ok(key) = hex::decode(key_hex) else {
+                continue;
+            };
+            if !key.starts_with(prefix) {
+                continue;
+            }
+
+            let value = self.get(cf, &key).await?.unwrap_or_default();
+            entries.push((key, value));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
+    async fn scan_range(&self, cf: &str, start: &[u8], end: &[u8]) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        use futures::TryStreamExt;
+
+        let cf_path = object_store::path::Path::from(format!("{}/{}", self.prefix, cf));
+        let mut stream = self.store.list(Some(&cf_path));
+        let mut entries = Vec::new();
+
+        while let Some(meta) = stream
+            .try_next()
+            .await
+            .map_err(|e| BeaconError::storage(format!("object store list failed: {}", e)))?
+        {
+            let Some(key_hex) = meta.location.filename() else {
+                continue;
+            };
+            let Ok(key) = hex::decode(key_hex) else {
+                continue;
+            };
+            if key.as_slice() < start || key.as_slice() >= end {
+                continue;
+            }
+
+            let value = self.get(cf, &key).await?.unwrap_or_default();
+            entries.push((key, value));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
+    async fn batch(&self, ops: Vec<BatchOp>) -> BeaconResult<()> {
+        for op in ops {
+            match op {
+                BatchOp::Put { cf, key, value } => self.put(&cf, &key, &value).await?,
+                BatchOp::Delete { cf, key } => self.delete(&cf, &key).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn range_query(&self, query: &RangeQuery) -> BeaconResult<RangeResult> {
+        use futures::TryStreamExt;
+
+        let cf_path = object_store::path::Path::from(format!("{}/{}", self.prefix, query.cf));
+        let mut stream = self.store.list(Some(&cf_path));
+        let mut entries = Vec::new();
+
+        while let Some(meta) = stream
+            .try_next()
+            .await
+            .map_err(|e| BeaconError::storage(format!("object store list failed: {}", e)))?
+        {
+            let Some(key_hex) = meta.location.filename() else {
+                continue;
+            };
+            let Ok(key) = hex::decode(key_hex) else {
+                continue;
+            };
+            if !key.starts_with(&query.prefix) {
+                continue;
+            }
+            if let Some(start) = &query.start {
+                if key.as_slice() < start.as_slice() {
+                    continue;
+                }
+            }
+            if let Some(end) = &query.end {
+                if key.as_slice() >= end.as_slice() {
+                    continue;
+                }
+            }
+
+            let value = self.get(&query.cf, &key).await?.unwrap_or_default();
+            entries.push((key, value));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        if query.reverse {
+            entries.reverse();
+        }
+
+        let next_start = if entries.len() > query.limit {
+            let cursor = entries[query.limit].0.clone();
+            entries.truncate(query.limit);
+            Some(cursor)
+        } else {
+            None
+        };
+
+        Ok(RangeResult { items: entries, next_start })
+    }
+
+    async fn maintenance(&self) -> BeaconResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_backend_get_put_delete() {
+        let backend = InMemoryBackend::new();
+
+        backend.put("state", b"key1", b"value1").await.unwrap();
+        assert_eq!(backend.get("state", b"key1").await.unwrap(), Some(b"value1".to_vec()));
+
+        backend.delete("state", b"key1").await.unwrap();
+        assert_eq!(backend.get("state", b"key1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_backend_scan_prefix_is_ordered() {
+        let backend = InMemoryBackend::new();
+
+        backend.put("state", b"state:b", b"2").await.unwrap();
+        backend.put("state", b"state:a", b"1").await.unwrap();
+        backend.put("state", b"other:a", b"ignored").await.unwrap();
+
+        let results = backend.scan_prefix("state", b"state:").await.unwrap();
+        assert_eq!(
+            results,
+            vec![(b"state:a".to_vec(), b"1".to_vec()), (b"state:b".to_vec(), b"2".to_vec())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_backend_batch_is_atomic_in_effect() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .batch(vec![
+                BatchOp::put("state", b"a".to_vec(), b"1".to_vec()),
+                BatchOp::put("state", b"b".to_vec(), b"2".to_vec()),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(backend.get("state", b"a").await.unwrap(), Some(b"1".to_vec()));
+        assert_eq!(backend.get("state", b"b").await.unwrap(), Some(b"2".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_backend_range_query_paginates() {
+        let backend = InMemoryBackend::new();
+
+        for i in 0..5u8 {
+            backend.put("state", &[b's', i], &[i]).await.unwrap();
+        }
+
+        let first = backend
+            .range_query(&RangeQuery::new("state", vec![b's'], 2))
+            .await
+            .unwrap();
+        assert_eq!(first.items, vec![(vec![b's', 0], vec![0]), (vec![b's', 1], vec![1])]);
+        let cursor = first.next_start.expect("more pages remain");
+
+        let second = backend
+            .range_query(&RangeQuery::new("state", vec![b's'], 2).with_bounds(Some(cursor), None))
+            .await
+            .unwrap();
+        assert_eq!(second.items, vec![(vec![b's', 2], vec![2]), (vec![b's', 3], vec![3])]);
+        assert!(second.next_start.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_backend_range_query_reverse() {
+        let backend = InMemoryBackend::new();
+
+        for i in 0..3u8 {
+            backend.put("state", &[b's', i], &[i]).await.unwrap();
+        }
+
+        let result = backend
+            .range_query(&RangeQuery::new("state", vec![b's'], 10).reversed())
+            .await
+            .unwrap();
+        assert_eq!(
+            result.items,
+            vec![(vec![b's', 2], vec![2]), (vec![b's', 1], vec![1]), (vec![b's', 0], vec![0])]
+        );
+        assert_eq!(result.next_start, None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_backend_batch_write_is_batch_alias() {
+        let backend = InMemoryBackend::new();
+
+        backend
+            .batch_write(vec![BatchOp::put("state", b"a".to_vec(), b"1".to_vec())])
+            .await
+            .unwrap();
+
+        assert_eq!(backend.get("state", b"a").await.unwrap(), Some(b"1".to_vec()));
+    }
+}
@@ -1,59 +1,188 @@
-use crate::{Database, Keys, CF_BLOCKS};
-use beacon_core::{BeaconResult, Block, BlockIndex};
+use crate::{BatchOp, Keys, StorageBackend, CF_BLOCKS, CF_INDICES, CF_METADATA};
+use beacon_core::{BeaconResult, Block, BlockIndex, Transaction};
+use lru::LruCache;
 use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Default number of deserialized blocks / hash->index mappings to keep cached
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
 
 /// Blockchain storage manager
 pub struct BlockchainStorage {
-    db: Arc<Database>,
+    db: Arc<dyn StorageBackend>,
+    /// Cache of deserialized blocks by index, populated on reads and on `store_block`
+    block_cache: Mutex<LruCache<BlockIndex, Arc<Block>>>,
+    /// Cache mapping block hash to index, so `get_block_by_hash` can skip to the cached block
+    hash_to_index: Mutex<LruCache<String, BlockIndex>>,
 }
 
 impl BlockchainStorage {
     /// Create a new blockchain storage instance
-    pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<dyn StorageBackend>) -> Self {
+        Self::with_cache_capacity(db, DEFAULT_CACHE_CAPACITY)
     }
 
-    /// Store a block
+    /// Create a new blockchain storage instance with a custom block-cache capacity
+    pub fn with_cache_capacity(db: Arc<dyn StorageBackend>, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap());
+        Self {
+            db,
+            block_cache: Mutex::new(LruCache::new(capacity)),
+            hash_to_index: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Store a block, keyed primarily by hash so competing blocks can coexist
+    /// at the same height. If `block` extends the current canonical head (or
+    /// is the genesis block), the `block:<index>` canonical pointer is
+    /// advanced to it; otherwise it's recorded as a side-branch block that
+    /// `reorganize` can later promote to canonical.
     pub async fn store_block(&self, block: &Block) -> BeaconResult<()> {
         let block_data = bincode::serialize(block)?;
-        let block_key = Keys::block(block.header.index);
-        let hash_key = Keys::block_hash(&block.hash);
+        let data_key = Keys::block_data(&block.hash);
 
-        // Create a batch to store both the block and its hash index atomically
-        let mut batch = self.db.create_batch();
-        batch.put_cf(self.db.cf_handle(CF_BLOCKS)?, &block_key, &block_data);
-        batch.put_cf(self.db.cf_handle(CF_BLOCKS)?, &hash_key, &block.header.index.to_le_bytes());
+        let mut ops = vec![BatchOp::put(CF_BLOCKS, data_key, block_data)];
 
-        self.db.write_batch(batch)?;
+        let extends_canonical_head = match self.get_latest_block().await? {
+            Some(head) => {
+                block.header.index == head.header.index + 1 && block.header.previous_hash == head.hash
+            }
+            None => block.header.index == 0,
+        };
+        if extends_canonical_head {
+            ops.push(BatchOp::put(
+                CF_BLOCKS,
+                Keys::block(block.header.index),
+                block.hash.as_bytes().to_vec(),
+            ));
+            ops.push(BatchOp::put(
+                CF_METADATA,
+                Keys::metadata("best_index"),
+                block.header.index.to_string().into_bytes(),
+            ));
+            ops.extend(self.transaction_location_ops(block)?);
+        }
+
+        self.db.batch(ops).await?;
+        self.cache_block(block).await;
         tracing::debug!("Stored block {} with hash {}", block.header.index, block.hash);
 
         Ok(())
     }
 
-    /// Get a block by index
+    /// Seed local state directly at `block` without requiring it to extend
+    /// the existing canonical chain, for checkpoint-sync bootstrap: a fresh
+    /// node trusts a checkpoint from a remote peer's HTTP API and starts
+    /// syncing forward from there instead of replaying the whole chain from
+    /// genesis. Unlike `store_block`, the canonical pointer is always
+    /// advanced to this block, regardless of what (if anything) precedes it
+    /// in local storage.
+    pub async fn store_checkpoint_block(&self, block: &Block) -> BeaconResult<()> {
+        let block_data = bincode::serialize(block)?;
+        let data_key = Keys::block_data(&block.hash);
+
+        let ops = vec![
+            BatchOp::put(CF_BLOCKS, data_key, block_data),
+            BatchOp::put(CF_BLOCKS, Keys::block(block.header.index), block.hash.as_bytes().to_vec()),
+            BatchOp::put(CF_METADATA, Keys::metadata("best_index"), block.header.index.to_string().into_bytes()),
+        ];
+
+        self.db.batch(ops).await?;
+        self.cache_block(block).await;
+        tracing::info!("Seeded checkpoint block {} (hash {}) as canonical head", block.header.index, block.hash);
+
+        Ok(())
+    }
+
+    /// Insert a block into the index/hash caches
+    async fn cache_block(&self, block: &Block) {
+        self.block_cache
+            .lock()
+            .await
+            .put(block.header.index, Arc::new(block.clone()));
+        self.hash_to_index
+            .lock()
+            .await
+            .put(block.hash.clone(), block.header.index);
+    }
+
+    /// Get a block by index, following the canonical chain pointer
     pub async fn get_block_by_index(&self, index: BlockIndex) -> BeaconResult<Option<Block>> {
+        if let Some(block) = self.block_cache.lock().await.get(&index) {
+            return Ok(Some((**block).clone()));
+        }
+
         let key = Keys::block(index);
-        if let Some(data) = self.db.get_cf(CF_BLOCKS, &key)? {
+        let Some(hash_bytes) = self.db.get(CF_BLOCKS, &key).await? else {
+            return Ok(None);
+        };
+        let hash = String::from_utf8(hash_bytes)
+            .map_err(|_| beacon_core::BeaconError::storage("Invalid canonical block pointer"))?;
+        self.get_block_by_hash(&hash).await
+    }
+
+    /// Get a block by hash, canonical or not
+    pub async fn get_block_by_hash(&self, hash: &str) -> BeaconResult<Option<Block>> {
+        if let Some(index) = self.hash_to_index.lock().await.get(hash).copied() {
+            if let Some(block) = self.block_cache.lock().await.get(&index) {
+                return Ok(Some((**block).clone()));
+            }
+        }
+
+        let key = Keys::block_data(hash);
+        if let Some(data) = self.db.get(CF_BLOCKS, &key).await? {
             let block: Block = bincode::deserialize(&data)?;
+            self.cache_block(&block).await;
             Ok(Some(block))
         } else {
             Ok(None)
         }
     }
 
-    /// Get a block by hash
-    pub async fn get_block_by_hash(&self, hash: &str) -> BeaconResult<Option<Block>> {
-        let hash_key = Keys::block_hash(hash);
-        if let Some(index_data) = self.db.get_cf(CF_BLOCKS, &hash_key)? {
-            let index = BlockIndex::from_le_bytes(
-                index_data.try_into()
-                    .map_err(|_| beacon_core::BeaconError::storage("Invalid block index data"))?
-            );
-            self.get_block_by_index(index).await
-        } else {
-            Ok(None)
-        }
+    /// Build the batch ops that record each of `block`'s transactions at their
+    /// `{ block_index, position_in_block }` address, OpenEthereum-`TransactionAddress`-style.
+    fn transaction_location_ops(&self, block: &Block) -> BeaconResult<Vec<BatchOp>> {
+        block
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(tx_index, tx)| {
+                let location = bincode::serialize(&(block.header.index, tx_index))?;
+                Ok(BatchOp::put(
+                    CF_INDICES,
+                    Keys::transaction_location(tx.id.as_str()),
+                    location,
+                ))
+            })
+            .collect()
+    }
+
+    /// Look up which block contains a transaction and its position within it
+    pub async fn get_transaction_location(&self, tx_hash: &str) -> BeaconResult<Option<(BlockIndex, usize)>> {
+        let key = Keys::transaction_location(tx_hash);
+        let Some(data) = self.db.get(CF_INDICES, &key).await? else {
+            return Ok(None);
+        };
+        let location: (BlockIndex, usize) = bincode::deserialize(&data)?;
+        Ok(Some(location))
+    }
+
+    /// Get a transaction by hash via the transaction-location index, without
+    /// scanning every block
+    pub async fn get_transaction(&self, tx_hash: &str) -> BeaconResult<Option<(Transaction, BlockIndex)>> {
+        let Some((block_index, tx_index)) = self.get_transaction_location(tx_hash).await? else {
+            return Ok(None);
+        };
+        let Some(block) = self.get_block_by_index(block_index).await? else {
+            return Ok(None);
+        };
+        Ok(block
+            .transactions
+            .into_iter()
+            .nth(tx_index)
+            .map(|tx| (tx, block_index)))
     }
 
     /// Get the latest block
@@ -66,31 +195,19 @@ impl BlockchainStorage {
         }
     }
 
-    /// Get the latest block index
+    /// Get the latest block index, from the `meta:best_index` pointer kept up
+    /// to date by `store_block`/`reorganize` rather than scanning the CF
     pub async fn get_latest_block_index(&self) -> BeaconResult<Option<BlockIndex>> {
-        // Iterate backwards through possible block indices
-        // In a real implementation, we'd store this as metadata
-        let mut iter = self.db.iter_cf_mode(CF_BLOCKS, rocksdb::IteratorMode::End)?;
-        
-        while let Some(result) = iter.next() {
-            match result {
-                Ok((key, _)) => {
-                    let key_str = String::from_utf8_lossy(&key);
-                    if key_str.starts_with("block:") {
-                        let index_str = &key_str[6..]; // Remove "block:" prefix
-                        if let Ok(index) = index_str.parse::<BlockIndex>() {
-                            return Ok(Some(index));
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating blocks: {}", e);
-                    break;
-                }
-            }
-        }
-        
-        Ok(None)
+        let key = Keys::metadata("best_index");
+        let Some(data) = self.db.get(CF_METADATA, &key).await? else {
+            return Ok(None);
+        };
+        let index_str = String::from_utf8(data)
+            .map_err(|_| beacon_core::BeaconError::storage("Invalid best_index metadata"))?;
+        index_str
+            .parse::<BlockIndex>()
+            .map(Some)
+            .map_err(|_| beacon_core::BeaconError::storage("Invalid best_index metadata"))
     }
 
     /// Get block count
@@ -105,7 +222,7 @@ impl BlockchainStorage {
     /// Check if a block exists
     pub async fn block_exists(&self, index: BlockIndex) -> BeaconResult<bool> {
         let key = Keys::block(index);
-        Ok(self.db.get_cf(CF_BLOCKS, &key)?.is_some())
+        Ok(self.db.get(CF_BLOCKS, &key).await?.is_some())
     }
 
     /// Get multiple blocks in a range
@@ -142,6 +259,122 @@ impl BlockchainStorage {
     }
 }
 
+/// The result of walking two branches back to their common ancestor, modeled
+/// on OpenEthereum's `TreeRoute`/`ImportRoute`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeRoute {
+    /// Hash of the common ancestor block
+    pub ancestor: String,
+    /// Blocks to undo, ordered old head -> ancestor (exclusive of the ancestor)
+    pub retracted: Vec<String>,
+    /// Blocks to apply, ordered ancestor -> new head (exclusive of the ancestor)
+    pub enacted: Vec<String>,
+}
+
+impl BlockchainStorage {
+    /// Find the common ancestor of `from_hash` and `to_hash` by walking both
+    /// branches back via `previous_hash`: first bring the deeper block up to
+    /// the shallower block's height, then step both branches back together
+    /// until their hashes match.
+    pub async fn tree_route(&self, from_hash: &str, to_hash: &str) -> BeaconResult<TreeRoute> {
+        let mut from_cursor = self.get_block_by_hash(from_hash).await?.ok_or_else(|| {
+            beacon_core::BeaconError::storage(format!("tree_route: unknown block {}", from_hash))
+        })?;
+        let mut to_cursor = self.get_block_by_hash(to_hash).await?.ok_or_else(|| {
+            beacon_core::BeaconError::storage(format!("tree_route: unknown block {}", to_hash))
+        })?;
+
+        let mut retracted = Vec::new();
+        let mut enacted = Vec::new();
+
+        while from_cursor.header.index > to_cursor.header.index {
+            retracted.push(from_cursor.hash.clone());
+            from_cursor = self.previous_block(&from_cursor).await?;
+        }
+        while to_cursor.header.index > from_cursor.header.index {
+            enacted.push(to_cursor.hash.clone());
+            to_cursor = self.previous_block(&to_cursor).await?;
+        }
+
+        while from_cursor.hash != to_cursor.hash {
+            retracted.push(from_cursor.hash.clone());
+            enacted.push(to_cursor.hash.clone());
+            from_cursor = self.previous_block(&from_cursor).await?;
+            to_cursor = self.previous_block(&to_cursor).await?;
+        }
+
+        enacted.reverse();
+
+        Ok(TreeRoute {
+            ancestor: from_cursor.hash,
+            retracted,
+            enacted,
+        })
+    }
+
+    /// Follow a block's `previous_hash` to its parent
+    async fn previous_block(&self, block: &Block) -> BeaconResult<Block> {
+        self.get_block_by_hash(&block.header.previous_hash).await?.ok_or_else(|| {
+            beacon_core::BeaconError::storage(format!(
+                "tree_route: chain broken before block {} (missing parent {})",
+                block.header.index, block.header.previous_hash
+            ))
+        })
+    }
+
+    /// Reorganize the canonical chain onto `new_head`: compute the tree route
+    /// from the current head and recompute the `block:<index>` pointers over
+    /// the enacted path. Returns the route so callers can undo/redo the state
+    /// changes associated with the retracted/enacted blocks.
+    pub async fn reorganize(&self, new_head: &str) -> BeaconResult<TreeRoute> {
+        let current_head = self
+            .get_latest_block()
+            .await?
+            .ok_or_else(|| beacon_core::BeaconError::storage("reorganize: no canonical head to reorganize from"))?;
+
+        let route = self.tree_route(&current_head.hash, new_head).await?;
+
+        let mut ops = Vec::new();
+        for hash in &route.retracted {
+            let block = self.get_block_by_hash(hash).await?.ok_or_else(|| {
+                beacon_core::BeaconError::storage(format!("reorganize: missing retracted block {}", hash))
+            })?;
+            ops.extend(
+                block
+                    .transactions
+                    .iter()
+                    .map(|tx| BatchOp::delete(CF_INDICES, Keys::transaction_location(tx.id.as_str()))),
+            );
+        }
+        let mut new_head_index = current_head.header.index;
+        for hash in &route.enacted {
+            let block = self.get_block_by_hash(hash).await?.ok_or_else(|| {
+                beacon_core::BeaconError::storage(format!("reorganize: missing enacted block {}", hash))
+            })?;
+            ops.push(BatchOp::put(CF_BLOCKS, Keys::block(block.header.index), hash.as_bytes().to_vec()));
+            ops.extend(self.transaction_location_ops(&block)?);
+            new_head_index = block.header.index;
+            self.cache_block(&block).await;
+        }
+        ops.push(BatchOp::put(
+            CF_METADATA,
+            Keys::metadata("best_index"),
+            new_head_index.to_string().into_bytes(),
+        ));
+        self.db.batch(ops).await?;
+
+        tracing::info!(
+            "Reorganized canonical chain to {} ({} retracted, {} enacted, ancestor {})",
+            new_head,
+            route.retracted.len(),
+            route.enacted.len(),
+            route.ancestor
+        );
+
+        Ok(route)
+    }
+}
+
 /// Blockchain statistics
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlockchainStats {
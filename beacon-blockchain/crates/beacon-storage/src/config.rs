@@ -14,6 +14,8 @@ pub struct StorageConfig {
     pub background_sync: bool,
     /// Sync interval in seconds
     pub sync_interval: u64,
+    /// Number of deserialized transactions to keep in `TransactionStorage`'s in-memory cache
+    pub transaction_cache_size: usize,
 }
 
 impl Default for StorageConfig {
@@ -24,6 +26,7 @@ impl Default for StorageConfig {
             compaction_interval: 3600, // 1 hour
             background_sync: true,
             sync_interval: 300, // 5 minutes
+            transaction_cache_size: 1024,
         }
     }
 }
@@ -10,6 +10,8 @@ pub const CF_TRANSACTIONS: &str = "transactions";
 pub const CF_STATE: &str = "state";
 pub const CF_METADATA: &str = "metadata";
 pub const CF_INDICES: &str = "indices";
+pub const CF_PEERS: &str = "peers";
+pub const CF_SLASHING: &str = "slashing";
 
 /// Database configuration
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -26,6 +28,9 @@ pub struct DatabaseConfig {
     pub max_open_files: i32,
     /// Enable statistics
     pub enable_statistics: bool,
+    /// Max entries kept in the in-memory read-through cache in front of
+    /// `get_cf`. `0` disables the cache.
+    pub read_cache_entries: usize,
 }
 
 impl Default for DatabaseConfig {
@@ -37,14 +42,26 @@ impl Default for DatabaseConfig {
             write_buffer_size: 64, // 64 MB
             max_open_files: 1000,
             enable_statistics: true,
+            read_cache_entries: 10_000,
         }
     }
 }
 
+/// Hit/miss counters for the read-through cache, snapshotted from the
+/// `Database`'s atomic counters.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
 /// Database wrapper for RocksDB
 pub struct Database {
     db: Arc<DB>,
     config: DatabaseConfig,
+    read_cache: Option<std::sync::Mutex<lru::LruCache<(String, Vec<u8>), Vec<u8>>>>,
+    cache_hits: std::sync::atomic::AtomicU64,
+    cache_misses: std::sync::atomic::AtomicU64,
 }
 
 impl Database {
@@ -90,6 +107,8 @@ impl Database {
             ColumnFamilyDescriptor::new(CF_STATE, Options::default()),
             ColumnFamilyDescriptor::new(CF_METADATA, Options::default()),
             ColumnFamilyDescriptor::new(CF_INDICES, Options::default()),
+            ColumnFamilyDescriptor::new(CF_PEERS, Options::default()),
+            ColumnFamilyDescriptor::new(CF_SLASHING, Options::default()),
         ];
 
         // Open database with column families
@@ -98,9 +117,15 @@ impl Database {
 
         info!("Database opened successfully");
 
+        let read_cache = std::num::NonZeroUsize::new(config.read_cache_entries)
+            .map(|capacity| std::sync::Mutex::new(lru::LruCache::new(capacity)));
+
         Ok(Self {
             db: Arc::new(db),
             config,
+            read_cache,
+            cache_hits: std::sync::atomic::AtomicU64::new(0),
+            cache_misses: std::sync::atomic::AtomicU64::new(0),
         })
     }
 
@@ -109,6 +134,11 @@ impl Database {
         &self.db
     }
 
+    /// Get the configuration the database was opened with
+    pub fn config(&self) -> &DatabaseConfig {
+        &self.config
+    }
+
     /// Get a column family handle
     pub fn cf_handle(&self, cf_name: &str) -> BeaconResult<&ColumnFamily> {
         self.db
@@ -128,7 +158,13 @@ impl Database {
         let cf = self.cf_handle(cf_name)?;
         self.db
             .put_cf(cf, key, value)
-            .map_err(|e| BeaconError::storage(format!("Failed to put data in CF '{}': {}", cf_name, e)))
+            .map_err(|e| BeaconError::storage(format!("Failed to put data in CF '{}': {}", cf_name, e)))?;
+
+        if let Some(cache) = &self.read_cache {
+            cache.lock().unwrap().put((cf_name.to_string(), key.to_vec()), value.to_vec());
+        }
+
+        Ok(())
     }
 
     /// Get a value by key from the default column family
@@ -138,12 +174,29 @@ impl Database {
             .map_err(|e| BeaconError::storage(format!("Failed to get data: {}", e)))
     }
 
-    /// Get a value by key from a specific column family
+    /// Get a value by key from a specific column family. Checked against the
+    /// read-through cache first; on a miss, the value fetched from RocksDB
+    /// populates the cache for next time.
     pub fn get_cf(&self, cf_name: &str, key: &[u8]) -> BeaconResult<Option<Vec<u8>>> {
+        if let Some(cache) = &self.read_cache {
+            let cache_key = (cf_name.to_string(), key.to_vec());
+            if let Some(value) = cache.lock().unwrap().get(&cache_key) {
+                self.cache_hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                return Ok(Some(value.clone()));
+            }
+            self.cache_misses.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
         let cf = self.cf_handle(cf_name)?;
-        self.db
+        let value = self.db
             .get_cf(cf, key)
-            .map_err(|e| BeaconError::storage(format!("Failed to get data from CF '{}': {}", cf_name, e)))
+            .map_err(|e| BeaconError::storage(format!("Failed to get data from CF '{}': {}", cf_name, e)))?;
+
+        if let (Some(cache), Some(value)) = (&self.read_cache, &value) {
+            cache.lock().unwrap().put((cf_name.to_string(), key.to_vec()), value.clone());
+        }
+
+        Ok(value)
     }
 
     /// Delete a key from the default column family
@@ -158,7 +211,13 @@ impl Database {
         let cf = self.cf_handle(cf_name)?;
         self.db
             .delete_cf(cf, key)
-            .map_err(|e| BeaconError::storage(format!("Failed to delete data from CF '{}': {}", cf_name, e)))
+            .map_err(|e| BeaconError::storage(format!("Failed to delete data from CF '{}': {}", cf_name, e)))?;
+
+        if let Some(cache) = &self.read_cache {
+            cache.lock().unwrap().pop(&(cf_name.to_string(), key.to_vec()));
+        }
+
+        Ok(())
     }
 
     /// Create a write batch for atomic operations
@@ -166,11 +225,42 @@ impl Database {
         WriteBatch::default()
     }
 
-    /// Write a batch atomically
+    /// Write a batch atomically. Bulk writes aren't tracked key-by-key, so
+    /// rather than introspect the batch this conservatively drops the whole
+    /// read cache - safe, and batched writes are rare enough that the next
+    /// round of `get_cf` misses repopulating it is not a concern.
     pub fn write_batch(&self, batch: WriteBatch) -> BeaconResult<()> {
         self.db
             .write(batch)
-            .map_err(|e| BeaconError::storage(format!("Failed to write batch: {}", e)))
+            .map_err(|e| BeaconError::storage(format!("Failed to write batch: {}", e)))?;
+
+        if let Some(cache) = &self.read_cache {
+            cache.lock().unwrap().clear();
+        }
+
+        Ok(())
+    }
+
+    /// Snapshot of the read-through cache's hit/miss counters.
+    pub fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.cache_hits.load(std::sync::atomic::Ordering::Relaxed),
+            misses: self.cache_misses.load(std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+
+    /// How many entries the read-through cache currently holds per column
+    /// family (e.g. `"blocks"` vs `"state"`), so operators can see which
+    /// workload - block/header lookups or state reads - is actually using
+    /// the shared cache budget.
+    pub fn cache_entries_by_cf(&self) -> std::collections::HashMap<String, usize> {
+        let mut counts = std::collections::HashMap::new();
+        if let Some(cache) = &self.read_cache {
+            for (key, _) in cache.lock().unwrap().iter() {
+                *counts.entry(key.0.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
     }
 
     /// Create an iterator over a column family
@@ -205,7 +295,7 @@ impl Database {
 
     /// Compact all column families
     pub fn compact_all(&self) -> BeaconResult<()> {
-        let cf_names = vec![CF_BLOCKS, CF_TRANSACTIONS, CF_STATE, CF_METADATA, CF_INDICES];
+        let cf_names = vec![CF_BLOCKS, CF_TRANSACTIONS, CF_STATE, CF_METADATA, CF_INDICES, CF_PEERS, CF_SLASHING];
         
         for cf_name in cf_names {
             self.compact_cf(cf_name)?;
@@ -217,23 +307,84 @@ impl Database {
 
     /// Create a checkpoint (backup)
     pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> BeaconResult<()> {
+        let path = path.as_ref();
         let checkpoint = rocksdb::checkpoint::Checkpoint::new(&self.db)
             .map_err(|e| BeaconError::storage(format!("Failed to create checkpoint object: {}", e)))?;
-        
+
         checkpoint
             .create_checkpoint(path)
             .map_err(|e| BeaconError::storage(format!("Failed to create checkpoint: {}", e)))?;
-        
+
+        write_checkpoint_manifest(path)?;
+
         info!("Created database checkpoint");
         Ok(())
     }
 
+    /// Recompute every file's hash against `manifest.json` in a checkpoint
+    /// directory and return the files that don't match (empty if the
+    /// checkpoint is intact). Run this before `restore_from_checkpoint` to
+    /// catch corruption in a cold backup before trusting it.
+    pub fn verify_checkpoint<P: AsRef<Path>>(path: P) -> BeaconResult<Vec<CheckpointMismatch>> {
+        let path = path.as_ref();
+        let manifest = read_checkpoint_manifest(path)?;
+        let mut mismatches = Vec::new();
+
+        for entry in &manifest.files {
+            let file_path = path.join(&entry.name);
+            let Ok(bytes) = std::fs::read(&file_path) else {
+                mismatches.push(CheckpointMismatch {
+                    file: entry.name.clone(),
+                    reason: "file missing".to_string(),
+                });
+                continue;
+            };
+
+            if bytes.len() as u64 != entry.size {
+                mismatches.push(CheckpointMismatch {
+                    file: entry.name.clone(),
+                    reason: format!("size mismatch: expected {}, found {}", entry.size, bytes.len()),
+                });
+                continue;
+            }
+
+            let hash = beacon_core::hash_message(&bytes);
+            if hash != entry.sha256 {
+                mismatches.push(CheckpointMismatch {
+                    file: entry.name.clone(),
+                    reason: "content hash mismatch".to_string(),
+                });
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Open a checkpoint directory as a `Database`, after verifying its
+    /// manifest. Refuses to open a checkpoint with any mismatch so a
+    /// corrupted cold backup can't silently be brought back online.
+    pub fn restore_from_checkpoint<P: AsRef<Path>>(path: P, config: DatabaseConfig) -> BeaconResult<Database> {
+        let path = path.as_ref();
+        let mismatches = Self::verify_checkpoint(path)?;
+        if !mismatches.is_empty() {
+            return Err(BeaconError::storage(format!(
+                "checkpoint at {} failed verification: {:?}",
+                path.display(),
+                mismatches
+            )));
+        }
+
+        let restore_config = DatabaseConfig { path: path.to_string_lossy().to_string(), ..config };
+        info!("Restoring database from verified checkpoint at {}", path.display());
+        Database::open(restore_config)
+    }
+
     /// Get database size information
     pub fn get_size_info(&self) -> BeaconResult<DatabaseSizeInfo> {
         let mut total_size = 0u64;
         let mut cf_sizes = std::collections::HashMap::new();
 
-        let cf_names = vec![CF_BLOCKS, CF_TRANSACTIONS, CF_STATE, CF_METADATA, CF_INDICES];
+        let cf_names = vec![CF_BLOCKS, CF_TRANSACTIONS, CF_STATE, CF_METADATA, CF_INDICES, CF_PEERS, CF_SLASHING];
         
         for cf_name in cf_names {
             if let Ok(Some(size_str)) = self.db.property_value_cf(
@@ -287,18 +438,89 @@ pub struct DatabaseSizeInfo {
     pub cf_sizes: std::collections::HashMap<String, u64>,
 }
 
+/// One file recorded in a checkpoint's `manifest.json`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CheckpointManifestEntry {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Integrity manifest written alongside a checkpoint by `create_checkpoint`,
+/// recording every SST/CURRENT/etc. file's size and content hash so the
+/// checkpoint can be verified before it's trusted as a restore source.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CheckpointManifest {
+    pub files: Vec<CheckpointManifestEntry>,
+}
+
+const CHECKPOINT_MANIFEST_FILE: &str = "manifest.json";
+
+/// A file in a checkpoint that failed to verify against its manifest entry.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CheckpointMismatch {
+    pub file: String,
+    pub reason: String,
+}
+
+/// Walk every file RocksDB wrote into the checkpoint directory (SST files,
+/// `CURRENT`, `MANIFEST-*`, `OPTIONS-*`, ...) and write `manifest.json`
+/// recording each one's size and SHA-256 hash.
+fn write_checkpoint_manifest(checkpoint_dir: &Path) -> BeaconResult<()> {
+    let mut files = Vec::new();
+
+    for entry in std::fs::read_dir(checkpoint_dir)
+        .map_err(|e| BeaconError::storage(format!("Failed to read checkpoint directory: {}", e)))?
+    {
+        let entry = entry.map_err(|e| BeaconError::storage(format!("Failed to read checkpoint entry: {}", e)))?;
+        if !entry.path().is_file() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        let bytes = std::fs::read(entry.path())
+            .map_err(|e| BeaconError::storage(format!("Failed to read checkpoint file '{}': {}", name, e)))?;
+
+        files.push(CheckpointManifestEntry {
+            size: bytes.len() as u64,
+            sha256: beacon_core::hash_message(&bytes),
+            name,
+        });
+    }
+
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let manifest = CheckpointManifest { files };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| BeaconError::storage(format!("Failed to serialize checkpoint manifest: {}", e)))?;
+
+    std::fs::write(checkpoint_dir.join(CHECKPOINT_MANIFEST_FILE), manifest_json)
+        .map_err(|e| BeaconError::storage(format!("Failed to write checkpoint manifest: {}", e)))
+}
+
+fn read_checkpoint_manifest(checkpoint_dir: &Path) -> BeaconResult<CheckpointManifest> {
+    let manifest_json = std::fs::read_to_string(checkpoint_dir.join(CHECKPOINT_MANIFEST_FILE))
+        .map_err(|e| BeaconError::storage(format!("Failed to read checkpoint manifest: {}", e)))?;
+
+    serde_json::from_str(&manifest_json)
+        .map_err(|e| BeaconError::storage(format!("Failed to parse checkpoint manifest: {}", e)))
+}
+
 /// Database key builders for consistent key formatting
 pub struct Keys;
 
 impl Keys {
-    /// Block key: "block:{index}"
+    /// Canonical chain pointer key: "block:{index}". Holds the hash of the
+    /// canonical block at that height, not the block itself.
     pub fn block(index: u64) -> Vec<u8> {
         format!("block:{:020}", index).into_bytes()
     }
 
-    /// Block hash key: "block_hash:{hash}"
-    pub fn block_hash(hash: &str) -> Vec<u8> {
-        format!("block_hash:{}", hash).into_bytes()
+    /// Block data key: "block_data:{hash}". Blocks are stored primarily by
+    /// hash so multiple blocks can coexist at the same height during a fork;
+    /// `block(index)` only points at the canonical block for that height.
+    pub fn block_data(hash: &str) -> Vec<u8> {
+        format!("block_data:{}", hash).into_bytes()
     }
 
     /// Transaction key: "tx:{tx_id}"
@@ -311,6 +533,20 @@ impl Keys {
         format!("tx_block:{:020}:{:010}", block_index, tx_index).into_bytes()
     }
 
+    /// Transaction-by-sender secondary index key: "tx_sender:{sender}:{tx_id}".
+    /// Lets `get_transactions_by_sender` do a prefix scan instead of walking
+    /// every transaction in `CF_TRANSACTIONS`.
+    pub fn transaction_by_sender(sender: &str, tx_id: &str) -> Vec<u8> {
+        format!("tx_sender:{}:{}", sender, tx_id).into_bytes()
+    }
+
+    /// Transaction-by-timestamp secondary index key: "tx_ts:{timestamp}:{tx_id}".
+    /// Zero-padded so lexicographic key order matches chronological order,
+    /// letting `get_recent_transactions` do a bounded reverse range scan.
+    pub fn transaction_by_timestamp(timestamp: u64, tx_id: &str) -> Vec<u8> {
+        format!("tx_ts:{:020}:{}", timestamp, tx_id).into_bytes()
+    }
+
     /// State key: "state:{key}"
     pub fn state(key: &str) -> Vec<u8> {
         format!("state:{}", key).into_bytes()
@@ -325,6 +561,80 @@ impl Keys {
     pub fn index(index_type: &str, value: &str) -> Vec<u8> {
         format!("index:{}:{}", index_type, value).into_bytes()
     }
+
+    /// Transaction location key: "tx_loc:{tx_hash}". Maps a transaction hash
+    /// to its `{ block_index, position_in_block }` address, OpenEthereum-style.
+    pub fn transaction_location(tx_hash: &str) -> Vec<u8> {
+        format!("tx_loc:{}", tx_hash).into_bytes()
+    }
+
+    /// Named state snapshot key: "snapshot:{id}". Holds a full copy of
+    /// `CF_STATE` at the time `create_snapshot` was called.
+    pub fn snapshot(snapshot_id: &str) -> Vec<u8> {
+        format!("snapshot:{}", snapshot_id).into_bytes()
+    }
+
+    /// Per-block state journal key: "state_journal:{block_hash}". Holds the
+    /// inverse write-set (previous value, or `None` for a fresh key) for every
+    /// state key touched while committing that block, so retracting it during
+    /// a reorg can be undone exactly.
+    pub fn state_journal(block_hash: &str) -> Vec<u8> {
+        format!("state_journal:{}", block_hash).into_bytes()
+    }
+
+    /// Per-key last-write provenance key: "state_provenance:{key}". Holds the
+    /// `(block_index, timestamp)` of the most recent block that wrote `key`,
+    /// so relative-timelocked transactions can be checked against it.
+    pub fn state_provenance(key: &str) -> Vec<u8> {
+        format!("state_provenance:{}", key).into_bytes()
+    }
+
+    /// Per-key MVCC version key: "state_version:{key}". Holds a
+    /// monotonically increasing sequence number bumped every time `key` is
+    /// written, for read-set conflict detection.
+    pub fn state_version(key: &str) -> Vec<u8> {
+        format!("state_version:{}", key).into_bytes()
+    }
+
+    /// Peer record key: "peer:{peer_id_hex}". Holds a bincode-serialized
+    /// `PeerInfo` snapshot from `beacon-networking`'s `PeerManager`, keyed by
+    /// the hex encoding of the peer's `PeerId` bytes (which aren't valid
+    /// UTF-8 on their own).
+    pub fn peer(peer_id_hex: &str) -> Vec<u8> {
+        format!("peer:{}", peer_id_hex).into_bytes()
+    }
+
+    /// Peer ban expiry key: "peer_ban:{peer_id_hex}". Holds the
+    /// bincode-encoded unix-seconds timestamp the ban expires at, stored
+    /// under a separate prefix from `peer` so a ban can outlive (or be
+    /// cleared independently of) the `PeerInfo` record itself.
+    pub fn peer_ban(peer_id_hex: &str) -> Vec<u8> {
+        format!("peer_ban:{}", peer_id_hex).into_bytes()
+    }
+
+    /// Discovered-peer record key: "discovered_peer:{peer_id_hex}". Holds a
+    /// bincode-serialized `DiscoveredPeer` from `beacon-networking`'s
+    /// `PeerDiscovery`, stored under its own prefix in `CF_PEERS` so
+    /// discovery-phase candidates don't collide with `PeerManager`'s own
+    /// `peer`/`peer_ban` records in the same column family.
+    pub fn discovered_peer(peer_id_hex: &str) -> Vec<u8> {
+        format!("discovered_peer:{}", peer_id_hex).into_bytes()
+    }
+
+    /// Discovery ban expiry key: "discovery_ban:{peer_id_hex}". Holds the
+    /// bincode-encoded unix-seconds timestamp the discovery-phase ban
+    /// expires at - see `peer_ban` for the equivalent on connected peers.
+    pub fn discovery_ban(peer_id_hex: &str) -> Vec<u8> {
+        format!("discovery_ban:{}", peer_id_hex).into_bytes()
+    }
+
+    /// Slashing-evidence store key: "slashing:{validator_id}:{height:020}".
+    /// Zero-padded height keeps entries for the same validator in ascending
+    /// height order, which `beacon-consensus`'s `Slasher` relies on to prune
+    /// everything below a cutoff height with a single prefix-bounded scan.
+    pub fn slashing_evidence(validator_id: &str, height: u64) -> Vec<u8> {
+        format!("slashing:{}:{:020}", validator_id, height).into_bytes()
+    }
 }
 
 #[cfg(test)]
@@ -366,5 +676,50 @@ mod tests {
         assert_eq!(Keys::block(123), b"block:00000000000000000123".to_vec());
         assert_eq!(Keys::transaction("tx123"), b"tx:tx123".to_vec());
         assert_eq!(Keys::state("balance:addr1"), b"state:balance:addr1".to_vec());
+        assert_eq!(Keys::transaction_by_sender("addr1", "tx123"), b"tx_sender:addr1:tx123".to_vec());
+        assert_eq!(
+            Keys::transaction_by_timestamp(42, "tx123"),
+            b"tx_ts:00000000000000000042:tx123".to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_verify_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(DatabaseConfig {
+            path: temp_dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        db.put_cf(CF_STATE, b"key", b"value").unwrap();
+
+        let checkpoint_dir = TempDir::new().unwrap();
+        let checkpoint_path = checkpoint_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+
+        assert!(Database::verify_checkpoint(&checkpoint_path).unwrap().is_empty());
+
+        let restored = Database::restore_from_checkpoint(&checkpoint_path, DatabaseConfig::default()).unwrap();
+        assert_eq!(restored.get_cf(CF_STATE, b"key").unwrap(), Some(b"value".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_verify_detects_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(DatabaseConfig {
+            path: temp_dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        db.put_cf(CF_STATE, b"key", b"value").unwrap();
+
+        let checkpoint_dir = TempDir::new().unwrap();
+        let checkpoint_path = checkpoint_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+
+        std::fs::write(checkpoint_path.join("CURRENT"), b"tampered").unwrap();
+
+        let mismatches = Database::verify_checkpoint(&checkpoint_path).unwrap();
+        assert!(!mismatches.is_empty());
     }
 }
@@ -1,10 +1,12 @@
 pub mod database;
+pub mod backend;
 pub mod blockchain;
 pub mod state;
 pub mod transactions;
 pub mod config;
 
 pub use database::*;
+pub use backend::*;
 pub use blockchain::*;
 pub use state::*;
 pub use transactions::*;
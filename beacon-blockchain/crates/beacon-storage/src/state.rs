@@ -1,37 +1,46 @@
-use crate::{Database, Keys, CF_STATE};
-use beacon_core::{BeaconResult, StateKey, StateValue, StateMap};
+use crate::{prefix_upper_bound, BatchOp, Keys, StorageBackend, CF_METADATA, CF_STATE};
+use beacon_core::{
+    BeaconResult, BlockIndex, MerkleProofStep, MerkleTree, SparseMerkleProof, SparseMerkleTree,
+    StateKey, StateValue, StateMap, Timestamp,
+};
+use std::collections::HashSet;
 use std::sync::Arc;
 
+/// Length of the `"state:"` db-key prefix every `Keys::state` key starts with
+const STATE_KEY_PREFIX_LEN: usize = 6;
+
 /// State storage manager
 pub struct StateStorage {
-    db: Arc<Database>,
+    db: Arc<dyn StorageBackend>,
 }
 
 impl StateStorage {
     /// Create a new state storage instance
-    pub fn new(db: Arc<Database>) -> Self {
+    pub fn new(db: Arc<dyn StorageBackend>) -> Self {
         Self { db }
     }
 
     /// Get a value from state
     pub async fn get_state(&self, key: &StateKey) -> BeaconResult<Option<StateValue>> {
         let db_key = Keys::state(key);
-        self.db.get_cf(CF_STATE, &db_key)
+        self.db.get(CF_STATE, &db_key).await
     }
 
     /// Set a value in state
     pub async fn set_state(&self, key: StateKey, value: StateValue) -> BeaconResult<()> {
         let db_key = Keys::state(&key);
-        self.db.put_cf(CF_STATE, &db_key, &value)?;
+        self.db.put(CF_STATE, &db_key, &value).await?;
         tracing::trace!("Set state: {} = {} bytes", key, value.len());
+        self.bump_versions(std::iter::once(&key)).await?;
         Ok(())
     }
 
     /// Delete a value from state
     pub async fn delete_state(&self, key: &StateKey) -> BeaconResult<()> {
         let db_key = Keys::state(key);
-        self.db.delete_cf(CF_STATE, &db_key)?;
+        self.db.delete(CF_STATE, &db_key).await?;
         tracing::trace!("Deleted state: {}", key);
+        self.bump_versions(std::iter::once(key)).await?;
         Ok(())
     }
 
@@ -41,19 +50,93 @@ impl StateStorage {
             return Ok(());
         }
 
-        let mut batch = self.db.create_batch();
-        let cf = self.db.cf_handle(CF_STATE)?;
+        let ops = changes
+            .iter()
+            .map(|(key, value)| BatchOp::put(CF_STATE, Keys::state(key), value.clone()))
+            .collect();
 
-        for (key, value) in changes {
-            let db_key = Keys::state(key);
-            batch.put_cf(cf, &db_key, value);
+        self.db.batch(ops).await?;
+        tracing::debug!("Applied {} state changes", changes.len());
+        self.bump_versions(changes.keys()).await?;
+        Ok(())
+    }
+
+    /// Apply a mixed batch of puts and deletes atomically in a single
+    /// `StorageBackend::batch` call - either every operation lands or none
+    /// do. `None` for a key's value means delete.
+    pub async fn apply_batch(&self, ops: Vec<(StateKey, Option<StateValue>)>) -> BeaconResult<()> {
+        if ops.is_empty() {
+            return Ok(());
         }
 
-        self.db.write_batch(batch)?;
-        tracing::debug!("Applied {} state changes", changes.len());
+        let batch_ops: Vec<BatchOp> = ops
+            .iter()
+            .map(|(key, value)| match value {
+                Some(value) => BatchOp::put(CF_STATE, Keys::state(key), value.clone()),
+                None => BatchOp::delete(CF_STATE, Keys::state(key)),
+            })
+            .collect();
+
+        let op_count = batch_ops.len();
+        self.db.batch(batch_ops).await?;
+        tracing::debug!("Applied atomic batch of {} state operation(s)", op_count);
+        self.bump_versions(ops.iter().map(|(key, _)| key)).await?;
         Ok(())
     }
 
+    /// Bump the MVCC version of each of `keys`, plus the global
+    /// `state_version`, by one. Called after a batch of writes has already
+    /// landed, so every keyed write path - `set_state`, `delete_state`,
+    /// `apply_state_changes`, `apply_batch`, `apply_state_changes_for_block`,
+    /// `undo_block` - is reflected in the versions
+    /// `key_version`/`state_version` report. Not folded into the same
+    /// backend batch as the write itself (an honest simplification, like
+    /// `BlockImportPipeline::commit`'s separate `state_root` write); a crash
+    /// between the two leaves a version stale rather than wrong-but-advanced.
+    async fn bump_versions<'a>(&self, keys: impl Iterator<Item = &'a StateKey>) -> BeaconResult<()> {
+        let mut ops = Vec::new();
+        for key in keys {
+            let next = self.key_version(key).await? + 1;
+            ops.push(BatchOp::put(CF_METADATA, Keys::state_version(key), next.to_le_bytes().to_vec()));
+        }
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        let next_global = self.state_version().await? + 1;
+        ops.push(BatchOp::put(
+            CF_METADATA,
+            Keys::metadata("global_state_version"),
+            next_global.to_le_bytes().to_vec(),
+        ));
+
+        self.db.batch(ops).await
+    }
+
+    /// Current MVCC version of `key`: a monotonically increasing sequence
+    /// number bumped every time the key is written. Used for read-set
+    /// conflict detection in the chaincode shim's simulate-then-validate
+    /// flow. A never-written key is version 0.
+    pub async fn key_version(&self, key: &StateKey) -> BeaconResult<u64> {
+        match self.db.get(CF_METADATA, &Keys::state_version(key)).await? {
+            Some(data) if data.len() == 8 => Ok(u64::from_le_bytes(data.try_into().unwrap())),
+            _ => Ok(0),
+        }
+    }
+
+    /// Version of `CF_STATE` as a whole - bumped alongside every individual
+    /// key's version by every keyed write (see `bump_versions`);
+    /// `restore_snapshot` and `clear_all_state` do not bump it. Used as a range/prefix read's phantom
+    /// boundary: if this changes, a key may have been inserted into or
+    /// removed from a previously-read range even though no individual key
+    /// the read actually returned changed its own version.
+    pub async fn state_version(&self) -> BeaconResult<u64> {
+        match self.db.get(CF_METADATA, &Keys::metadata("global_state_version")).await? {
+            Some(data) if data.len() == 8 => Ok(u64::from_le_bytes(data.try_into().unwrap())),
+            _ => Ok(0),
+        }
+    }
+
     /// Get multiple state values
     pub async fn get_state_batch(&self, keys: &[StateKey]) -> BeaconResult<StateMap> {
         let mut result = StateMap::new();
@@ -67,45 +150,38 @@ impl StateStorage {
         Ok(result)
     }
 
-    /// Get all state with a given prefix
-    pub async fn get_state_with_prefix(&self, prefix: &str) -> BeaconResult<StateMap> {
-        let mut result = StateMap::new();
+    /// Get all state with a given prefix. Keys are returned as raw bytes
+    /// (stripped of the `"state:"` db prefix only) rather than a lossily
+    /// decoded `String`, so binary state keys survive the round trip intact.
+    pub async fn get_state_with_prefix(&self, prefix: &str) -> BeaconResult<Vec<(Vec<u8>, StateValue)>> {
         let db_prefix = Keys::state(prefix);
-        
-        let iter = self.db.iter_cf_mode(
-            CF_STATE,
-            rocksdb::IteratorMode::From(&db_prefix, rocksdb::Direction::Forward)
-        )?;
-
-        for item in iter {
-            match item {
-                Ok((key, value)) => {
-                    if key.starts_with(&db_prefix) {
-                        // Extract the original state key by removing the "state:" prefix
-                        let key_str = String::from_utf8_lossy(&key);
-                        if key_str.starts_with("state:") {
-                            let state_key = key_str[6..].to_string(); // Remove "state:" prefix
-                            result.insert(state_key, value.to_vec());
-                        }
-                    } else {
-                        // We've gone past the prefix
-                        break;
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating state with prefix {}: {}", prefix, e);
-                    break;
-                }
-            }
-        }
-        
-        Ok(result)
+        let entries = match prefix_upper_bound(&db_prefix) {
+            Some(upper_bound) => self.db.scan_range(CF_STATE, &db_prefix, &upper_bound).await?,
+            None => self.db.scan_prefix(CF_STATE, &db_prefix).await?,
+        };
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, value)| (key[STATE_KEY_PREFIX_LEN..].to_vec(), value))
+            .collect())
+    }
+
+    /// Paginated variant of `get_state_with_prefix`: skips past `start_after`
+    /// (if given) and returns at most `limit` entries plus whether more remain.
+    pub async fn get_state_with_prefix_page(
+        &self,
+        prefix: &str,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> BeaconResult<(Vec<(Vec<u8>, StateValue)>, bool)> {
+        let entries = self.get_state_with_prefix(prefix).await?;
+        Ok(paginate(entries, start_after, limit))
     }
 
     /// Check if a state key exists
     pub async fn state_exists(&self, key: &StateKey) -> BeaconResult<bool> {
         let db_key = Keys::state(key);
-        Ok(self.db.get_cf(CF_STATE, &db_key)?.is_some())
+        Ok(self.db.get(CF_STATE, &db_key).await?.is_some())
     }
 
     /// Get the size of a state value
@@ -117,40 +193,291 @@ impl StateStorage {
         }
     }
 
-    /// Create a state snapshot (for rollback purposes)
+    /// Capture every `(db_key, value)` pair currently in `CF_STATE`
+    async fn capture_state(&self) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.db.scan_prefix(CF_STATE, b"state:").await
+    }
+
+    /// Create a named state snapshot: a full copy of `CF_STATE` recorded
+    /// under `snapshot:<id>`, restorable later with `restore_snapshot`
     pub async fn create_snapshot(&self, snapshot_id: &str) -> BeaconResult<()> {
-        // In a real implementation, this would create a consistent snapshot
-        // For now, we'll just log it
+        let entries = self.capture_state().await?;
+        let data = bincode::serialize(&entries)?;
+
+        let mut order = self.snapshot_ids().await?;
+        if !order.iter().any(|id| id == snapshot_id) {
+            order.push(snapshot_id.to_string());
+        }
+
+        self.db
+            .batch(vec![
+                BatchOp::put(CF_METADATA, Keys::snapshot(snapshot_id), data),
+                BatchOp::put(CF_METADATA, Keys::metadata("snapshot_order"), bincode::serialize(&order)?),
+            ])
+            .await?;
         tracing::info!("Created state snapshot: {}", snapshot_id);
         Ok(())
     }
 
-    /// Restore from a state snapshot
+    /// Restore `CF_STATE` to exactly what it held when `snapshot_id` was created
     pub async fn restore_snapshot(&self, snapshot_id: &str) -> BeaconResult<()> {
-        // In a real implementation, this would restore from a snapshot
+        let key = Keys::snapshot(snapshot_id);
+        let data = self.db.get(CF_METADATA, &key).await?.ok_or_else(|| {
+            beacon_core::BeaconError::storage(format!("Unknown state snapshot: {}", snapshot_id))
+        })?;
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = bincode::deserialize(&data)?;
+
+        let mut ops: Vec<BatchOp> = self
+            .capture_state()
+            .await?
+            .into_iter()
+            .map(|(key, _)| BatchOp::delete(CF_STATE, key))
+            .collect();
+        ops.extend(entries.into_iter().map(|(key, value)| BatchOp::put(CF_STATE, key, value)));
+
+        self.db.batch(ops).await?;
         tracing::info!("Restored state snapshot: {}", snapshot_id);
         Ok(())
     }
 
-    /// Clear all state (dangerous!)
-    pub async fn clear_all_state(&self) -> BeaconResult<()> {
-        let iter = self.db.iter_cf(CF_STATE)?;
-        let mut batch = self.db.create_batch();
-        let cf = self.db.cf_handle(CF_STATE)?;
-
-        for item in iter {
-            match item {
-                Ok((key, _)) => {
-                    batch.delete_cf(cf, &key);
-                }
-                Err(e) => {
-                    tracing::error!("Error clearing state: {}", e);
-                    break;
-                }
+    /// List the ids of all named snapshots, oldest first
+    pub async fn snapshot_ids(&self) -> BeaconResult<Vec<String>> {
+        let key = Keys::metadata("snapshot_order");
+        match self.db.get(CF_METADATA, &key).await? {
+            Some(data) => Ok(bincode::deserialize(&data)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Evict all but the `keep_depth` most recently created snapshots
+    pub async fn evict_snapshots_older_than(&self, keep_depth: usize) -> BeaconResult<()> {
+        let order = self.snapshot_ids().await?;
+        if order.len() <= keep_depth {
+            return Ok(());
+        }
+
+        let split = order.len() - keep_depth;
+        let (evicted, kept) = order.split_at(split);
+
+        let mut ops: Vec<BatchOp> = evicted
+            .iter()
+            .map(|id| BatchOp::delete(CF_METADATA, Keys::snapshot(id)))
+            .collect();
+        ops.push(BatchOp::put(
+            CF_METADATA,
+            Keys::metadata("snapshot_order"),
+            bincode::serialize(&kept.to_vec())?,
+        ));
+
+        self.db.batch(ops).await?;
+        tracing::debug!("Evicted {} state snapshot(s), keeping {}", evicted.len(), kept.len());
+        Ok(())
+    }
+
+    /// Apply state changes produced while committing `block_hash`, recording
+    /// the inverse write-set (previous value, or `None` for a freshly-created
+    /// key) so `undo_block` can later replay it to exactly undo this block's
+    /// effects during a reorg. Safe to call multiple times for the same block
+    /// (e.g. once per transaction) — only the first previous value seen for
+    /// each key is kept, so undoing restores the pre-block state.
+    pub async fn apply_state_changes_for_block(&self, block_hash: &str, changes: &StateMap) -> BeaconResult<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let journal_key = Keys::state_journal(block_hash);
+        let mut journal: Vec<(StateKey, Option<StateValue>)> = match self.db.get(CF_METADATA, &journal_key).await? {
+            Some(data) => bincode::deserialize(&data)?,
+            None => Vec::new(),
+        };
+
+        let already_journaled: HashSet<StateKey> = journal.iter().map(|(key, _)| key.clone()).collect();
+        for key in changes.keys() {
+            if !already_journaled.contains(key) {
+                let previous = self.get_state(key).await?;
+                journal.push((key.clone(), previous));
             }
         }
 
-        self.db.write_batch(batch)?;
+        let mut ops = vec![BatchOp::put(CF_METADATA, journal_key, bincode::serialize(&journal)?)];
+        ops.extend(
+            changes
+                .iter()
+                .map(|(key, value)| BatchOp::put(CF_STATE, Keys::state(key), value.clone())),
+        );
+
+        self.db.batch(ops).await?;
+        self.bump_versions(changes.keys()).await?;
+        tracing::debug!(
+            "Applied {} state changes for block {} (journaled for rollback)",
+            changes.len(),
+            block_hash
+        );
+        Ok(())
+    }
+
+    /// Re-key a journal written under `provisional_key` to `block_hash`, once
+    /// the block's real hash is known. The pipeline journals state changes as
+    /// they're applied per-transaction, before `consensus.create_block` has
+    /// assembled the block and before its hash - which depends on
+    /// `state_root`/`state_smt_root`, themselves only known after execution -
+    /// has been computed; it journals under the prospective block index
+    /// instead and promotes the journal here once `commit` has the final
+    /// hash. A no-op if no state changes were journaled under
+    /// `provisional_key` (e.g. a block with no state-touching transactions).
+    pub async fn finalize_block_journal(&self, provisional_key: &str, block_hash: &str) -> BeaconResult<()> {
+        let provisional = Keys::state_journal(provisional_key);
+        let Some(data) = self.db.get(CF_METADATA, &provisional).await? else {
+            return Ok(());
+        };
+
+        let ops = vec![
+            BatchOp::put(CF_METADATA, Keys::state_journal(block_hash), data),
+            BatchOp::delete(CF_METADATA, provisional),
+        ];
+        self.db.batch(ops).await?;
+        tracing::debug!("Finalized state journal for block {} (was {})", block_hash, provisional_key);
+        Ok(())
+    }
+
+    /// Undo the state changes recorded for `block_hash` by `apply_state_changes_for_block`,
+    /// restoring every touched key to its pre-block value (or deleting it if the
+    /// block created it). Called when a block is retracted during a reorg.
+    pub async fn undo_block(&self, block_hash: &str) -> BeaconResult<()> {
+        let journal_key = Keys::state_journal(block_hash);
+        let Some(data) = self.db.get(CF_METADATA, &journal_key).await? else {
+            return Ok(());
+        };
+        let journal: Vec<(StateKey, Option<StateValue>)> = bincode::deserialize(&data)?;
+        let touched: Vec<StateKey> = journal.iter().map(|(key, _)| key.clone()).collect();
+
+        let mut ops: Vec<BatchOp> = journal
+            .into_iter()
+            .map(|(key, previous)| match previous {
+                Some(value) => BatchOp::put(CF_STATE, Keys::state(&key), value),
+                None => BatchOp::delete(CF_STATE, Keys::state(&key)),
+            })
+            .collect();
+        ops.push(BatchOp::delete(CF_METADATA, journal_key));
+
+        self.db.batch(ops).await?;
+        self.bump_versions(touched.iter()).await?;
+        tracing::info!("Undid state changes for retracted block {}", block_hash);
+        Ok(())
+    }
+
+    /// Merkle root committing to the entire current state, for recording in
+    /// `BlockHeader.metadata`'s `"state_root"` entry. Each leaf hashes a
+    /// `(db_key, value)` pair, in key order, so the root changes if any
+    /// key's value changes or if a key is added or removed. State isn't
+    /// partitioned by channel in storage today, so this covers all of
+    /// `CF_STATE` rather than a single channel.
+    pub async fn state_root(&self) -> BeaconResult<String> {
+        let leaves: Vec<Vec<u8>> = self
+            .capture_state()
+            .await?
+            .into_iter()
+            .map(|(key, value)| [key, value].concat())
+            .collect();
+        Ok(MerkleTree::new(&leaves).root())
+    }
+
+    /// Build an inclusion proof for `key`'s current value against
+    /// `state_root()`, so an external verifier can confirm the value without
+    /// trusting the node. Returns `None` if the key doesn't exist.
+    pub async fn state_proof(&self, key: &StateKey) -> BeaconResult<Option<(StateValue, Vec<MerkleProofStep>, String)>> {
+        let db_key = Keys::state(key);
+        let entries = self.capture_state().await?;
+        let Some(index) = entries.iter().position(|(k, _)| k == &db_key) else {
+            return Ok(None);
+        };
+
+        let value = entries[index].1.clone();
+        let leaves: Vec<Vec<u8>> = entries
+            .into_iter()
+            .map(|(key, value)| [key, value].concat())
+            .collect();
+        let tree = MerkleTree::new(&leaves);
+        let proof = tree.generate_proof(index).expect("index came from this same entry list");
+
+        Ok(Some((value, proof, tree.root())))
+    }
+
+    /// Sparse Merkle root committing to the entire current state, for
+    /// recording in `BlockHeader.metadata`'s `"state_smt_root"` entry.
+    /// Unlike `state_root()`'s flat tree, this is a fixed-depth binary
+    /// sparse Merkle tree (see `SparseMerkleTree`), which is what lets
+    /// `state_smt_proof` answer non-membership as well as membership.
+    pub async fn state_smt_root(&self) -> BeaconResult<String> {
+        Ok(self.smt().await?.root())
+    }
+
+    /// Build a membership or non-membership proof for `key` against
+    /// `state_smt_root()`, so a light client can trustlessly verify either
+    /// that `key` holds a given value or that it's absent from state
+    /// entirely, without replaying the chain or trusting the queried node.
+    pub async fn state_smt_proof(&self, key: &StateKey) -> BeaconResult<(SparseMerkleProof, String)> {
+        let tree = self.smt().await?;
+        let db_key = Keys::state(key);
+        Ok((tree.prove(&db_key), tree.root()))
+    }
+
+    /// Build the sparse Merkle tree over every `(db_key, value)` pair
+    /// currently in `CF_STATE`. Rebuilt fresh on each call, same as
+    /// `state_root()` - see its doc comment for why that's fine here.
+    async fn smt(&self) -> BeaconResult<SparseMerkleTree> {
+        Ok(SparseMerkleTree::new(&self.capture_state().await?))
+    }
+
+    /// Look up `(block_index, timestamp)` of the most recent block that wrote
+    /// `key`, as recorded by `record_provenance`. Returns `None` if the key
+    /// has never been written, which relative-timelocked transactions treat
+    /// as "no lock to satisfy".
+    pub async fn key_provenance(&self, key: &StateKey) -> BeaconResult<Option<(BlockIndex, Timestamp)>> {
+        let db_key = Keys::state_provenance(key);
+        match self.db.get(CF_METADATA, &db_key).await? {
+            Some(data) => Ok(Some(bincode::deserialize(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record that `keys` were written by the block at `block_index` with
+    /// timestamp `block_timestamp`, for later relative-timelock checks via
+    /// `key_provenance`. Called once per committed block, after its state
+    /// changes have already been applied.
+    pub async fn record_provenance(
+        &self,
+        keys: impl Iterator<Item = StateKey>,
+        block_index: BlockIndex,
+        block_timestamp: Timestamp,
+    ) -> BeaconResult<()> {
+        let ops: Vec<BatchOp> = keys
+            .map(|key| {
+                BatchOp::put(
+                    CF_METADATA,
+                    Keys::state_provenance(&key),
+                    bincode::serialize(&(block_index, block_timestamp)).unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        if ops.is_empty() {
+            return Ok(());
+        }
+        self.db.batch(ops).await
+    }
+
+    /// Clear all state (dangerous!)
+    pub async fn clear_all_state(&self) -> BeaconResult<()> {
+        let ops = self
+            .db
+            .scan_prefix(CF_STATE, b"")
+            .await?
+            .into_iter()
+            .map(|(key, _)| BatchOp::delete(CF_STATE, key))
+            .collect();
+
+        self.db.batch(ops).await?;
         tracing::warn!("Cleared all state data");
         Ok(())
     }
@@ -228,39 +555,49 @@ impl StateStorage {
     }
 
     /// Get state values within a key range
-    pub async fn get_state_range(&self, start_key: &str, end_key: &str) -> BeaconResult<Vec<(String, Vec<u8>)>> {
-        let mut result = Vec::new();
+    pub async fn get_state_range(&self, start_key: &str, end_key: &str) -> BeaconResult<Vec<(Vec<u8>, Vec<u8>)>> {
         let db_start = Keys::state(start_key);
         let db_end = Keys::state(end_key);
-        
-        let iter = self.db.iter_cf_mode(
-            CF_STATE,
-            rocksdb::IteratorMode::From(&db_start, rocksdb::Direction::Forward)
-        )?;
-
-        for item in iter {
-            match item {
-                Ok((key, value)) => {
-                    if key.as_ref() >= db_end.as_slice() {
-                        // We've reached the end of the range
-                        break;
-                    }
-                    
-                    // Extract the original state key by removing the "state:" prefix
-                    let key_str = String::from_utf8_lossy(&key);
-                    if key_str.starts_with("state:") {
-                        let state_key = key_str[6..].to_string(); // Remove "state:" prefix
-                        result.push((state_key, value.to_vec()));
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating state range {} to {}: {}", start_key, end_key, e);
-                    break;
-                }
-            }
-        }
+
+        let result: Vec<(Vec<u8>, Vec<u8>)> = self
+            .db
+            .scan_range(CF_STATE, &db_start, &db_end)
+            .await?
+            .into_iter()
+            .map(|(key, value)| (key[STATE_KEY_PREFIX_LEN..].to_vec(), value))
+            .collect();
 
         tracing::debug!("Found {} state entries in range {} to {}", result.len(), start_key, end_key);
         Ok(result)
     }
+
+    /// Paginated variant of `get_state_range`: skips past `start_after` (if
+    /// given) and returns at most `limit` entries plus whether more remain.
+    pub async fn get_state_range_page(
+        &self,
+        start_key: &str,
+        end_key: &str,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> BeaconResult<(Vec<(Vec<u8>, Vec<u8>)>, bool)> {
+        let entries = self.get_state_range(start_key, end_key).await?;
+        Ok(paginate(entries, start_after, limit))
+    }
+}
+
+/// Skip past `start_after` (exclusive) in an already-sorted `(key, value)`
+/// list, then return at most `limit` entries plus whether more remain beyond them
+fn paginate<V>(entries: Vec<(Vec<u8>, V)>, start_after: Option<&[u8]>, limit: usize) -> (Vec<(Vec<u8>, V)>, bool) {
+    let start_index = match start_after {
+        Some(after) => entries
+            .iter()
+            .position(|(key, _)| key.as_slice() > after)
+            .unwrap_or(entries.len()),
+        None => 0,
+    };
+
+    let mut page: Vec<(Vec<u8>, V)> = entries.into_iter().skip(start_index).collect();
+    let has_more = page.len() > limit;
+    page.truncate(limit);
+    (page, has_more)
 }
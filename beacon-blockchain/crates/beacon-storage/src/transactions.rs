@@ -1,16 +1,60 @@
-use crate::{Database, Keys, CF_TRANSACTIONS, CF_INDICES};
+use crate::{BatchOp, Keys, RangeQuery, StorageBackend, CF_TRANSACTIONS, CF_INDICES};
 use beacon_core::{BeaconResult, Transaction, TransactionId, TransactionResult, BlockIndex};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Default number of deserialized transactions to keep in the in-memory cache
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
 
 /// Transaction storage manager
 pub struct TransactionStorage {
-    db: Arc<Database>,
+    db: Arc<dyn StorageBackend>,
+    /// Cache of deserialized transactions by ID, populated on `store_transaction*`
+    /// and `get_transaction`, invalidated on `delete_transaction`
+    cache: Mutex<LruCache<TransactionId, Arc<Transaction>>>,
+    /// Running total of stored transactions, adjusted on store/delete so
+    /// `get_transaction_count` doesn't need a full column-family scan
+    tx_count: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
 }
 
 impl TransactionStorage {
     /// Create a new transaction storage instance
-    pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<dyn StorageBackend>) -> Self {
+        Self::with_cache_capacity(db, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Create a new transaction storage instance with a custom cache capacity
+    pub fn with_cache_capacity(db: Arc<dyn StorageBackend>, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(DEFAULT_CACHE_CAPACITY).unwrap());
+        Self {
+            db,
+            cache: Mutex::new(LruCache::new(capacity)),
+            tx_count: AtomicU64::new(0),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Insert a transaction into the read cache (write-through)
+    async fn cache_transaction(&self, transaction: &Transaction) {
+        self.cache
+            .lock()
+            .await
+            .put(transaction.id.clone(), Arc::new(transaction.clone()));
+    }
+
+    /// Get cache hit/miss counters for this instance
+    pub fn cache_stats(&self) -> TransactionCacheStats {
+        TransactionCacheStats {
+            hits: self.cache_hits.load(Ordering::Relaxed),
+            misses: self.cache_misses.load(Ordering::Relaxed),
+        }
     }
 
     /// Store a transaction
@@ -18,7 +62,9 @@ impl TransactionStorage {
         let tx_data = bincode::serialize(transaction)?;
         let tx_key = Keys::transaction(transaction.id.as_str());
 
-        self.db.put_cf(CF_TRANSACTIONS, &tx_key, &tx_data)?;
+        self.db.put(CF_TRANSACTIONS, &tx_key, &tx_data).await?;
+        self.cache_transaction(transaction).await;
+        self.tx_count.fetch_add(1, Ordering::Relaxed);
         tracing::debug!("Stored transaction: {}", transaction.id.as_str());
 
         Ok(())
@@ -37,21 +83,22 @@ impl TransactionStorage {
         let tx_key = Keys::transaction(transaction.id.as_str());
         let result_key = format!("{}:result", transaction.id.as_str());
         let block_index_key = Keys::transaction_by_block(block_index, tx_index);
-
-        let mut batch = self.db.create_batch();
-        let tx_cf = self.db.cf_handle(CF_TRANSACTIONS)?;
-        let idx_cf = self.db.cf_handle(CF_INDICES)?;
-
-        // Store transaction
-        batch.put_cf(tx_cf, &tx_key, &tx_data);
-        
-        // Store transaction result
-        batch.put_cf(tx_cf, result_key.as_bytes(), &result_data);
-        
-        // Store block index reference
-        batch.put_cf(idx_cf, &block_index_key, transaction.id.as_str().as_bytes());
-
-        self.db.write_batch(batch)?;
+        let sender_index_key = Keys::transaction_by_sender(transaction.from.as_str(), transaction.id.as_str());
+        let timestamp_index_key =
+            Keys::transaction_by_timestamp(transaction.timestamp.0, transaction.id.as_str());
+        let tx_id_bytes = transaction.id.as_str().as_bytes().to_vec();
+
+        self.db
+            .batch(vec![
+                BatchOp::put(CF_TRANSACTIONS, tx_key, tx_data),
+                BatchOp::put(CF_TRANSACTIONS, result_key.into_bytes(), result_data),
+                BatchOp::put(CF_INDICES, block_index_key, tx_id_bytes.clone()),
+                BatchOp::put(CF_INDICES, sender_index_key, tx_id_bytes.clone()),
+                BatchOp::put(CF_INDICES, timestamp_index_key, tx_id_bytes),
+            ])
+            .await?;
+        self.cache_transaction(transaction).await;
+        self.tx_count.fetch_add(1, Ordering::Relaxed);
         tracing::debug!(
             "Stored transaction {} with result in block {} at index {}",
             transaction.id.as_str(),
@@ -64,9 +111,16 @@ impl TransactionStorage {
 
     /// Get a transaction by ID
     pub async fn get_transaction(&self, tx_id: &TransactionId) -> BeaconResult<Option<Transaction>> {
+        if let Some(transaction) = self.cache.lock().await.get(tx_id) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some((**transaction).clone()));
+        }
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+
         let tx_key = Keys::transaction(tx_id.as_str());
-        if let Some(data) = self.db.get_cf(CF_TRANSACTIONS, &tx_key)? {
+        if let Some(data) = self.db.get(CF_TRANSACTIONS, &tx_key).await? {
             let transaction: Transaction = bincode::deserialize(&data)?;
+            self.cache_transaction(&transaction).await;
             Ok(Some(transaction))
         } else {
             Ok(None)
@@ -76,7 +130,7 @@ impl TransactionStorage {
     /// Get a transaction result by transaction ID
     pub async fn get_transaction_result(&self, tx_id: &TransactionId) -> BeaconResult<Option<TransactionResult>> {
         let result_key = format!("{}:result", tx_id.as_str());
-        if let Some(data) = self.db.get_cf(CF_TRANSACTIONS, result_key.as_bytes())? {
+        if let Some(data) = self.db.get(CF_TRANSACTIONS, result_key.as_bytes()).await? {
             let result: TransactionResult = bincode::deserialize(&data)?;
             Ok(Some(result))
         } else {
@@ -84,75 +138,49 @@ impl TransactionStorage {
         }
     }
 
-    /// Get all transactions in a block
+    /// Get all transactions in a block, ordered by their position in the block
     pub async fn get_transactions_in_block(&self, block_index: BlockIndex) -> BeaconResult<Vec<Transaction>> {
-        let mut transactions = Vec::new();
         let prefix = format!("tx_block:{:020}:", block_index);
-        let prefix_bytes = prefix.as_bytes();
-
-        let iter = self.db.iter_cf_mode(
-            CF_INDICES,
-            rocksdb::IteratorMode::From(prefix_bytes, rocksdb::Direction::Forward)
-        )?;
-
-        for item in iter {
-            match item {
-                Ok((key, value)) => {
-                    if key.starts_with(prefix_bytes) {
-                        let tx_id_str = String::from_utf8_lossy(&value);
-                        let tx_id = TransactionId::from_string(tx_id_str.to_string());
-                        if let Some(transaction) = self.get_transaction(&tx_id).await? {
-                            transactions.push(transaction);
-                        }
-                    } else {
-                        break; // We've gone past the prefix
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating transactions in block {}: {}", block_index, e);
-                    break;
-                }
+        let mut indexed = Vec::new();
+
+        for (key, value) in self.db.scan_prefix(CF_INDICES, prefix.as_bytes()).await? {
+            // Trailing ":{:010}" segment of "tx_block:{block_index}:{tx_index}" is the
+            // transaction's position in the block; parse it back out rather than relying
+            // on scan order, since the index row doesn't carry it in the value.
+            let key_str = String::from_utf8_lossy(&key);
+            let tx_index: usize = key_str
+                .rsplit(':')
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+
+            let tx_id_str = String::from_utf8_lossy(&value);
+            let tx_id = TransactionId::from_string(tx_id_str.to_string());
+            if let Some(transaction) = self.get_transaction(&tx_id).await? {
+                indexed.push((tx_index, transaction));
             }
         }
 
-        // Sort by transaction index in block
-        transactions.sort_by_key(|_tx| {
-            // Extract tx_index from the database iteration order
-            // This is a simplified approach - in production, you'd store the index explicitly
-            0 // For now, maintain original order
-        });
-
-        Ok(transactions)
+        indexed.sort_by_key(|(tx_index, _)| *tx_index);
+        Ok(indexed.into_iter().map(|(_, tx)| tx).collect())
     }
 
     /// Check if a transaction exists
     pub async fn transaction_exists(&self, tx_id: &TransactionId) -> BeaconResult<bool> {
         let tx_key = Keys::transaction(tx_id.as_str());
-        Ok(self.db.get_cf(CF_TRANSACTIONS, &tx_key)?.is_some())
+        Ok(self.db.get(CF_TRANSACTIONS, &tx_key).await?.is_some())
     }
 
     /// Get transactions by sender address
     pub async fn get_transactions_by_sender(&self, sender: &str) -> BeaconResult<Vec<Transaction>> {
+        let prefix = format!("tx_sender:{}:", sender);
         let mut transactions = Vec::new();
-        let iter = self.db.iter_cf(CF_TRANSACTIONS)?;
-
-        for item in iter {
-            match item {
-                Ok((key, value)) => {
-                    let key_str = String::from_utf8_lossy(&key);
-                    // Only process transaction keys (not result keys)
-                    if key_str.starts_with("tx:") && !key_str.contains(":result") {
-                        if let Ok(transaction) = bincode::deserialize::<Transaction>(&value) {
-                            if transaction.from.as_str() == sender {
-                                transactions.push(transaction);
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating transactions by sender: {}", e);
-                    break;
-                }
+
+        for (_, value) in self.db.scan_prefix(CF_INDICES, prefix.as_bytes()).await? {
+            let tx_id_str = String::from_utf8_lossy(&value);
+            let tx_id = TransactionId::from_string(tx_id_str.to_string());
+            if let Some(transaction) = self.get_transaction(&tx_id).await? {
+                transactions.push(transaction);
             }
         }
 
@@ -163,98 +191,99 @@ impl TransactionStorage {
 
     /// Get recent transactions (last N transactions)
     pub async fn get_recent_transactions(&self, limit: usize) -> BeaconResult<Vec<Transaction>> {
-        let mut transactions = Vec::new();
-        let iter = self.db.iter_cf_mode(CF_TRANSACTIONS, rocksdb::IteratorMode::End)?;
-
-        for item in iter {
-            match item {
-                Ok((key, value)) => {
-                    let key_str = String::from_utf8_lossy(&key);
-                    // Only process transaction keys (not result keys)
-                    if key_str.starts_with("tx:") && !key_str.contains(":result") {
-                        if let Ok(transaction) = bincode::deserialize::<Transaction>(&value) {
-                            transactions.push(transaction);
-                            if transactions.len() >= limit {
-                                break;
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error iterating recent transactions: {}", e);
-                    break;
-                }
+        let query = RangeQuery::new(CF_INDICES, b"tx_ts:".to_vec(), limit).reversed();
+        let result = self.db.range_query(&query).await?;
+
+        let mut transactions = Vec::with_capacity(result.items.len());
+        for (_, value) in result.items {
+            let tx_id_str = String::from_utf8_lossy(&value);
+            let tx_id = TransactionId::from_string(tx_id_str.to_string());
+            if let Some(transaction) = self.get_transaction(&tx_id).await? {
+                transactions.push(transaction);
             }
         }
-
-        // Sort by timestamp (newest first)
-        transactions.sort_by(|a, b| b.timestamp.0.cmp(&a.timestamp.0));
         Ok(transactions)
     }
 
-    /// Get transaction count
+    /// Get transaction count. Backed by a running counter maintained on
+    /// store/delete rather than a full `CF_TRANSACTIONS` scan; it reflects
+    /// transactions seen by this instance, not a scan of prior storage state.
     pub async fn get_transaction_count(&self) -> BeaconResult<u64> {
-        let mut count = 0u64;
-        let iter = self.db.iter_cf(CF_TRANSACTIONS)?;
-
-        for item in iter {
-            match item {
-                Ok((key, _)) => {
-                    let key_str = String::from_utf8_lossy(&key);
-                    // Only count transaction keys (not result keys)
-                    if key_str.starts_with("tx:") && !key_str.contains(":result") {
-                        count += 1;
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Error counting transactions: {}", e);
-                    break;
-                }
-            }
-        }
-
-        Ok(count)
+        Ok(self.tx_count.load(Ordering::Relaxed))
     }
 
     /// Delete a transaction (use with caution)
     pub async fn delete_transaction(&self, tx_id: &TransactionId) -> BeaconResult<()> {
         let tx_key = Keys::transaction(tx_id.as_str());
         let result_key = format!("{}:result", tx_id.as_str());
-
-        let mut batch = self.db.create_batch();
-        let tx_cf = self.db.cf_handle(CF_TRANSACTIONS)?;
-
-        batch.delete_cf(tx_cf, &tx_key);
-        batch.delete_cf(tx_cf, result_key.as_bytes());
-
-        self.db.write_batch(batch)?;
+        let existed = self.db.get(CF_TRANSACTIONS, &tx_key).await?.is_some();
+
+        self.db
+            .batch(vec![
+                BatchOp::delete(CF_TRANSACTIONS, tx_key),
+                BatchOp::delete(CF_TRANSACTIONS, result_key.into_bytes()),
+            ])
+            .await?;
+        self.cache.lock().await.pop(tx_id);
+        if existed {
+            self.tx_count.fetch_sub(1, Ordering::Relaxed);
+        }
         tracing::debug!("Deleted transaction: {}", tx_id.as_str());
 
         Ok(())
     }
 
-    /// Create indices for faster querying
+    /// Build the by-sender and by-timestamp secondary indices for every
+    /// transaction currently in `CF_TRANSACTIONS`, applied as one atomic batch.
     pub async fn create_indices(&self) -> BeaconResult<()> {
-        // This would create additional indices for common queries
-        // For now, we'll just log that indices are being created
         tracing::info!("Creating transaction indices");
-        
-        // In a real implementation, you might create indices for:
-        // - Transactions by timestamp
-        // - Transactions by type
-        // - Transactions by chaincode
-        // - etc.
-        
+
+        let mut ops = Vec::new();
+        for (_, value) in self.db.scan_prefix(CF_TRANSACTIONS, b"tx:").await? {
+            if let Ok(transaction) = bincode::deserialize::<Transaction>(&value) {
+                let tx_id_bytes = transaction.id.as_str().as_bytes().to_vec();
+                ops.push(BatchOp::put(
+                    CF_INDICES,
+                    Keys::transaction_by_sender(transaction.from.as_str(), transaction.id.as_str()),
+                    tx_id_bytes.clone(),
+                ));
+                ops.push(BatchOp::put(
+                    CF_INDICES,
+                    Keys::transaction_by_timestamp(transaction.timestamp.0, transaction.id.as_str()),
+                    tx_id_bytes,
+                ));
+            }
+        }
+
+        let indexed = ops.len() / 2;
+        self.db.batch(ops).await?;
+        tracing::info!("Created indices for {} transactions", indexed);
+
         Ok(())
     }
 
-    /// Rebuild indices (for maintenance)
+    /// Rebuild indices (for maintenance): drop the existing by-sender and
+    /// by-timestamp entries and regenerate them from `CF_TRANSACTIONS`, so a
+    /// corrupted or stale secondary index can't linger after `create_indices`.
     pub async fn rebuild_indices(&self) -> BeaconResult<()> {
         tracing::info!("Rebuilding transaction indices");
-        
-        // Clear existing indices
-        // Rebuild from transaction data
-        
+
+        let mut ops = Vec::new();
+        for (key, _) in self.db.scan_prefix(CF_INDICES, b"tx_sender:").await? {
+            ops.push(BatchOp::delete(CF_INDICES, key));
+        }
+        for (key, _) in self.db.scan_prefix(CF_INDICES, b"tx_ts:").await? {
+            ops.push(BatchOp::delete(CF_INDICES, key));
+        }
+        self.db.batch(ops).await?;
+
         self.create_indices().await
     }
 }
+
+/// Transaction cache statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}